@@ -0,0 +1,135 @@
+//! The [wasmedge-macro](https://crates.io/crates/wasmedge-macro) crate defines the procedural macros used by [wasmedge-sys](https://crates.io/crates/wasmedge-sys) crate.
+//!
+//! See also
+//!
+//! * [WasmEdge Runtime](https://wasmedge.org/)
+
+use proc_macro::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, spanned::Spanned, FnArg, ItemFn, Pat, ReturnType, Type};
+
+/// Generates a constructor creating a [host function](https://wasmedge.github.io/WasmEdge/wasmedge_sys/struct.Function.html) from a plain Rust function.
+///
+/// The annotated function is kept as is. Besides, a function named after it with the `_function` suffix is generated, which takes the cost of the host function and returns a `WasmEdgeResult<Function>`. The types of the arguments and returns of the host function are derived from the signature of the annotated function: the arguments must be of `i32`, `i64`, `f32`, or `f64`, and the return type must be one of them, a tuple of them, or `()`. If the host function is called with the mismatched arguments, then it fails with the `FuncSigMismatch` error code.
+///
+/// Notice that the generated code refers to both `wasmedge_sys` and `wasmedge_types` crates.
+///
+/// # Example
+///
+/// ```ignore
+/// use wasmedge_sys::host_function;
+///
+/// #[host_function]
+/// fn add(a: i32, b: i32) -> i32 {
+///     a + b
+/// }
+///
+/// // create a host function of the type `(i32, i32) -> (i32)`
+/// let func = add_function(0).expect("fail to create a host function");
+/// ```
+#[proc_macro_attribute]
+pub fn host_function(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if !attr.is_empty() {
+        return syn::Error::new(
+            proc_macro2::TokenStream::from(attr).span(),
+            "#[host_function] takes no arguments",
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    let item_fn = parse_macro_input!(item as ItemFn);
+    match expand_host_function(&item_fn) {
+        Ok(expanded) => expanded.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+fn expand_host_function(item_fn: &ItemFn) -> syn::Result<proc_macro2::TokenStream> {
+    let sig = &item_fn.sig;
+    if !sig.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            sig.generics.span(),
+            "#[host_function] doesn't support generic functions",
+        ));
+    }
+    if let Some(asyncness) = &sig.asyncness {
+        return Err(syn::Error::new(
+            asyncness.span(),
+            "#[host_function] doesn't support async functions",
+        ));
+    }
+
+    // collect the names and types of the arguments
+    let mut arg_names = vec![];
+    let mut arg_types = vec![];
+    for input in sig.inputs.iter() {
+        match input {
+            FnArg::Typed(arg) => match arg.pat.as_ref() {
+                Pat::Ident(pat) => {
+                    arg_names.push(format_ident!("__{}", pat.ident));
+                    arg_types.push(arg.ty.as_ref().clone());
+                }
+                pat => {
+                    return Err(syn::Error::new(
+                        pat.span(),
+                        "#[host_function] only supports the arguments bound to identifiers",
+                    ))
+                }
+            },
+            FnArg::Receiver(receiver) => {
+                return Err(syn::Error::new(
+                    receiver.span(),
+                    "#[host_function] doesn't support methods",
+                ))
+            }
+        }
+    }
+
+    // collect the types of the returns
+    let return_types = match &sig.output {
+        ReturnType::Default => vec![],
+        ReturnType::Type(_, ty) => match ty.as_ref() {
+            Type::Tuple(tuple) => tuple.elems.iter().cloned().collect(),
+            ty => vec![ty.clone()],
+        },
+    };
+
+    let vis = &item_fn.vis;
+    let name = &sig.ident;
+    let constructor = format_ident!("{}_function", name);
+    let doc = format!(
+        "Creates a host function calling [{}].\n\n# Argument\n\n* `cost` - The function cost in the `Statistics`. Pass 0 if the calculation is not needed.",
+        name
+    );
+
+    Ok(quote! {
+        #item_fn
+
+        #[doc = #doc]
+        #[allow(clippy::let_unit_value)]
+        #vis fn #constructor(
+            cost: u64,
+        ) -> ::wasmedge_types::WasmEdgeResult<::wasmedge_sys::Function> {
+            let mut params: Vec<::wasmedge_types::ValType> = Vec::new();
+            #(params.extend(<#arg_types as ::wasmedge_sys::HostMarshal>::val_types());)*
+            let mut returns: Vec<::wasmedge_types::ValType> = Vec::new();
+            #(returns.extend(<#return_types as ::wasmedge_sys::HostMarshal>::val_types());)*
+            let ty = ::wasmedge_sys::FuncType::create(params, returns)?;
+
+            ::wasmedge_sys::Function::create(
+                &ty,
+                Box::new(|inputs: Vec<::wasmedge_sys::WasmValue>| {
+                    let (#(#arg_names,)*): (#(#arg_types,)*) =
+                        ::wasmedge_sys::UnpackWasmValues::unpack(inputs.as_slice()).map_err(
+                            |_| ::wasmedge_sys::ffi::WasmEdge_ErrCode_FuncSigMismatch as u8,
+                        )?;
+                    Ok(::wasmedge_sys::IntoWasmValues::into_wasm_values(#name(
+                        #(#arg_names),*
+                    )))
+                }),
+                cost,
+            )
+        }
+    })
+}
@@ -73,17 +73,12 @@ impl Table {
     ///
     /// If fail to grow the table, then an error is returned.
     pub fn grow(&mut self, delta: u32, init: Option<Val>) -> WasmEdgeResult<u32> {
-        // get the current size
-        let original_size = self.size();
-        // grow the table by delta
-        self.inner.grow(delta)?;
-        // initialize the new elements
-        if let Some(init) = init {
-            for idx in original_size..original_size + delta {
-                self.inner.set_data(init.clone().into(), idx)?;
-            }
-        }
-        Ok(original_size)
+        // initialize the new elements with null references if `init` is not given
+        let init = match init {
+            Some(init) => init.into(),
+            None => sys::WasmValue::from_null_ref(self.ty()?.elem_ty()),
+        };
+        self.inner.grow(delta, init)
     }
 
     /// Returns the table element at the `index`.
@@ -1,6 +1,10 @@
 use crate::{io::WasmValTypeList, Global, Memory, Table, WasmEdgeResult};
+use std::collections::HashSet;
 use wasmedge_sys::{self as sys, ImportInstance, WasmValue};
-use wasmedge_types::FuncType;
+use wasmedge_types::{
+    error::{InstanceError, WasmEdgeError},
+    FuncType,
+};
 
 /// Creates a normal, wasi, or wasmedge process [import object](crate::ImportObject).
 ///
@@ -169,6 +173,26 @@ impl ImportObjectBuilder {
         Ok(self)
     }
 
+    /// Checks that no export name is used more than once across the functions, globals, memories, and tables added so far.
+    fn check_export_names(&self) -> WasmEdgeResult<()> {
+        let mut names = HashSet::new();
+        let all_names = self
+            .funcs
+            .iter()
+            .map(|(name, _)| name)
+            .chain(self.globals.iter().map(|(name, _)| name))
+            .chain(self.memories.iter().map(|(name, _)| name))
+            .chain(self.tables.iter().map(|(name, _)| name));
+        for name in all_names {
+            if !names.insert(name.as_str()) {
+                return Err(WasmEdgeError::Instance(InstanceError::DuplicateExportName(
+                    name.to_owned(),
+                )));
+            }
+        }
+        Ok(())
+    }
+
     /// Creates a new [ImportObject].
     ///
     /// # Argument
@@ -177,8 +201,10 @@ impl ImportObjectBuilder {
     ///
     /// # Error
     ///
-    /// If fail to create the [ImportObject], then an error is returned.
+    /// If an export name is used more than once, then an `InstanceError::DuplicateExportName` error is returned. If fail to create the [ImportObject], then an error is returned.
     pub fn build(self, name: impl AsRef<str>) -> WasmEdgeResult<ImportObject> {
+        self.check_export_names()?;
+
         let mut inner = sys::ImportModule::create(name.as_ref())?;
 
         // add func
@@ -216,13 +242,15 @@ impl ImportObjectBuilder {
     ///
     /// # Error
     ///
-    /// If fail to create a wasi import module, then an error is returned.
+    /// If an export name is used more than once, then an `InstanceError::DuplicateExportName` error is returned. If fail to create a wasi import module, then an error is returned.
     pub fn build_as_wasi<'a>(
         self,
         args: Option<Vec<&'a str>>,
         envs: Option<Vec<&'a str>>,
         preopens: Option<Vec<&'a str>>,
     ) -> WasmEdgeResult<ImportObject> {
+        self.check_export_names()?;
+
         let mut inner = sys::WasiModule::create(args, envs, preopens)?;
 
         // add func
@@ -258,12 +286,14 @@ impl ImportObjectBuilder {
     ///
     /// # Error
     ///
-    /// If fail to create a wasmedge process import module, then an error is returned.
+    /// If an export name is used more than once, then an `InstanceError::DuplicateExportName` error is returned. If fail to create a wasmedge process import module, then an error is returned.
     pub fn build_as_wasmedge_process(
         self,
         allowed_cmds: Option<Vec<&str>>,
         allowed: bool,
     ) -> WasmEdgeResult<ImportObject> {
+        self.check_export_names()?;
+
         // load plugins from the default paths
         sys::utils::load_plugin_from_default_paths();
 
@@ -327,7 +357,7 @@ mod tests {
         thread,
     };
     use wasmedge_types::{
-        error::{CoreError, CoreInstantiationError, GlobalError, WasmEdgeError},
+        error::{CoreError, CoreInstantiationError, GlobalError, InstanceError, WasmEdgeError},
         GlobalType, MemoryType, Mutability, RefType, TableType, ValType,
     };
 
@@ -518,6 +548,60 @@ mod tests {
         assert_eq!(func_ty.returns().unwrap(), [ValType::I32]);
     }
 
+    #[test]
+    fn test_import_add_funcs() {
+        // create an import object with two host functions
+        let result = ImportObjectBuilder::new()
+            .with_func::<(i32, i32), i32>("add", real_add)
+            .expect("failed to add host func")
+            .with_func::<(i32, i32), i32>("add2", real_add)
+            .expect("failed to add host func")
+            .build("extern");
+        assert!(result.is_ok());
+        let import = result.unwrap();
+
+        // register the import object into a store
+        let result = Executor::new(None, None);
+        assert!(result.is_ok());
+        let mut executor = result.unwrap();
+        let result = Store::new();
+        assert!(result.is_ok());
+        let mut store = result.unwrap();
+        let result = store.register_import_module(&mut executor, &import);
+        assert!(result.is_ok());
+
+        // both host functions are exported
+        let result = store.module_instance("extern");
+        assert!(result.is_some());
+        let instance = result.unwrap();
+        assert!(instance.func("add").is_some());
+        assert!(instance.func("add2").is_some());
+
+        // reject the duplicate export names, even across different kinds of instances
+        let result = ImportObjectBuilder::new()
+            .with_func::<(i32, i32), i32>("add", real_add)
+            .expect("failed to add host func")
+            .with_func::<(i32, i32), i32>("add", real_add)
+            .expect("failed to add host func")
+            .build("extern");
+        assert_eq!(
+            result.unwrap_err(),
+            WasmEdgeError::Instance(InstanceError::DuplicateExportName("add".into()))
+        );
+        let memory = Memory::new(MemoryType::new(1, None));
+        assert!(memory.is_ok());
+        let result = ImportObjectBuilder::new()
+            .with_func::<(i32, i32), i32>("add", real_add)
+            .expect("failed to add host func")
+            .with_memory("add", memory.unwrap())
+            .expect("failed to add memory")
+            .build("extern");
+        assert_eq!(
+            result.unwrap_err(),
+            WasmEdgeError::Instance(InstanceError::DuplicateExportName("add".into()))
+        );
+    }
+
     #[test]
     fn test_import_add_memory() {
         // create a memory
@@ -75,6 +75,16 @@ impl Module {
         Ok(Self { inner })
     }
 
+    /// Returns the length of the bytes from which the [module](crate::Module) was loaded.
+    pub fn bytes_len(&self) -> usize {
+        self.inner.bytes_len()
+    }
+
+    /// Checks if the [module](crate::Module) was loaded from an AOT compiled artifact.
+    pub fn is_aot(&self) -> bool {
+        self.inner.is_aot()
+    }
+
     /// Returns the count of the imported WasmEdge instances in the [module](crate::Module).
     pub fn count_of_imports(&self) -> u32 {
         self.inner.count_of_imports()
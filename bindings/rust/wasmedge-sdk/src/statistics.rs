@@ -63,8 +63,12 @@ impl<'a> Statistics<'a> {
     ///
     /// # Arguments
     ///
-    /// - `cost_table` specifies the slice of cost table.
-    pub fn set_cost_table(&mut self, cost_table: impl AsRef<[u64]>) {
+    /// - `cost_table` specifies the slice of cost table, which covers all the opcodes.
+    ///
+    /// # Error
+    ///
+    /// If the cost table doesn't cover all the opcodes, then an error is returned.
+    pub fn set_cost_table(&mut self, cost_table: impl AsRef<[u64]>) -> WasmEdgeResult<()> {
         self.inner.set_cost_table(cost_table)
     }
 
@@ -5,7 +5,11 @@ use crate::{
     error::{ExportError, ImportError, WasmEdgeError},
     WasmEdgeResult,
 };
-use std::{borrow::Cow, ffi::CStr};
+use std::{
+    borrow::Cow,
+    ffi::CStr,
+    io::{Read, Seek, SeekFrom},
+};
 use wasmedge_types::{
     ExternalInstanceType, FuncType, GlobalType, MemoryType, Mutability, RefType, TableType, ValType,
 };
@@ -19,6 +23,8 @@ use wasmedge_types::{
 #[derive(Debug)]
 pub struct Module {
     pub(crate) inner: InnerModule,
+    pub(crate) bytes_len: usize,
+    pub(crate) aot: bool,
 }
 impl Drop for Module {
     fn drop(&mut self) {
@@ -28,6 +34,29 @@ impl Drop for Module {
     }
 }
 impl Module {
+    /// Creates a [Module] from the given context, which is parsed from the given source bytes.
+    pub(crate) fn from_raw(ctx: *mut ffi::WasmEdge_ASTModuleContext, source: &[u8]) -> Self {
+        Self {
+            inner: InnerModule(ctx),
+            bytes_len: source.len(),
+            aot: is_aot_bytes(source),
+        }
+    }
+
+    /// Returns the length of the bytes from which the [Module] was loaded.
+    ///
+    /// For the [Module] loaded from a file, the length is the size of the file.
+    pub fn bytes_len(&self) -> usize {
+        self.bytes_len
+    }
+
+    /// Checks if the [Module] was loaded from an AOT compiled artifact, i.e., a native shared library or a universal wasm file with the AOT compiled codes in the `wasmedge` custom section.
+    ///
+    /// Notice that WasmEdge provides no API to serialize a [Module] back to wasm bytes, so the applications caching the modules should keep the source bytes themselves.
+    pub fn is_aot(&self) -> bool {
+        self.aot
+    }
+
     /// Returns the number of wasm imports in the [Module].
     pub fn count_of_imports(&self) -> u32 {
         unsafe { ffi::WasmEdge_ASTModuleListImportsLength(self.inner.0) }
@@ -75,6 +104,75 @@ impl Module {
     }
 }
 
+/// Checks if the given bytes are in an AOT compiled format.
+pub(crate) fn is_aot_bytes(bytes: &[u8]) -> bool {
+    is_aot_source(&mut std::io::Cursor::new(bytes))
+}
+
+/// Checks if the source read from the given reader is in an AOT compiled format.
+///
+/// The source without the wasm magic is regarded as a native shared library, since the loader accepts nothing else. The wasm source is in the universal wasm format if it contains a custom section named `wasmedge`. Only the section headers are read, and the contents of the sections are skipped by seeking, so a large source is never loaded as a whole.
+pub(crate) fn is_aot_source(source: &mut (impl Read + Seek)) -> bool {
+    const WASM_MAGIC: [u8; 4] = [0x00, 0x61, 0x73, 0x6D];
+    const AOT_SECTION_NAME: &[u8; 8] = b"wasmedge";
+
+    fn read_byte(source: &mut impl Read) -> Option<u8> {
+        let mut byte = [0u8];
+        source.read_exact(&mut byte).ok()?;
+        Some(byte[0])
+    }
+
+    // reads an unsigned LEB128 integer
+    fn read_u32(source: &mut impl Read) -> Option<u64> {
+        let mut value = 0u64;
+        for shift in (0..35).step_by(7) {
+            let byte = read_byte(source)?;
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Some(value);
+            }
+        }
+        None
+    }
+
+    let mut magic = [0u8; 4];
+    if source.read_exact(&mut magic).is_err() || magic != WASM_MAGIC {
+        return true;
+    }
+
+    // skip the version
+    let mut version = [0u8; 4];
+    if source.read_exact(&mut version).is_err() {
+        return false;
+    }
+    loop {
+        let id = match read_byte(source) {
+            Some(id) => id,
+            None => return false,
+        };
+        let size = match read_u32(source) {
+            Some(size) => size,
+            None => return false,
+        };
+        let start = match source.stream_position() {
+            Ok(start) => start,
+            Err(_) => return false,
+        };
+        if id == 0 && read_u32(source) == Some(AOT_SECTION_NAME.len() as u64) {
+            let mut name = [0u8; 8];
+            if source.read_exact(&mut name).is_ok() && name == *AOT_SECTION_NAME {
+                return true;
+            }
+        }
+        if source
+            .seek(SeekFrom::Start(start.saturating_add(size)))
+            .is_err()
+        {
+            return false;
+        }
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct InnerModule(pub(crate) *mut ffi::WasmEdge_ASTModuleContext);
 unsafe impl Send for InnerModule {}
@@ -392,14 +490,201 @@ pub(crate) struct InnerExportType(pub(crate) *const ffi::WasmEdge_ExportTypeCont
 unsafe impl Send for InnerExportType {}
 unsafe impl Sync for InnerExportType {}
 
+/// Defines the differences between the function imports of a [Module] and a set of registered host functions.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportDiff {
+    /// The `(module_name, func_name)` pairs of the registered host functions which are not imported by the module.
+    pub dead: Vec<(String, String)>,
+    /// The `(module_name, func_name)` pairs of the function imports of the module which have no matching registered host function.
+    pub missing: Vec<(String, String)>,
+}
+impl ImportDiff {
+    /// Returns `true` if every registered host function is imported by the module, and every function import of the module has a matching host function.
+    pub fn is_empty(&self) -> bool {
+        self.dead.is_empty() && self.missing.is_empty()
+    }
+}
+
+/// Compares the function imports of a [Module] with a set of registered host functions, and reports both the dead registrations and the missing imports.
+///
+/// # Arguments
+///
+/// * `module` - The [Module] of which the function imports are compared.
+///
+/// * `registered_funcs` - The `(module_name, func_name)` pairs under which the host functions are registered, for example, the name of an [ImportModule](crate::ImportModule) and the name passed to its [add_func](crate::ImportInstance::add_func).
+pub fn diff_imports(module: &Module, registered_funcs: &[(&str, &str)]) -> ImportDiff {
+    let imported: Vec<(String, String)> = module
+        .imports()
+        .iter()
+        .filter(|import| matches!(import.ty(), Ok(ExternalInstanceType::Func(_))))
+        .map(|import| {
+            (
+                import.module_name().into_owned(),
+                import.name().into_owned(),
+            )
+        })
+        .collect();
+
+    let dead = registered_funcs
+        .iter()
+        .filter(|(mod_name, name)| !imported.iter().any(|(m, n)| m == mod_name && n == name))
+        .map(|(mod_name, name)| (mod_name.to_string(), name.to_string()))
+        .collect();
+    let missing = imported
+        .into_iter()
+        .filter(|(m, n)| {
+            !registered_funcs
+                .iter()
+                .any(|(mod_name, name)| m == mod_name && n == name)
+        })
+        .collect();
+
+    ImportDiff { dead, missing }
+}
+
 #[cfg(test)]
 mod tests {
+    use super::{diff_imports, is_aot_bytes};
     use crate::{Config, Loader};
     use std::{
         sync::{Arc, Mutex},
         thread,
     };
-    use wasmedge_types::{ExternalInstanceType, Mutability, RefType, ValType};
+    use wasmedge_types::{wat2wasm, ExternalInstanceType, Mutability, RefType, ValType};
+
+    #[test]
+    fn test_module_source() {
+        let result = wat2wasm(
+            br#"
+            (module
+                (func (export "add") (param i32 i32) (result i32)
+                    (i32.add (local.get 0) (local.get 1)))
+            )
+"#,
+        );
+        assert!(result.is_ok());
+        let wasm_bytes = result.unwrap();
+
+        let result = Loader::create(None);
+        assert!(result.is_ok());
+        let loader = result.unwrap();
+
+        // load from the in-memory bytes
+        let result = loader.from_bytes(&wasm_bytes);
+        assert!(result.is_ok());
+        let module = result.unwrap();
+        assert_eq!(module.bytes_len(), wasm_bytes.len());
+        assert!(!module.is_aot());
+
+        // persist the bytes and reload them from the file
+        let path = std::env::temp_dir().join(format!(
+            "wasmedge-sys-test-module-source-{}.wasm",
+            std::process::id()
+        ));
+        assert!(std::fs::write(&path, &wasm_bytes).is_ok());
+        let result = loader.from_file(&path);
+        assert!(result.is_ok());
+        let reloaded = result.unwrap();
+        assert_eq!(reloaded.bytes_len(), module.bytes_len());
+        assert!(!reloaded.is_aot());
+        assert_eq!(reloaded.count_of_exports(), module.count_of_exports());
+        assert_eq!(reloaded.exports()[0].name(), "add");
+        assert!(std::fs::remove_file(&path).is_ok());
+    }
+
+    #[test]
+    fn test_module_is_aot_bytes() {
+        // a native shared library
+        assert!(is_aot_bytes(b"\x7fELF\x02\x01\x01\x00"));
+
+        // a plain wasm module
+        let result = wat2wasm(br#"(module (func (export "f")))"#);
+        assert!(result.is_ok());
+        assert!(!is_aot_bytes(&result.unwrap()));
+
+        // a wasm module with an unrelated custom section
+        let result = wat2wasm(br#"(module (@custom "name2" "x") (func (export "f")))"#);
+        assert!(result.is_ok());
+        assert!(!is_aot_bytes(&result.unwrap()));
+
+        // a universal wasm module
+        let result = wat2wasm(br#"(module (func (export "f")) (@custom "wasmedge" "x"))"#);
+        assert!(result.is_ok());
+        assert!(is_aot_bytes(&result.unwrap()));
+
+        // the truncated wasm bytes
+        assert!(!is_aot_bytes(b"\0asm\x01\0\0\0\x00\xff"));
+    }
+
+    #[test]
+    #[cfg(feature = "aot")]
+    fn test_module_source_aot() {
+        let result = wat2wasm(br#"(module (func (export "f")))"#);
+        assert!(result.is_ok());
+        let wasm_bytes = result.unwrap();
+
+        let result = crate::Compiler::create(None);
+        assert!(result.is_ok());
+        let compiler = result.unwrap();
+        let path = std::env::temp_dir().join(format!(
+            "wasmedge-sys-test-module-source-aot-{}.wasm",
+            std::process::id()
+        ));
+        assert!(compiler.compile_from_bytes(&wasm_bytes, &path).is_ok());
+
+        let result = Loader::create(None);
+        assert!(result.is_ok());
+        let loader = result.unwrap();
+        let result = loader.from_file(&path);
+        assert!(result.is_ok());
+        let module = result.unwrap();
+        assert!(module.is_aot());
+        assert!(module.bytes_len() > wasm_bytes.len());
+        assert!(std::fs::remove_file(&path).is_ok());
+    }
+
+    #[test]
+    fn test_module_diff_imports() {
+        let path = std::path::PathBuf::from(env!("WASMEDGE_DIR"))
+            .join("bindings/rust/wasmedge-sys/tests/data/import.wasm");
+
+        let result = Config::create();
+        assert!(result.is_ok());
+        let mut config = result.unwrap();
+        config.bulk_memory_operations(true);
+
+        let result = Loader::create(Some(config));
+        assert!(result.is_ok());
+        let loader = result.unwrap();
+        let result = loader.from_file(path);
+        assert!(result.is_ok());
+        let module = result.unwrap();
+
+        // "extern::func-fail" is not registered, and "extern::func-pow" is not imported
+        let registered = [
+            ("extern", "func-add"),
+            ("extern", "func-sub"),
+            ("extern", "func-mul"),
+            ("extern", "func-div"),
+            ("extern", "func-term"),
+            ("extern", "func-pow"),
+        ];
+        let diff = diff_imports(&module, &registered);
+        assert!(!diff.is_empty());
+        assert_eq!(diff.dead, vec![("extern".into(), "func-pow".into())]);
+        assert_eq!(diff.missing, vec![("extern".into(), "func-fail".into())]);
+
+        // the non-function imports are ignored
+        let registered = [
+            ("extern", "func-add"),
+            ("extern", "func-sub"),
+            ("extern", "func-mul"),
+            ("extern", "func-div"),
+            ("extern", "func-term"),
+            ("extern", "func-fail"),
+        ];
+        assert!(diff_imports(&module, &registered).is_empty());
+    }
 
     #[test]
     fn test_module_import() {
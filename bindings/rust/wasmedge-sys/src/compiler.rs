@@ -3,6 +3,10 @@
 use crate::{error::WasmEdgeError, ffi, utils, utils::check, Config, WasmEdgeResult};
 use std::path::Path;
 
+/// The counter used to name the temporary input files of [Compiler::compile_from_bytes].
+#[cfg(feature = "aot")]
+static TEMP_FILE_COUNTER: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
 /// Defines WasmEdge ahead-of-time(AOT) compiler and the relevant APIs.
 #[cfg(feature = "aot")]
 #[derive(Debug)]
@@ -19,6 +23,12 @@ impl Drop for Compiler {
 impl Compiler {
     /// Creates a new AOT [compiler](crate::Compiler).
     ///
+    /// The output format, the optimization level, and the dump IR option of the compiler are taken from the given [Config](crate::Config). See [Config::set_aot_compiler_output_format](crate::Config::set_aot_compiler_output_format), [Config::set_aot_optimization_level](crate::Config::set_aot_optimization_level), and [Config::dump_ir](crate::Config::dump_ir).
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration of the compiler. If `None` is given, the default configuration is used.
+    ///
     /// # Error
     ///
     /// If fail to create a AOT [compiler](crate::Compiler), then an error is returned.
@@ -68,6 +78,38 @@ impl Compiler {
             ))
         }
     }
+
+    /// The compiler compiles the input WASM from the given in-memory bytes for the AOT mode and stores the result to the output file path.
+    ///
+    /// Since the WasmEdge C API compiles files only, the bytes are written to a temporary file which is removed after compiling.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The in-memory WASM bytes.
+    ///
+    /// * `out_path` - The output WASM file path.
+    ///
+    /// # Error
+    ///
+    /// If fail to write the temporary file, then a `WasmEdgeError::Io` error is returned. If fail to compile, then an error is returned.
+    #[cfg(feature = "aot")]
+    pub fn compile_from_bytes(
+        &self,
+        bytes: impl AsRef<[u8]>,
+        out_path: impl AsRef<Path>,
+    ) -> WasmEdgeResult<()> {
+        let in_path = std::env::temp_dir().join(format!(
+            "wasmedge-aot-{}-{}.wasm",
+            std::process::id(),
+            TEMP_FILE_COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed)
+        ));
+        std::fs::write(&in_path, bytes.as_ref())
+            .map_err(|e| WasmEdgeError::Io(format!("{}: {}", in_path.display(), e)))?;
+
+        let result = self.compile(&in_path, out_path);
+        let _ = std::fs::remove_file(&in_path);
+        result
+    }
 }
 
 #[cfg(feature = "aot")]
@@ -82,14 +124,14 @@ mod tests {
     use super::*;
     use crate::{
         error::{CoreError, CoreLoadError},
-        Config,
+        Config, Vm, WasmValue,
     };
     use std::{
         io::Read,
         sync::{Arc, Mutex},
         thread,
     };
-    use wasmedge_types::CompilerOutputFormat;
+    use wasmedge_types::{wat2wasm, CompilerOptimizationLevel, CompilerOutputFormat};
 
     #[test]
     fn test_compiler() {
@@ -158,6 +200,112 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_compiler_from_bytes() {
+        let result = Config::create();
+        assert!(result.is_ok());
+        let mut config = result.unwrap();
+        config.set_aot_compiler_output_format(CompilerOutputFormat::Native);
+        let result = Compiler::create(Some(config));
+        assert!(result.is_ok());
+        let compiler = result.unwrap();
+
+        // compile in-memory wasm bytes to a shared library
+        let result = wat2wasm(
+            br#"
+            (module
+                (func (export "add") (param i32 i32) (result i32)
+                    (i32.add (local.get 0) (local.get 1)))
+            )
+"#,
+        );
+        assert!(result.is_ok());
+        let wasm_bytes = result.unwrap();
+        let out_path =
+            std::env::temp_dir().join(format!("wasmedge-sys-test-aot-{}.so", std::process::id()));
+        let result = compiler.compile_from_bytes(&wasm_bytes, &out_path);
+        assert!(result.is_ok());
+        assert!(out_path.exists());
+
+        // the compiled artifact is loaded and run transparently
+        let result = Vm::create(None, None);
+        assert!(result.is_ok());
+        let mut vm = result.unwrap();
+        let result = vm.run_wasm_from_file(
+            &out_path,
+            "add",
+            [WasmValue::from_i32(2), WasmValue::from_i32(3)],
+        );
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap()[0].to_i32(), 5);
+        assert!(std::fs::remove_file(&out_path).is_ok());
+
+        // the compiler errors are surfaced
+        let result = compiler.compile_from_bytes(b"not a wasm module", "not_exist_aot.so");
+        assert!(result.is_err());
+        assert!(matches!(
+            result.unwrap_err(),
+            WasmEdgeError::Core(CoreError::Load(_))
+        ));
+        assert!(!std::path::Path::new("not_exist_aot.so").exists());
+    }
+
+    #[test]
+    fn test_compiler_optimization_level() {
+        let result = wat2wasm(
+            br#"
+            (module
+                (func (export "add") (param i32 i32) (result i32)
+                    (i32.add (local.get 0) (local.get 1)))
+            )
+"#,
+        );
+        assert!(result.is_ok());
+        let wasm_bytes = result.unwrap();
+
+        for (opt_level, suffix) in [
+            (CompilerOptimizationLevel::O0, "o0"),
+            (CompilerOptimizationLevel::O3, "o3"),
+        ] {
+            let result = Config::create();
+            assert!(result.is_ok());
+            let mut config = result.unwrap();
+            config.set_aot_compiler_output_format(CompilerOutputFormat::Wasm);
+            config.set_aot_optimization_level(opt_level);
+            config.dump_ir(false);
+            let result = Compiler::create(Some(config));
+            assert!(result.is_ok());
+            let compiler = result.unwrap();
+
+            let out_path = std::env::temp_dir().join(format!(
+                "wasmedge-sys-test-aot-{}-{}.wasm",
+                suffix,
+                std::process::id()
+            ));
+            let result = compiler.compile_from_bytes(&wasm_bytes, &out_path);
+            assert!(result.is_ok());
+            assert!(out_path.exists());
+
+            // the universal wasm output keeps the wasm magic
+            let result = std::fs::read(&out_path);
+            assert!(result.is_ok());
+            assert_eq!(&result.unwrap()[..4], &[0x00, 0x61, 0x73, 0x6D]);
+
+            // the artifact is loadable and runnable
+            let result = Vm::create(None, None);
+            assert!(result.is_ok());
+            let mut vm = result.unwrap();
+            let result = vm.run_wasm_from_file(
+                &out_path,
+                "add",
+                [WasmValue::from_i32(2), WasmValue::from_i32(3)],
+            );
+            assert!(result.is_ok());
+            assert_eq!(result.unwrap()[0].to_i32(), 5);
+            assert!(std::fs::remove_file(&out_path).is_ok());
+        }
+    }
+
     #[test]
     #[ignore]
     fn test_compiler_send() {
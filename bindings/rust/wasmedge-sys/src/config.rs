@@ -210,6 +210,8 @@ impl Config {
 
     /// Enables or disables host registration wasi.
     ///
+    /// If enabled, a [Vm](crate::Vm) created with this configuration registers the built-in wasi module automatically, so the wasi imports of the wasm modules are resolved without creating a [WasiModule](crate::WasiModule) manually. The registered module can be retrieved by [Vm::wasi_module_mut](crate::Vm::wasi_module_mut).
+    ///
     /// # Argument
     ///
     /// * `enable` - Whether the option turns on or not.
@@ -2,16 +2,26 @@
 
 use super::ffi;
 use crate::{
-    error::WasmEdgeError, instance::module::InnerInstance, types::WasmEdgeString, utils::check,
+    error::{FuncError, StoreError, WasmEdgeError},
+    instance::{
+        function::{charge_host_costs_to, clear_host_error, take_host_error},
+        module::InnerInstance,
+    },
+    statistics::HostCost,
+    types::WasmEdgeString,
+    utils::check,
     Config, Engine, FuncRef, Function, ImportObject, Instance, Module, Statistics, Store,
     WasmEdgeResult, WasmValue,
 };
+use std::sync::Arc;
 
 /// Defines an execution environment for both pure WASM and compiled WASM.
 #[derive(Debug)]
 pub struct Executor {
     pub(crate) inner: InnerExecutor,
     pub(crate) registered: bool,
+    // the counter of the computed costs of host functions in the attached statistics
+    pub(crate) host_cost: Option<Arc<HostCost>>,
 }
 impl Executor {
     /// Creates a new [executor](crate::Executor) to be associated with the given [config](crate::Config) and [statistics](crate::Statistics).
@@ -26,6 +36,7 @@ impl Executor {
     ///
     /// If fail to create a [executor](crate::Executor), then an error is returned.
     pub fn create(config: Option<Config>, stat: Option<&mut Statistics>) -> WasmEdgeResult<Self> {
+        let host_cost = stat.as_ref().map(|stat| Arc::clone(&stat.host_cost));
         let ctx = match config {
             Some(mut config) => match stat {
                 Some(stat) => {
@@ -56,6 +67,7 @@ impl Executor {
             false => Ok(Executor {
                 inner: InnerExecutor(ctx),
                 registered: false,
+                host_cost,
             }),
         }
     }
@@ -117,13 +129,14 @@ impl Executor {
     ///
     /// # Error
     ///
-    /// If fail to register the given [module](crate::Module), then an error is returned.
+    /// If the [module](crate::Module) imports from a module not registered in the [store](crate::Store), then a `StoreError::NotFoundModule` error with the name of the missing module is returned. If fail to register the given [module](crate::Module), then an error is returned.
     pub fn register_named_module(
         &mut self,
         store: &mut Store,
         module: &Module,
         name: impl AsRef<str>,
     ) -> WasmEdgeResult<Instance> {
+        check_import_modules(store, module)?;
         let mut instance_ctx = std::ptr::null_mut();
         let mod_name: WasmEdgeString = name.as_ref().into();
         unsafe {
@@ -156,12 +169,13 @@ impl Executor {
     ///
     /// # Error
     ///
-    /// If fail to instantiate the given [module](crate::Module), then an error is returned.
+    /// If the [module](crate::Module) imports from a module not registered in the [store](crate::Store), then a `StoreError::NotFoundModule` error with the name of the missing module is returned. If fail to instantiate the given [module](crate::Module), then an error is returned.
     pub fn register_active_module(
         &mut self,
         store: &mut Store,
         module: &Module,
     ) -> WasmEdgeResult<Instance> {
+        check_import_modules(store, module)?;
         let mut instance_ctx = std::ptr::null_mut();
         unsafe {
             check(ffi::WasmEdge_ExecutorInstantiate(
@@ -176,6 +190,73 @@ impl Executor {
             registered: false,
         })
     }
+
+    /// Runs the given [functions](crate::Function) sequentially, and returns the returns of each call in order.
+    ///
+    /// Compared with calling [run_func](crate::Engine::run_func) in a loop, the buffers for the arguments and returns are reused across the calls.
+    ///
+    /// # Arguments
+    ///
+    /// * `calls` - The functions to run and the arguments passed to each of them.
+    ///
+    /// # Error
+    ///
+    /// If any call fails, then the remaining calls are skipped, and a `FuncError::BatchCallFailed` error carrying the index of the failed call and its error is returned.
+    pub fn run_funcs(
+        &mut self,
+        calls: &[(&Function, Vec<WasmValue>)],
+    ) -> WasmEdgeResult<Vec<Vec<WasmValue>>> {
+        let mut results = Vec::with_capacity(calls.len());
+        let mut raw_params = Vec::new();
+        let mut raw_returns = Vec::new();
+        for (index, (func, params)) in calls.iter().enumerate() {
+            let returns = self
+                .invoke_with(func, params, &mut raw_params, &mut raw_returns)
+                .map_err(|error| {
+                    WasmEdgeError::Func(FuncError::BatchCallFailed {
+                        index,
+                        error: Box::new(error),
+                    })
+                })?;
+            results.push(returns);
+        }
+
+        Ok(results)
+    }
+
+    /// Runs a [function](crate::Function) with the given buffers for the raw arguments and returns.
+    fn invoke_with(
+        &mut self,
+        func: &Function,
+        params: &[WasmValue],
+        raw_params: &mut Vec<ffi::WasmEdge_Value>,
+        raw_returns: &mut Vec<ffi::WasmEdge_Value>,
+    ) -> WasmEdgeResult<Vec<WasmValue>> {
+        raw_params.clear();
+        raw_params.extend(params.iter().map(|x| x.as_raw()));
+
+        // get the length of the function's returns
+        let returns_len = func.ty()?.returns_len();
+        raw_returns.clear();
+        raw_returns.reserve(returns_len as usize);
+
+        clear_host_error();
+        let _guard = charge_host_costs_to(self.host_cost.clone());
+        unsafe {
+            check(ffi::WasmEdge_ExecutorInvoke(
+                self.inner.0,
+                func.inner.0 as *const _,
+                raw_params.as_ptr(),
+                raw_params.len() as u32,
+                raw_returns.as_mut_ptr(),
+                returns_len,
+            ))
+            .map_err(take_host_error)?;
+            raw_returns.set_len(returns_len as usize);
+        }
+
+        Ok(raw_returns.drain(..).map(Into::into).collect())
+    }
 }
 impl Drop for Executor {
     fn drop(&mut self) {
@@ -197,6 +278,8 @@ impl Engine for Executor {
         let returns_len = func_ty.returns_len();
         let mut returns = Vec::with_capacity(returns_len as usize);
 
+        clear_host_error();
+        let _guard = charge_host_costs_to(self.host_cost.clone());
         unsafe {
             check(ffi::WasmEdge_ExecutorInvoke(
                 self.inner.0,
@@ -205,7 +288,8 @@ impl Engine for Executor {
                 raw_params.len() as u32,
                 returns.as_mut_ptr(),
                 returns_len,
-            ))?;
+            ))
+            .map_err(take_host_error)?;
             returns.set_len(returns_len as usize);
         }
 
@@ -224,6 +308,8 @@ impl Engine for Executor {
         let returns_len = func_ty.returns_len();
         let mut returns = Vec::with_capacity(returns_len as usize);
 
+        clear_host_error();
+        let _guard = charge_host_costs_to(self.host_cost.clone());
         unsafe {
             check(ffi::WasmEdge_ExecutorInvoke(
                 self.inner.0,
@@ -232,7 +318,8 @@ impl Engine for Executor {
                 raw_params.len() as u32,
                 returns.as_mut_ptr(),
                 returns_len,
-            ))?;
+            ))
+            .map_err(take_host_error)?;
             returns.set_len(returns_len as usize);
         }
 
@@ -245,18 +332,41 @@ pub(crate) struct InnerExecutor(pub(crate) *mut ffi::WasmEdge_ExecutorContext);
 unsafe impl Send for InnerExecutor {}
 unsafe impl Sync for InnerExecutor {}
 
+/// Checks if all the modules the given [module](crate::Module) imports from are registered in the given [store](crate::Store).
+fn check_import_modules(store: &Store, module: &Module) -> WasmEdgeResult<()> {
+    let imports = module.imports();
+    let not_found =
+        |mod_name: &str| WasmEdgeError::Store(StoreError::NotFoundModule(mod_name.to_string()));
+    let first = match imports.first() {
+        Some(first) => first,
+        None => return Ok(()),
+    };
+    // a store without any registered module satisfies none of the imports
+    let registered = store
+        .module_names()
+        .ok_or_else(|| not_found(&first.module_name()))?;
+    for import in imports.iter() {
+        let mod_name = import.module_name();
+        if !registered.iter().any(|name| *name == mod_name) {
+            return Err(not_found(&mod_name));
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
-        Config, FuncType, Function, Global, GlobalType, ImportInstance, ImportModule, MemType,
-        Memory, Statistics, Table, TableType,
+        error::{self, CoreError, CoreExecutionError, StoreError},
+        Config, FuncType, Function, Global, GlobalType, ImportInstance, ImportModule, Loader,
+        MemType, Memory, Statistics, Table, TableType, Validator,
     };
     use std::{
         sync::{Arc, Mutex},
         thread,
     };
-    use wasmedge_types::{Mutability, RefType, ValType};
+    use wasmedge_types::{wat2wasm, Mutability, RefType, ValType};
 
     #[test]
     fn test_executor_create() {
@@ -447,6 +557,209 @@ mod tests {
         handle.join().unwrap();
     }
 
+    #[test]
+    fn test_executor_run_funcs() {
+        let module = load_module(
+            br#"
+            (module
+                (func (export "add") (param i32 i32) (result i32)
+                    (i32.add (local.get 0) (local.get 1)))
+            )
+"#,
+        );
+
+        let result = Store::create();
+        assert!(result.is_ok());
+        let mut store = result.unwrap();
+        let result = Executor::create(None, None);
+        assert!(result.is_ok());
+        let mut executor = result.unwrap();
+        let result = executor.register_active_module(&mut store, &module);
+        assert!(result.is_ok());
+        let instance = result.unwrap();
+        let result = instance.get_func("add");
+        assert!(result.is_ok());
+        let add = result.unwrap();
+
+        // the results are in the order of the calls
+        let calls = [
+            (&add, vec![WasmValue::from_i32(1), WasmValue::from_i32(2)]),
+            (&add, vec![WasmValue::from_i32(3), WasmValue::from_i32(4)]),
+            (&add, vec![WasmValue::from_i32(5), WasmValue::from_i32(6)]),
+        ];
+        let result = executor.run_funcs(&calls);
+        assert!(result.is_ok());
+        let results = result.unwrap();
+        assert_eq!(results.len(), 3);
+        let sums = results.iter().map(|r| r[0].to_i32()).collect::<Vec<_>>();
+        assert_eq!(sums, [3, 7, 11]);
+
+        // an empty batch
+        let result = executor.run_funcs(&[]);
+        assert!(result.is_ok());
+        assert!(result.unwrap().is_empty());
+
+        // the batch stops at the failed call
+        let calls = [
+            (&add, vec![WasmValue::from_i32(1), WasmValue::from_i32(2)]),
+            (&add, vec![WasmValue::from_i32(3)]),
+            (&add, vec![WasmValue::from_i32(5), WasmValue::from_i32(6)]),
+        ];
+        let result = executor.run_funcs(&calls);
+        assert!(result.is_err());
+        match result.unwrap_err() {
+            WasmEdgeError::Func(FuncError::BatchCallFailed { index, error }) => {
+                assert_eq!(index, 1);
+                assert!(matches!(
+                    *error,
+                    WasmEdgeError::Core(CoreError::Execution { .. })
+                ));
+            }
+            err => panic!("unexpected error: {}", err),
+        }
+    }
+
+    #[test]
+    fn test_executor_register_named_module_imports() {
+        let math = load_module(
+            br#"
+            (module
+                (func (export "add") (param i32 i32) (result i32)
+                    (i32.add (local.get 0) (local.get 1)))
+            )
+"#,
+        );
+        let app = load_module(
+            br#"
+            (module
+                (import "math" "add" (func $add (param i32 i32) (result i32)))
+                (func (export "add_three") (param i32 i32 i32) (result i32)
+                    (call $add (call $add (local.get 0) (local.get 1)) (local.get 2)))
+            )
+"#,
+        );
+
+        // the import from an unregistered module fails
+        let result = Store::create();
+        assert!(result.is_ok());
+        let mut store = result.unwrap();
+        let result = Executor::create(None, None);
+        assert!(result.is_ok());
+        let mut executor = result.unwrap();
+        let result = executor.register_active_module(&mut store, &app);
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err(),
+            WasmEdgeError::Store(StoreError::NotFoundModule("math".into()))
+        );
+        let result = executor.register_named_module(&mut store, &app, "app");
+        assert_eq!(
+            result.unwrap_err(),
+            WasmEdgeError::Store(StoreError::NotFoundModule("math".into()))
+        );
+        assert!(!store.contains("app"));
+
+        // register the module to import from
+        let result = executor.register_named_module(&mut store, &math, "math");
+        assert!(result.is_ok());
+        assert_eq!(store.module_names(), Some(vec![String::from("math")]));
+        let result = store.module("math");
+        assert!(result.is_ok());
+        assert!(result.unwrap().get_func("add").is_ok());
+
+        // the imports are resolved by the registered module
+        let result = executor.register_active_module(&mut store, &app);
+        assert!(result.is_ok());
+        let instance = result.unwrap();
+        let result = instance.get_func("add_three");
+        assert!(result.is_ok());
+        let add_three = result.unwrap();
+        let result = executor.run_func(
+            &add_three,
+            [
+                WasmValue::from_i32(1),
+                WasmValue::from_i32(2),
+                WasmValue::from_i32(3),
+            ],
+        );
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap()[0].to_i32(), 6);
+    }
+
+    #[test]
+    fn test_executor_trap_kind() {
+        let module = load_module(
+            br#"
+            (module
+                (func $div (export "div") (param i32 i32) (result i32)
+                    (i32.div_s (local.get 0) (local.get 1)))
+                (func (export "call_div") (param i32 i32) (result i32)
+                    (call $div (local.get 0) (local.get 1)))
+                (func (export "trap") unreachable)
+            )
+"#,
+        );
+        let result = Store::create();
+        assert!(result.is_ok());
+        let mut store = result.unwrap();
+        let result = Executor::create(None, None);
+        assert!(result.is_ok());
+        let mut executor = result.unwrap();
+        let result = executor.register_active_module(&mut store, &module);
+        assert!(result.is_ok());
+        let instance = result.unwrap();
+
+        // the trap in a nested call is reported by its kind
+        for name in ["div", "call_div"] {
+            let result = instance.get_func(name);
+            assert!(result.is_ok());
+            let func = result.unwrap();
+            let result = executor.run_func(&func, [WasmValue::from_i32(1), WasmValue::from_i32(0)]);
+            assert!(result.is_err());
+            let err = result.unwrap_err();
+            // the C API provides no frames, so the trace is present but empty
+            assert_eq!(
+                err,
+                WasmEdgeError::Core(CoreError::Execution {
+                    kind: CoreExecutionError::DivideByZero,
+                    trace: vec![],
+                })
+            );
+            assert_eq!(err.to_string(), "integer divide by zero");
+            assert_eq!(err.retryability(), error::Retryability::GuestTrap);
+        }
+
+        let result = instance.get_func("trap");
+        assert!(result.is_ok());
+        let func = result.unwrap();
+        let result = executor.run_func(&func, []);
+        assert_eq!(
+            result.unwrap_err(),
+            WasmEdgeError::Core(CoreError::Execution {
+                kind: CoreExecutionError::Unreachable,
+                trace: vec![],
+            })
+        );
+    }
+
+    // compiles the module in the WAT format to wasm, and returns the loaded and validated module
+    fn load_module(wat: &[u8]) -> Module {
+        let result = wat2wasm(wat);
+        assert!(result.is_ok());
+        let wasm_bytes = result.unwrap();
+        let result = Loader::create(None);
+        assert!(result.is_ok());
+        let loader = result.unwrap();
+        let result = loader.from_bytes(wasm_bytes);
+        assert!(result.is_ok());
+        let module = result.unwrap();
+        let result = Validator::create(None);
+        assert!(result.is_ok());
+        let validator = result.unwrap();
+        assert!(validator.validate(&module).is_ok());
+        module
+    }
+
     fn real_add(inputs: Vec<WasmValue>) -> Result<Vec<WasmValue>, u8> {
         if inputs.len() != 2 {
             return Err(1);
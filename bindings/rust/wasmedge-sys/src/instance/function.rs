@@ -1,26 +1,44 @@
 //! Defines WasmEdge Function and FuncType structs.
 
 use crate::{
-    error::{FuncError, WasmEdgeError},
-    ffi, BoxedFn, Engine, WasmEdgeResult, WasmValue, HOST_FUNCS,
+    error::{CoreError, CoreExecutionError, FuncError, HostTrap, WasmEdgeError},
+    ffi,
+    statistics::HostCost,
+    BoxedArgFilter, BoxedAsyncFn, BoxedCapacityHandler, BoxedCostFn, BoxedFn, BoxedFnMut,
+    BoxedMemFn, BoxedRecorder, BoxedStreamFn, Engine, Executor, HostFuncSignature, HostFuncStats,
+    HostMarshal, HostRegistry, MemoryMut, RawHostFn, WasmEdgeResult, WasmValue, HOST_REGISTRY,
 };
 use core::ffi::c_void;
 use rand::Rng;
-use std::convert::TryInto;
-use wasmedge_types::ValType;
+use std::{
+    collections::HashMap,
+    convert::TryInto,
+    panic::AssertUnwindSafe,
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        mpsc::{RecvTimeoutError, Sender},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+use wasmedge_types::{RefType, ValType};
 
 extern "C" fn wraper_fn(
     key_ptr: *mut c_void,
     _data: *mut c_void,
-    _mem_cxt: *mut ffi::WasmEdge_MemoryInstanceContext,
+    mem_cxt: *mut ffi::WasmEdge_MemoryInstanceContext,
     params: *const ffi::WasmEdge_Value,
     param_len: u32,
     returns: *mut ffi::WasmEdge_Value,
     return_len: u32,
 ) -> ffi::WasmEdge_Result {
+    let registry = host_registry();
     let key = key_ptr as *const usize as usize;
 
-    let input = {
+    HOST_CALLS_IN_THREAD.with(|count| count.set(count.get() + 1));
+    TOTAL_HOST_CALLS.fetch_add(1, Ordering::Relaxed);
+
+    let mut input = {
         let raw_input = unsafe {
             std::slice::from_raw_parts(
                 params,
@@ -32,22 +50,123 @@ extern "C" fn wraper_fn(
         raw_input.iter().map(|r| (*r).into()).collect::<Vec<_>>()
     };
 
+    // keep the arguments of the last calls for crash forensics
+    #[cfg(feature = "forensics")]
+    if let Some((ring_size, ring)) = registry
+        .forensics
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get_mut(&key)
+    {
+        if ring.len() == *ring_size {
+            ring.pop_front();
+        }
+        ring.push_back(input.clone());
+    }
+
     let return_len = return_len
         .try_into()
         .expect("len of returns should not greater than usize");
     let raw_returns = unsafe { std::slice::from_raw_parts_mut(returns, return_len) };
 
-    let result = {
-        let host_functions = HOST_FUNCS.lock().expect("[wasmedge-sys] try lock failed.");
-        let real_fn = host_functions
+    // expose the memory of the caller during the call
+    let caller_memory = CALLER_MEMORY.with(|memory| memory.replace(mem_cxt));
+    // run all the user callbacks under the same guard, since a panic must never unwind across the C frames of WasmEdge
+    let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+        // rewrite the arguments before dispatching them; release the lock before the filter runs
+        let arg_filter = registry
+            .arg_filters
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
             .get(&key)
-            .expect("host function should be there");
-        real_fn(input)
-    };
+            .cloned();
+        if let Some(filter) = arg_filter {
+            filter(&mut input);
+        }
+
+        // charge the cost computed from the arguments before the function body runs
+        let cost_fn = registry
+            .cost_fns
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&key)
+            .map(|(cost_fn, _)| Arc::clone(cost_fn));
+        let (computed_cost, within_limit) = match cost_fn {
+            Some(cost_fn) => {
+                let cost = cost_fn(&input);
+                if let Some((_, charged)) = registry
+                    .cost_fns
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .get_mut(&key)
+                {
+                    *charged += cost;
+                }
+                // the C API can't add to the total cost, so the statistics of the caller keeps it aside
+                let within_limit = CALLER_COST.with(|counter| match counter.borrow().as_ref() {
+                    Some(counter) => counter.charge(cost),
+                    None => true,
+                });
+                (cost, within_limit)
+            }
+            None => (0, true),
+        };
+        if let Some(stats) = registry
+            .stats
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get_mut(&key)
+        {
+            stats.calls += 1;
+            stats.cost += stats.static_cost + computed_cost;
+        }
+
+        // the body doesn't run once the cost limit is exceeded, like the instructions
+        if !within_limit {
+            return Err(ffi::WasmEdge_ErrCode_CostLimitExceeded as u8);
+        }
+
+        // release the lock before the closure runs, so the closure can call back into wasm
+        let real_fn = lock_recovered(&registry.funcs).get(&key).cloned();
+        match real_fn {
+            Some(real_fn) => real_fn(input),
+            // the host function has been removed from the registry
+            None => Err(ffi::WasmEdge_ErrCode_ExecutionFailed as u8),
+        }
+    }))
+    .unwrap_or_else(|payload| {
+        let msg = payload
+            .downcast_ref::<&str>()
+            .copied()
+            .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+            .unwrap_or("unknown panic payload");
+        log::error!("host function {:#x} panicked: {}", key, msg);
+        // keep the panic message, so the caller can tell why the call failed
+        let code = ffi::WasmEdge_ErrCode_ExecutionFailed as u8;
+        let message = format!("host function {:#x} panicked: {}", key, msg);
+        LAST_HOST_ERROR.with(|last| *last.borrow_mut() = Some(FuncError::Host { code, message }));
+        Err(code)
+    });
+    CALLER_MEMORY.with(|memory| memory.set(caller_memory));
 
     match result {
+        // the cap takes precedence over the check of the number of the returns
+        Ok(v) if v.len() > MAX_HOST_RESULT_LEN.load(Ordering::Relaxed) => ffi::WasmEdge_Result {
+            Code: raise_host_trap(HostTrap::ResultTooLarge),
+        },
+        Ok(v) if v.len() != return_len => {
+            let code = ffi::WasmEdge_ErrCode_FuncSigMismatch as u8;
+            let message = format!(
+                "host function {:#x} returned {} values, but {} are declared",
+                key,
+                v.len(),
+                return_len
+            );
+            LAST_HOST_ERROR
+                .with(|last| *last.borrow_mut() = Some(FuncError::Host { code, message }));
+            ffi::WasmEdge_Result { Code: code }
+        }
         Ok(v) => {
-            assert!(v.len() == return_len);
             for (idx, item) in v.into_iter().enumerate() {
                 raw_returns[idx] = item.as_raw();
             }
@@ -57,6 +176,475 @@ extern "C" fn wraper_fn(
     }
 }
 
+thread_local! {
+    // The number of the host function calls made in the current thread.
+    static HOST_CALLS_IN_THREAD: std::cell::Cell<u64> = const { std::cell::Cell::new(0) };
+    // The error of the last failed host function call made in the current thread, with its message.
+    static LAST_HOST_ERROR: std::cell::RefCell<Option<FuncError>> = const { std::cell::RefCell::new(None) };
+    // The memory of the caller of the host function being called in the current thread.
+    static CALLER_MEMORY: std::cell::Cell<*mut ffi::WasmEdge_MemoryInstanceContext> =
+        const { std::cell::Cell::new(std::ptr::null_mut()) };
+    // The accounting of the computed costs in the statistics of the executor running in the current thread.
+    static CALLER_COST: std::cell::RefCell<Option<Arc<HostCost>>> = const { std::cell::RefCell::new(None) };
+}
+
+// Charges the costs computed by the host functions called in the current thread to the given accounting, until the returned guard is dropped.
+pub(crate) fn charge_host_costs_to(counter: Option<Arc<HostCost>>) -> HostCostGuard {
+    HostCostGuard(CALLER_COST.with(|caller| caller.replace(counter)))
+}
+
+// Restores the counter of the outer call on drop, since a host function may call back into wasm.
+pub(crate) struct HostCostGuard(Option<Arc<HostCost>>);
+impl Drop for HostCostGuard {
+    fn drop(&mut self) {
+        let counter = self.0.take();
+        CALLER_COST.with(|caller| *caller.borrow_mut() = counter);
+    }
+}
+
+// The number of the host function calls made in all threads.
+static TOTAL_HOST_CALLS: AtomicU64 = AtomicU64::new(0);
+
+// The maximum number of the returns a host function call is accepted with.
+static MAX_HOST_RESULT_LEN: AtomicUsize = AtomicUsize::new(usize::MAX);
+
+/// Returns the number of the host function calls made in the current thread.
+pub(crate) fn host_calls_in_thread() -> u64 {
+    HOST_CALLS_IN_THREAD.with(|count| count.get())
+}
+
+/// Sets the maximum number of the returns a host function call is accepted with.
+///
+/// A host function call returning more values than `n` traps with [HostTrap::ResultTooLarge] instead of being checked against the number of the returns of the function, which guards against the buggy host functions producing enormous results. The host functions created by [create_streaming](crate::Function::create_streaming) stop producing their returns once the cap is exceeded, so the oversized result is never buffered. The other host functions build their whole result before it is checked, so the cap only reports it as a distinct trap rather than saving the memory. The cap applies to all host functions, and is unlimited by default. Pass `usize::MAX` to remove the cap.
+///
+/// # Argument
+///
+/// * `n` - The maximum number of the returns.
+pub fn set_max_host_result_len(n: usize) {
+    MAX_HOST_RESULT_LEN.store(n, Ordering::Relaxed);
+}
+
+/// Sets the capacity of the host function registry, which is the maximum number of the host functions alive at a time.
+///
+/// The initial capacity is read from the `MAX_HOST_FUNC_LENGTH` environment variable, and defaults to 500. Raising the capacity reserves the space in advance, so it is best called before any host functions are created. Notice that the registry may round the capacity up, so [host_func_capacity] may report a capacity larger than `n`.
+///
+/// # Argument
+///
+/// * `n` - The new capacity.
+///
+/// # Error
+///
+/// If `n` is less than the number of the registered host functions, then a `FuncError::CapacityBelowLength` error is returned, and the capacity is left unchanged.
+pub fn set_host_func_capacity(n: usize) -> WasmEdgeResult<()> {
+    let registry = host_registry();
+    let mut host_functions = lock_recovered(&registry.funcs);
+    let len = host_functions.len();
+    if n < len {
+        return Err(WasmEdgeError::Func(FuncError::CapacityBelowLength(n, len)));
+    }
+    match n > host_functions.capacity() {
+        true => host_functions.reserve(n - len),
+        false => host_functions.shrink_to(n),
+    }
+
+    // the side tables grow along with the registry
+    let mut func_types = registry.types.lock().unwrap_or_else(|e| e.into_inner());
+    let additional = n.saturating_sub(func_types.len());
+    func_types.reserve(additional);
+    let mut stats = registry.stats.lock().unwrap_or_else(|e| e.into_inner());
+    let additional = n.saturating_sub(stats.len());
+    stats.reserve(additional);
+    Ok(())
+}
+
+/// Returns the capacity of the host function registry, which is the maximum number of the host functions alive at a time.
+pub fn host_func_capacity() -> usize {
+    let registry = host_registry();
+    let capacity = lock_recovered(&registry.funcs).capacity();
+    capacity
+}
+
+/// Returns the arguments of the last calls of the host function of the given key, from the oldest to the latest.
+///
+/// Only the host functions created by [create_with_forensics](crate::Function::create_with_forensics) keep their arguments; for the others, an empty vector is returned. The arguments are kept until the host function is removed from the registry, so they can be inspected after a crash or panic inside the host function.
+///
+/// # Argument
+///
+/// * `key` - The [key](crate::Function::key) of the host function.
+#[cfg(feature = "forensics")]
+pub fn dump_forensics(key: usize) -> Vec<Vec<WasmValue>> {
+    let registry = host_registry();
+    registry
+        .forensics
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&key)
+        .map(|(_, ring)| ring.iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Returns the number of the host function calls made in all threads since startup.
+///
+/// The counter is a single atomic, so reading it is cheaper than summing the statistics of every [Function].
+pub fn total_host_calls() -> u64 {
+    TOTAL_HOST_CALLS.load(Ordering::Relaxed)
+}
+
+/// Retains only the registered host functions specified by the predicate.
+///
+/// The predicate is called with the key and the [type](crate::FuncType) of each registered host function. The host functions for which the predicate returns `false` are removed from the registry, and their closures are dropped. Calling a [Function] whose host function has been removed fails with the `ExecutionFailed` error code. Notice that the registry is locked while the predicate runs, so the predicate must not create or call host functions.
+///
+/// # Argument
+///
+/// * `predicate` - The function deciding which host functions to retain.
+pub fn retain_host_funcs(predicate: impl Fn(usize, &FuncType) -> bool) {
+    let registry = host_registry();
+    let mut host_functions = lock_recovered(&registry.funcs);
+    let mut func_types = registry.types.lock().unwrap_or_else(|e| e.into_inner());
+
+    let removed = func_types
+        .iter()
+        .filter(|(key, (params, returns))| {
+            match FuncType::create(params.clone(), returns.clone()) {
+                Ok(ty) => !predicate(**key, &ty),
+                Err(_) => false,
+            }
+        })
+        .map(|(key, _)| *key)
+        .collect::<Vec<_>>();
+
+    let removed = remove_host_funcs(&registry, &mut host_functions, &mut func_types, &removed);
+
+    // the closures may own other host functions, so drop them after unlocking the registry
+    drop(func_types);
+    drop(host_functions);
+    drop(removed);
+}
+
+// Removes the host functions of the given keys from the registry and the side tables, and returns their closures.
+//
+// The closures are returned rather than dropped, so the caller can drop them after releasing the locks of the registry.
+fn remove_host_funcs(
+    registry: &HostRegistry,
+    host_functions: &mut HashMap<usize, Arc<BoxedFn>>,
+    func_types: &mut HashMap<usize, HostFuncSignature>,
+    keys: &[usize],
+) -> Vec<Arc<BoxedFn>> {
+    let mut arg_filters = registry
+        .arg_filters
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    let mut cost_fns = registry.cost_fns.lock().unwrap_or_else(|e| e.into_inner());
+    let mut names = registry.names.lock().unwrap_or_else(|e| e.into_inner());
+    let mut categories = registry
+        .categories
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    let mut stats = registry.stats.lock().unwrap_or_else(|e| e.into_inner());
+    let mut forensics = registry.forensics.lock().unwrap_or_else(|e| e.into_inner());
+    let mut removed = Vec::with_capacity(keys.len());
+    for key in keys {
+        removed.extend(host_functions.remove(key));
+        func_types.remove(key);
+        arg_filters.remove(key);
+        cost_fns.remove(key);
+        names.remove(key);
+        categories.remove(key);
+        stats.remove(key);
+        forensics.remove(key);
+    }
+    removed
+}
+
+/// Defines the aggregated statistics of the host functions in a category.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CategoryStats {
+    /// The number of the host functions in the category.
+    pub funcs: usize,
+    /// The number of the calls of the host functions in the category.
+    pub calls: u64,
+    /// The total cost of the calls, including the static costs and the costs computed by the cost functions.
+    pub cost: u64,
+}
+
+/// Returns the statistics of the registered host functions aggregated by their [categories](crate::Function::set_category).
+///
+/// The host functions without a category are not included.
+pub fn host_stats_by_category() -> HashMap<String, CategoryStats> {
+    let registry = host_registry();
+    let categories = registry
+        .categories
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    let stats = registry.stats.lock().unwrap_or_else(|e| e.into_inner());
+
+    let mut result = HashMap::<String, CategoryStats>::new();
+    for (key, category) in categories.iter() {
+        let entry = result.entry(category.clone()).or_default();
+        entry.funcs += 1;
+        if let Some(stats) = stats.get(key) {
+            entry.calls += stats.calls;
+            entry.cost += stats.cost;
+        }
+    }
+    result
+}
+
+/// Validates a function signature without creating a [FuncType].
+///
+/// A signature is valid if none of the types is `ValType::None`, which has no encoding in a function type of WebAssembly.
+///
+/// # Arguments
+///
+/// * `params` - The types of the arguments.
+///
+/// * `returns` - The types of the returns.
+///
+/// # Error
+///
+/// If the signature is invalid, then a `FuncError::UnsupportedType` error is returned.
+pub fn validate_signature(params: &[ValType], returns: &[ValType]) -> Result<(), FuncError> {
+    match params
+        .iter()
+        .chain(returns)
+        .find(|ty| **ty == ValType::None)
+    {
+        Some(ty) => Err(FuncError::UnsupportedType(*ty)),
+        None => Ok(()),
+    }
+}
+
+/// Runs the given closure against a fresh, empty host function registry in the current thread, and restores the original registry afterward, even if the closure panics.
+///
+/// This is meant for tests which need a clean registry. The registry covers the side tables of the host functions, for example, their names and statistics, as well as the [registration recorder](crate::set_registration_recorder) and the [capacity warning handler](crate::set_capacity_warning_handler). The override is thread-local, so the other threads keep using the original registry; inside the scope, the host functions created before are not callable from the current thread, and the ones created inside the scope are only callable from the current thread until the scope ends. The scopes can be nested.
+///
+/// # Argument
+///
+/// * `f` - The closure to run.
+#[cfg(any(test, debug_assertions))]
+pub fn with_isolated_host_funcs<R>(f: impl FnOnce() -> R) -> R {
+    struct Guard;
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            // drop the registry of the scope, which may own other host functions, after popping it
+            let registry = ISOLATED_REGISTRIES.with(|registries| registries.borrow_mut().pop());
+            drop(registry);
+        }
+    }
+
+    let capacity = lock_recovered(&host_registry().funcs).capacity();
+    ISOLATED_REGISTRIES.with(|registries| {
+        registries
+            .borrow_mut()
+            .push(Arc::new(HostRegistry::with_capacity(capacity)))
+    });
+    let _guard = Guard;
+    f()
+}
+
+#[cfg(any(test, debug_assertions))]
+thread_local! {
+    // The registries swapped in by `with_isolated_host_funcs` in the current thread, from the outermost scope to the innermost one.
+    static ISOLATED_REGISTRIES: std::cell::RefCell<Vec<Arc<HostRegistry>>> = const { std::cell::RefCell::new(Vec::new()) };
+}
+
+// Returns the host function registry in effect in the current thread.
+fn host_registry() -> Arc<HostRegistry> {
+    #[cfg(any(test, debug_assertions))]
+    if let Some(registry) =
+        ISOLATED_REGISTRIES.with(|registries| registries.borrow().last().cloned())
+    {
+        return registry;
+    }
+    Arc::clone(&HOST_REGISTRY)
+}
+
+/// Encodes an invocation of the given host function into bytes, for example, to call the host function living in another process.
+///
+/// The encoding starts with the [key](crate::Function::key) of the function as a little-endian `u64`, followed by the number of the arguments as a little-endian `u32`. Each argument is then encoded as its type code, the same as the ones accepted by [from_type_codes](crate::FuncType::from_type_codes), followed by the `to_le_bytes` encoding of its value. Use [decode_invocation] to reconstruct the invocation.
+///
+/// # Arguments
+///
+/// * `func` - The host function to invoke.
+///
+/// * `args` - The arguments to pass to the host function.
+///
+/// # Error
+///
+/// If the function has no key, or any argument is a reference which cannot be transferred across processes, then a `FuncError::Invocation` error is returned. If the arguments don't match the type of the function, then an error is returned.
+pub fn encode_invocation(func: &Function, args: &[WasmValue]) -> WasmEdgeResult<Vec<u8>> {
+    let key = func.key().ok_or_else(|| {
+        WasmEdgeError::Func(FuncError::Invocation(
+            "the function is not a registered host function".into(),
+        ))
+    })?;
+    func.ty()?.validate_args(args)?;
+
+    let mut bytes = Vec::with_capacity(12 + args.len() * 17);
+    bytes.extend((key as u64).to_le_bytes());
+    bytes.extend((args.len() as u32).to_le_bytes());
+    for arg in args {
+        match arg.ty() {
+            ValType::I32 => {
+                bytes.push(0x7F);
+                bytes.extend(arg.to_i32().to_le_bytes());
+            }
+            ValType::I64 => {
+                bytes.push(0x7E);
+                bytes.extend(arg.to_i64().to_le_bytes());
+            }
+            ValType::F32 => {
+                bytes.push(0x7D);
+                bytes.extend(arg.to_f32().to_le_bytes());
+            }
+            ValType::F64 => {
+                bytes.push(0x7C);
+                bytes.extend(arg.to_f64().to_le_bytes());
+            }
+            ValType::V128 => {
+                bytes.push(0x7B);
+                bytes.extend(arg.to_v128().to_le_bytes());
+            }
+            ty => {
+                return Err(WasmEdgeError::Func(FuncError::Invocation(format!(
+                    "the arguments of {:?} type cannot be encoded",
+                    ty
+                ))))
+            }
+        }
+    }
+    Ok(bytes)
+}
+
+/// Decodes an invocation encoded by [encode_invocation], and returns the key of the host function and the arguments.
+///
+/// Notice that the returned key identifies the host function in the process which encoded the invocation; mapping it to a host function on this side is up to the caller.
+///
+/// # Argument
+///
+/// * `bytes` - The encoded invocation.
+///
+/// # Error
+///
+/// If the bytes are truncated or have trailing bytes, then a `FuncError::Invocation` error is returned. If any type code is unknown, then a `FuncError::UnknownTypeCode` error is returned.
+pub fn decode_invocation(bytes: &[u8]) -> WasmEdgeResult<(u64, Vec<WasmValue>)> {
+    fn take<const N: usize>(bytes: &mut &[u8]) -> WasmEdgeResult<[u8; N]> {
+        if bytes.len() < N {
+            return Err(WasmEdgeError::Func(FuncError::Invocation(
+                "unexpected end of the bytes".into(),
+            )));
+        }
+        let (head, tail) = bytes.split_at(N);
+        *bytes = tail;
+        Ok(head.try_into().unwrap())
+    }
+
+    let mut bytes = bytes;
+    let key = u64::from_le_bytes(take(&mut bytes)?);
+    let len = u32::from_le_bytes(take(&mut bytes)?);
+    let mut args = Vec::new();
+    for _ in 0..len {
+        let [code] = take::<1>(&mut bytes)?;
+        let arg = match code {
+            0x7F => WasmValue::from_i32(i32::from_le_bytes(take(&mut bytes)?)),
+            0x7E => WasmValue::from_i64(i64::from_le_bytes(take(&mut bytes)?)),
+            0x7D => WasmValue::from_f32(f32::from_le_bytes(take(&mut bytes)?)),
+            0x7C => WasmValue::from_f64(f64::from_le_bytes(take(&mut bytes)?)),
+            0x7B => WasmValue::from_v128(i128::from_le_bytes(take(&mut bytes)?)),
+            _ => return Err(WasmEdgeError::Func(FuncError::UnknownTypeCode(code))),
+        };
+        args.push(arg);
+    }
+    match bytes.is_empty() {
+        true => Ok((key, args)),
+        false => Err(WasmEdgeError::Func(FuncError::Invocation(format!(
+            "{} trailing bytes",
+            bytes.len()
+        )))),
+    }
+}
+
+/// Defines a set of the closures of host functions, keyed by the [keys](crate::Function::key) of the host functions.
+///
+/// A [FunctionSet] is built off the registry and then swapped in by [swap_host_funcs], for example, on config reload.
+#[derive(Default)]
+pub struct FunctionSet {
+    funcs: HashMap<usize, Arc<BoxedFn>>,
+}
+impl FunctionSet {
+    /// Creates an empty [FunctionSet].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the closure to run for the host function of the given key, and returns the closure replaced, if any.
+    ///
+    /// The closures are shared with the calls in flight, so the replaced closure is returned behind an [Arc].
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The [key](crate::Function::key) of the host function.
+    ///
+    /// * `real_fn` - The closure to run when the host function is called.
+    pub fn insert(&mut self, key: usize, real_fn: BoxedFn) -> Option<Arc<BoxedFn>> {
+        self.funcs.insert(key, Arc::new(real_fn))
+    }
+
+    /// Removes the closure of the host function of the given key from this set, and returns it, if any.
+    ///
+    /// # Argument
+    ///
+    /// * `key` - The [key](crate::Function::key) of the host function.
+    pub fn remove(&mut self, key: usize) -> Option<Arc<BoxedFn>> {
+        self.funcs.remove(&key)
+    }
+
+    /// Checks if this set contains a closure for the host function of the given key.
+    ///
+    /// # Argument
+    ///
+    /// * `key` - The [key](crate::Function::key) of the host function.
+    pub fn contains(&self, key: usize) -> bool {
+        self.funcs.contains_key(&key)
+    }
+
+    /// Returns the keys of the host functions in this set, in arbitrary order.
+    pub fn keys(&self) -> Vec<usize> {
+        self.funcs.keys().copied().collect()
+    }
+
+    /// Returns the number of the host functions in this set.
+    pub fn len(&self) -> usize {
+        self.funcs.len()
+    }
+
+    /// Checks if this set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.funcs.is_empty()
+    }
+}
+impl std::fmt::Debug for FunctionSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FunctionSet")
+            .field("keys", &self.keys())
+            .finish()
+    }
+}
+
+/// Atomically replaces the closures of all host functions with the given set, and returns the replaced set for cleanup.
+///
+/// The whole set is replaced under the lock of the registry, so no call ever sees a half-applied update: each call looks up its closure when it starts and keeps it alive until it returns, so the calls in flight complete against their original closures, and the calls made after the swap run the new ones. A host function of which the key is not in the new set fails with `ExecutionFailed` until a set containing it is swapped in. The [types](crate::Function::ty), costs, names, and statistics of the host functions are kept as is.
+///
+/// # Argument
+///
+/// * `new_set` - The closures to run for the host functions from now on.
+pub fn swap_host_funcs(mut new_set: FunctionSet) -> FunctionSet {
+    let registry = host_registry();
+    let mut funcs = lock_recovered(&registry.funcs);
+    std::mem::swap(&mut *funcs, &mut new_set.funcs);
+    new_set
+}
+
 /// Defines a host function.
 ///
 /// A WasmEdge [Function] defines a WebAssembly host function described by its [type](crate::FuncType). A host function is a closure of the original function defined in either the host or the WebAssembly module.
@@ -75,7 +663,10 @@ extern "C" fn wraper_fn(
 #[derive(Debug)]
 pub struct Function {
     pub(crate) inner: InnerFunc,
+    pub(crate) key: Option<usize>,
     pub(crate) registered: bool,
+    // the C API has no getter for the cost, so the cost assigned at creation is kept here
+    pub(crate) cost: u64,
 }
 impl Function {
     #[allow(clippy::type_complexity)]
@@ -131,7 +722,14 @@ impl Function {
     /// let func = Function::create(&func_ty, Box::new(real_add), 0).expect("fail to create a Function instance");
     /// ```
     pub fn create(ty: &FuncType, real_fn: BoxedFn, cost: u64) -> WasmEdgeResult<Self> {
-        let mut host_functions = HOST_FUNCS.lock().expect("[wasmedge-sys] try lock failed.");
+        let registry = host_registry();
+        if ty.wildcard {
+            return Err(WasmEdgeError::Func(FuncError::CreateBinding(String::from(
+                "The wildcard function type can only be used for matching",
+            ))));
+        }
+
+        let mut host_functions = lock_recovered(&registry.funcs);
         if host_functions.len() >= host_functions.capacity() {
             return Err(WasmEdgeError::Func(FuncError::CreateBinding(format!(
                 "The number of the host functions reaches the upper bound: {}",
@@ -145,7 +743,29 @@ impl Function {
         while host_functions.contains_key(&key) {
             key = rng.gen();
         }
-        host_functions.insert(key, real_fn);
+        host_functions.insert(key, Arc::new(real_fn));
+        registry
+            .stats
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(
+                key,
+                HostFuncStats {
+                    static_cost: cost,
+                    ..Default::default()
+                },
+            );
+        registry
+            .types
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(
+                key,
+                (
+                    ty.params_type_iter().collect(),
+                    ty.returns_type_iter().collect(),
+                ),
+            );
 
         let ctx = unsafe {
             ffi::WasmEdge_FunctionInstanceCreateBinding(
@@ -157,497 +777,4929 @@ impl Function {
             )
         };
 
+        let (len, capacity) = (host_functions.len(), host_functions.capacity());
+        drop(host_functions);
+
+        match ctx.is_null() {
+            true => {
+                // no function instance owns the key, so free the closure and the side entries now
+                release_host_funcs(&[key]);
+                Err(WasmEdgeError::Func(FuncError::Create))
+            }
+            false => {
+                warn_capacity(len, capacity);
+                record(RegistrationEvent::Created {
+                    key,
+                    ty: func_type_of(ty),
+                    cost,
+                });
+                Ok(Self {
+                    inner: InnerFunc(ctx),
+                    key: Some(key),
+                    registered: false,
+                    cost,
+                })
+            }
+        }
+    }
+
+    /// Creates a [host function](crate::Function) from a C-compatible function pointer, bypassing the Rust closure machinery and the host function registry.
+    ///
+    /// The pointer is passed to WasmEdge as is. Each call invokes `raw_fn` with the following arguments in order: a null pointer, the given `data`, the pointer to the memory instance of the caller (may be null), the pointer to the arguments and their number, and the pointer to the buffer for the returns and its length. The function must write exactly the number of returns declared by `ty`, and returns a `WasmEdge_Result` of which the code `0` means success.
+    ///
+    /// # Arguments
+    ///
+    /// * `ty` - The types of the arguments and returns of the target function.
+    ///
+    /// * `raw_fn` - The C-compatible host function.
+    ///
+    /// * `data` - The pointer to the host data passed to `raw_fn` on each call.
+    ///
+    /// * `cost` - The function cost in the [Statistics](crate::Statistics). Pass 0 if the calculation is not needed.
+    ///
+    /// # Error
+    ///
+    /// If fail to create a [Function], then an error is returned.
+    ///
+    /// # Safety
+    ///
+    /// The caller must guarantee that `raw_fn` follows the ABI described above and matches `ty`, and that `data` stays valid as long as the created [Function] may be called.
+    pub unsafe fn from_raw_host_fn(
+        ty: &FuncType,
+        raw_fn: RawHostFn,
+        data: *mut c_void,
+        cost: u64,
+    ) -> WasmEdgeResult<Self> {
+        if ty.wildcard {
+            return Err(WasmEdgeError::Func(FuncError::CreateBinding(String::from(
+                "The wildcard function type can only be used for matching",
+            ))));
+        }
+
+        let ctx = ffi::WasmEdge_FunctionInstanceCreateBinding(
+            ty.inner.0,
+            Some(raw_fn),
+            std::ptr::null_mut(),
+            data,
+            cost,
+        );
+
         match ctx.is_null() {
             true => Err(WasmEdgeError::Func(FuncError::Create)),
             false => Ok(Self {
                 inner: InnerFunc(ctx),
+                key: None,
                 registered: false,
+                cost,
             }),
         }
     }
 
-    /// Returns the underlying wasm type of this [Function].
+    /// Creates a [host function](crate::Function) whose cost is computed from the arguments of each call.
     ///
-    /// # Errors
+    /// Before the body of the host function runs, `cost_fn` is called with the arguments, and the returned value is accumulated as the cost charged by this [Function]. This is useful for the host functions operating on guest buffers, of which the cost is proportional to the buffer length. The computed cost is added to the [total cost](crate::Statistics::cost_in_total) of the [Statistics](crate::Statistics) attached to the calling [Executor](crate::Executor) or [Vm](crate::Vm), and to the [charged cost](crate::Function::charged_cost) of this [Function]. Notice that WasmEdge core only supports static costs, so the computed cost is accounted on the Rust side, where the [cost limit](crate::Statistics::set_cost_limit) is enforced on it as well: a call of which the computed cost exceeds the limit fails with a `CoreCommonError::CostLimitExceeded` error before the body runs. The calls run by [run_function_interruptible](crate::Vm::run_function_interruptible) aren't charged.
     ///
-    /// If fail to get the function type, then an error is returned.
+    /// # Arguments
     ///
-    pub fn ty(&self) -> WasmEdgeResult<FuncType> {
-        let ty = unsafe { ffi::WasmEdge_FunctionInstanceGetFunctionType(self.inner.0) };
-        match ty.is_null() {
-            true => Err(WasmEdgeError::Func(FuncError::Type)),
-            false => Ok(FuncType {
-                inner: InnerFuncType(ty as *mut _),
-                registered: true,
-            }),
+    /// * `ty` - The types of the arguments and returns of the target function.
+    ///
+    /// * `real_fn` - The pointer to the target function.
+    ///
+    /// * `cost_fn` - The function computing the cost of a call from its arguments.
+    ///
+    /// # Error
+    ///
+    /// If fail to create a [Function], then an error is returned.
+    pub fn create_with_cost_fn(
+        ty: &FuncType,
+        real_fn: BoxedFn,
+        cost_fn: BoxedCostFn,
+    ) -> WasmEdgeResult<Self> {
+        let registry = host_registry();
+        let func = Self::create(ty, real_fn, 0)?;
+        if let Some(key) = func.key {
+            registry
+                .cost_fns
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .insert(key, (Arc::new(cost_fn), 0));
         }
+        Ok(func)
     }
 
-    /// Runs this host function and returns the result.
+    /// Creates a [host function](crate::Function) of which the signature and the marshaling of the arguments and returns are derived from the given request and response types.
+    ///
+    /// The types of the arguments are given by `Req` and the types of the returns are given by `Resp`. If the arguments of a call can't be unmarshaled into `Req`, then the call fails with the `FuncSigMismatch` error code.
     ///
     /// # Arguments
     ///
-    /// * `engine` - The object implements Engine trait.
+    /// * `real_fn` - The closure taking a request and returning a response.
     ///
-    /// * `args` - The arguments passed to the host function.
+    /// * `cost` - The function cost in the [Statistics](crate::Statistics). Pass 0 if the calculation is not needed.
     ///
     /// # Error
     ///
-    /// If fail to run the host function, then an error is returned.
+    /// If fail to create a [Function], then an error is returned.
+    pub fn create_marshaled<Req, Resp>(
+        real_fn: impl Fn(Req) -> Result<Resp, u8> + Send + Sync + 'static,
+        cost: u64,
+    ) -> WasmEdgeResult<Self>
+    where
+        Req: HostMarshal,
+        Resp: HostMarshal,
+    {
+        let ty = FuncType::create(Req::val_types(), Resp::val_types())?;
+        Self::create(
+            &ty,
+            Box::new(move |input: Vec<WasmValue>| {
+                let req =
+                    Req::from_values(&input).ok_or(ffi::WasmEdge_ErrCode_FuncSigMismatch as u8)?;
+                real_fn(req).map(|resp| resp.to_values())
+            }),
+            cost,
+        )
+    }
+
+    /// Creates a [host function](crate::Function) of which each call is capped by the given wall-clock deadline.
     ///
-    /// # Example
+    /// Each call runs `real_fn` on a watched worker thread. If `real_fn` doesn't finish within the deadline, then the call fails with a `FuncError::HostTrap(HostTrap::Timeout)` error; if `real_fn` panics, then the call fails with a `FuncError::HostTrap(HostTrap::Panicked)` error. Notice that Rust threads can't be killed, so the watcher only stops waiting: the timed out closure keeps running to completion in the background, and its result is discarded. Therefore, `real_fn` should be abortable by itself, for example, by checking a shared flag or a deadline of its own; otherwise the watcher can only signal the timeout while the closure still occupies a thread.
     ///
-    /// ```rust
-    /// use wasmedge_sys::{FuncType, Function, WasmValue, Executor};
-    /// use wasmedge_types::ValType;
+    /// The worker threads are reused across the calls, and a new one is spawned only if all of them are busy, for example, running a timed out closure or serving a concurrent call. Since `real_fn` runs on a worker thread rather than the thread calling the host function, the per-thread state of the calling thread isn't forwarded to it: `real_fn` has no access to the [memory of the caller](crate::Function::create_with_memory), which is only valid until the call returns, possibly before a timed out closure finishes, and the [error messages](crate::Function::create_with_error) are not reported.
     ///
-    /// fn real_add(input: Vec<WasmValue>) -> Result<Vec<WasmValue>, u8> {
-    ///     println!("Rust: Entering Rust function real_add");
+    /// # Arguments
     ///
-    ///     if input.len() != 2 {
-    ///         return Err(1);
-    ///     }
+    /// * `ty` - The types of the arguments and returns of the target function.
     ///
-    ///     let a = if input[0].ty() == ValType::I32 {
-    ///         input[0].to_i32()
-    ///     } else {
-    ///         return Err(2);
-    ///     };
+    /// * `real_fn` - The pointer to the target function.
     ///
-    ///     let b = if input[1].ty() == ValType::I32 {
-    ///         input[1].to_i32()
-    ///     } else {
-    ///         return Err(3);
-    ///     };
+    /// * `cost` - The function cost in the [Statistics](crate::Statistics). Pass 0 if the calculation is not needed.
     ///
-    ///     let c = a + b;
-    ///     println!("Rust: calcuating in real_add c: {:?}", c);
+    /// * `deadline` - The maximum wall-clock time of a call.
     ///
-    ///     println!("Rust: Leaving Rust function real_add");
-    ///     Ok(vec![WasmValue::from_i32(c)])
-    /// }
+    /// # Error
     ///
-    /// // create a FuncType
-    /// let result = FuncType::create(vec![ValType::I32; 2], vec![ValType::I32]);
-    /// assert!(result.is_ok());
-    /// let func_ty = result.unwrap();
-    /// // create a host function
-    /// let result = Function::create(&func_ty, Box::new(real_add), 0);
-    /// assert!(result.is_ok());
-    /// let host_func = result.unwrap();
-    ///
-    /// // create an Executor instance
-    /// let result = Executor::create(None, None);
-    /// assert!(result.is_ok());
-    /// let mut executor = result.unwrap();
-    ///
-    /// // run this function
-    /// let result = host_func.call(
-    ///     &mut executor,
-    ///     vec![WasmValue::from_i32(1), WasmValue::from_i32(2)],
-    /// );
-    /// assert!(result.is_ok());
-    /// let returns = result.unwrap();
-    /// assert_eq!(returns[0].to_i32(), 3);
-    /// ```
-    pub fn call<E: Engine>(
-        &self,
-        engine: &mut E,
-        args: impl IntoIterator<Item = WasmValue>,
-    ) -> WasmEdgeResult<Vec<WasmValue>> {
-        engine.run_func(self, args)
-    }
-
-    /// Returns a reference to this [Function] instance.
-    pub fn as_ref(&self) -> FuncRef {
-        FuncRef {
-            inner: InnerFuncRef(self.inner.0 as *const _),
-        }
-    }
-}
-impl Drop for Function {
-    fn drop(&mut self) {
-        if !self.registered && !self.inner.0.is_null() {
-            unsafe { ffi::WasmEdge_FunctionInstanceDelete(self.inner.0) };
-        }
+    /// If fail to create a [Function], then an error is returned.
+    pub fn create_with_deadline(
+        ty: &FuncType,
+        real_fn: BoxedFn,
+        cost: u64,
+        deadline: Duration,
+    ) -> WasmEdgeResult<Self> {
+        let real_fn = Arc::new(real_fn);
+        let idle_workers = Mutex::new(Vec::<DeadlineWorker>::new());
+        Self::create(
+            ty,
+            Box::new(move |input: Vec<WasmValue>| {
+                let worker = idle_workers
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .pop()
+                    .unwrap_or_else(|| spawn_deadline_worker(Arc::clone(&real_fn)));
+                let (tx, rx) = std::sync::mpsc::channel();
+                if worker.send((input, tx)).is_err() {
+                    // the worker is gone, which only happens if it panicked
+                    return Err(raise_host_trap(HostTrap::Panicked));
+                }
+                match rx.recv_timeout(deadline) {
+                    Ok(result) => {
+                        idle_workers
+                            .lock()
+                            .unwrap_or_else(|e| e.into_inner())
+                            .push(worker);
+                        result
+                    }
+                    // the busy worker exits once the closure finishes, since its sender is dropped
+                    Err(RecvTimeoutError::Timeout) => Err(raise_host_trap(HostTrap::Timeout)),
+                    // the worker drops the sender of the result only if the closure panicked
+                    Err(RecvTimeoutError::Disconnected) => Err(raise_host_trap(HostTrap::Panicked)),
+                }
+            }),
+            cost,
+        )
     }
-}
-
-#[derive(Debug)]
-pub(crate) struct InnerFunc(pub(crate) *mut ffi::WasmEdge_FunctionInstanceContext);
-unsafe impl Send for InnerFunc {}
-unsafe impl Sync for InnerFunc {}
 
-/// Defines the type of a [host function](crate::Function).
-///
-/// A WasmEdge [FuncType] classifies the signature of a [Function], including the type information of both the arguments and the returns.
-#[derive(Debug)]
-pub struct FuncType {
-    pub(crate) inner: InnerFuncType,
-    pub(crate) registered: bool,
-}
-impl FuncType {
-    /// Create a new [FuncType] to be associated with the given arguments and returns.
+    /// Creates a [host function](crate::Function) of which the invocations are dispatched to the given thread pool.
     ///
-    /// # Arguments
+    /// Each call submits `real_fn` to `pool`, and the calling thread waits for the result. This keeps the blocking work, for example, disk or network I/O, off the latency-sensitive threads running the VM, while the host function stays synchronous to the guest. If the pool drops the job without running it, the call fails with the `ExecutionFailed` error code.
     ///
-    /// * `args` - The agrgument types of a [Function].
+    /// Notice that reentrancy may deadlock: if `real_fn` runs WebAssembly code which in turn calls a host function dispatched to the same pool, and all the threads of the pool are waiting, no thread is left to run the inner call.
     ///
-    /// * `returns` - The types of the returns of a [Function].
+    /// # Arguments
     ///
-    /// # Error
+    /// * `ty` - The types of the arguments and returns of the target function.
     ///
-    /// If fail to create a [FuncType], then an error is returned.
+    /// * `real_fn` - The pointer to the target function.
     ///
-    /// # Example
+    /// * `cost` - The function cost in the [Statistics](crate::Statistics). Pass 0 if the calculation is not needed.
     ///
-    /// ```rust
-    /// use wasmedge_sys::FuncType;
-    /// use wasmedge_types::ValType;
+    /// * `pool` - The thread pool to run `real_fn` on.
     ///
-    /// let func_ty = FuncType::create(vec![ValType::I32;2], vec![ValType::I32]).expect("fail to create a FuncType");
-    /// ```
-    pub fn create<I: IntoIterator<Item = ValType>, R: IntoIterator<Item = ValType>>(
-        args: I,
-        returns: R,
+    /// # Error
+    ///
+    /// If fail to create a [Function], then an error is returned.
+    pub fn create_on_pool(
+        ty: &FuncType,
+        real_fn: BoxedFn,
+        cost: u64,
+        pool: Arc<dyn ThreadPool>,
     ) -> WasmEdgeResult<Self> {
-        let param_tys = args
-            .into_iter()
-            .map(|x| x.into())
-            .collect::<Vec<ffi::WasmEdge_ValType>>();
-        let ret_tys = returns
-            .into_iter()
-            .map(|x| x.into())
-            .collect::<Vec<ffi::WasmEdge_ValType>>();
-
-        let ctx = unsafe {
-            ffi::WasmEdge_FunctionTypeCreate(
-                param_tys.as_ptr() as *const _,
-                param_tys.len() as u32,
-                ret_tys.as_ptr() as *const _,
-                ret_tys.len() as u32,
-            )
-        };
-        match ctx.is_null() {
-            true => Err(WasmEdgeError::FuncTypeCreate),
-            false => Ok(Self {
-                inner: InnerFuncType(ctx),
-                registered: false,
+        let real_fn = Arc::new(real_fn);
+        Self::create(
+            ty,
+            Box::new(move |input: Vec<WasmValue>| {
+                let (tx, rx) = std::sync::mpsc::channel();
+                let real_fn = Arc::clone(&real_fn);
+                pool.execute(Box::new(move || {
+                    let _ = tx.send(real_fn(input));
+                }));
+                match rx.recv() {
+                    Ok(result) => result,
+                    // the job is dropped without running
+                    Err(_) => Err(ffi::WasmEdge_ErrCode_ExecutionFailed as u8),
+                }
             }),
-        }
-    }
-
-    /// Returns the number of the arguments of a [Function].
-    pub fn params_len(&self) -> u32 {
-        unsafe { ffi::WasmEdge_FunctionTypeGetParametersLength(self.inner.0) }
-    }
-
-    /// Returns an Iterator of the arguments of a [Function].
-    pub fn params_type_iter(&self) -> impl Iterator<Item = ValType> {
-        let len = self.params_len();
-        let mut types = Vec::with_capacity(len as usize);
-        unsafe {
-            ffi::WasmEdge_FunctionTypeGetParameters(self.inner.0, types.as_mut_ptr(), len);
-            types.set_len(len as usize);
-        }
-
-        types.into_iter().map(Into::into)
+            cost,
+        )
     }
 
-    ///Returns the number of the returns of a [Function].
-    pub fn returns_len(&self) -> u32 {
-        unsafe { ffi::WasmEdge_FunctionTypeGetReturnsLength(self.inner.0) }
+    /// Creates a [host function](crate::Function) producing its returns as an iterator.
+    ///
+    /// The number of the items produced by `real_fn` is validated against the number of the returns declared by `ty` according to the given policy. If the validation fails, then the call fails with the `FuncSigMismatch` error code.
+    ///
+    /// # Arguments
+    ///
+    /// * `ty` - The types of the arguments and returns of the target function.
+    ///
+    /// * `real_fn` - The pointer to the target function.
+    ///
+    /// * `cost` - The function cost in the [Statistics](crate::Statistics). Pass 0 if the calculation is not needed.
+    ///
+    /// * `policy` - The policy for validating the number of the produced items.
+    ///
+    /// # Error
+    ///
+    /// If fail to create a [Function], then an error is returned.
+    pub fn create_streaming(
+        ty: &FuncType,
+        real_fn: BoxedStreamFn,
+        cost: u64,
+        policy: StreamingPolicy,
+    ) -> WasmEdgeResult<Self> {
+        let return_len = ty.returns_len() as usize;
+        Self::create(
+            ty,
+            Box::new(move |input: Vec<WasmValue>| {
+                // stop producing once the cap is exceeded, so an oversized result is never buffered
+                let cap = MAX_HOST_RESULT_LEN.load(Ordering::Relaxed);
+                let returns = real_fn(input)?
+                    .take(cap.saturating_add(1).min(return_len + 1))
+                    .collect::<Vec<WasmValue>>();
+                if returns.len() > cap {
+                    return Err(raise_host_trap(HostTrap::ResultTooLarge));
+                }
+                match (returns.len(), policy) {
+                    (n, _) if n == return_len => Ok(returns),
+                    (n, StreamingPolicy::Lenient) if n > return_len => {
+                        Ok(returns.into_iter().take(return_len).collect())
+                    }
+                    _ => Err(ffi::WasmEdge_ErrCode_FuncSigMismatch as u8),
+                }
+            }),
+            cost,
+        )
     }
 
-    /// Returns an Iterator of the return types of a [Function].
-    pub fn returns_type_iter(&self) -> impl Iterator<Item = ValType> {
-        let len = self.returns_len();
-        let mut types = Vec::with_capacity(len as usize);
-        unsafe {
-            ffi::WasmEdge_FunctionTypeGetReturns(self.inner.0, types.as_mut_ptr(), len);
-            types.set_len(len as usize);
-        }
-
-        types.into_iter().map(Into::into)
-    }
-}
-impl Drop for FuncType {
-    fn drop(&mut self) {
-        if !self.registered && !self.inner.0.is_null() {
-            unsafe { ffi::WasmEdge_FunctionTypeDelete(self.inner.0) };
+    /// Creates a [host function](crate::Function) of which the arguments are rewritten by the given filter before each call.
+    ///
+    /// The filter is invoked with the arguments of each call before they reach `real_fn`, so it can sanitize or clamp the inputs transparently.
+    ///
+    /// # Arguments
+    ///
+    /// * `ty` - The types of the arguments and returns of the target function.
+    ///
+    /// * `real_fn` - The pointer to the target function.
+    ///
+    /// * `filter` - The function rewriting the arguments.
+    ///
+    /// # Error
+    ///
+    /// If fail to create a [Function], then an error is returned.
+    pub fn create_with_arg_filter(
+        ty: &FuncType,
+        real_fn: BoxedFn,
+        filter: BoxedArgFilter,
+    ) -> WasmEdgeResult<Self> {
+        let registry = host_registry();
+        let func = Self::create(ty, real_fn, 0)?;
+        if let Some(key) = func.key {
+            registry
+                .arg_filters
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .insert(key, Arc::new(filter));
         }
+        Ok(func)
     }
-}
-impl From<wasmedge_types::FuncType> for FuncType {
-    fn from(ty: wasmedge_types::FuncType) -> Self {
-        let param_tys: Vec<_> = match ty.args() {
-            Some(args) => args.to_vec(),
-            None => Vec::new(),
-        };
-        let ret_tys: Vec<_> = match ty.returns() {
-            Some(returns) => returns.to_vec(),
-            None => Vec::new(),
-        };
-
-        FuncType::create(param_tys, ret_tys).expect("[wasmedge-sys] Failed to convert wasmedge_types::FuncType into wasmedge_sys::FuncType.")
-    }
-}
-impl From<FuncType> for wasmedge_types::FuncType {
-    fn from(ty: FuncType) -> Self {
-        let args = if ty.params_len() > 0 {
-            let mut args = Vec::with_capacity(ty.params_len() as usize);
-            for ty in ty.params_type_iter() {
-                args.push(ty);
-            }
-            Some(args)
-        } else {
-            None
-        };
-
-        let returns = if ty.returns_len() > 0 {
-            let mut returns = Vec::with_capacity(ty.returns_len() as usize);
-            for ty in ty.returns_type_iter() {
-                returns.push(ty);
-            }
-            Some(returns)
-        } else {
-            None
-        };
-
-        wasmedge_types::FuncType::new(args, returns)
-    }
-}
-
-#[derive(Debug)]
-pub(crate) struct InnerFuncType(pub(crate) *mut ffi::WasmEdge_FunctionTypeContext);
-unsafe impl Send for InnerFuncType {}
-unsafe impl Sync for InnerFuncType {}
 
-/// Defines a reference to a [host function](crate::Function).
-#[derive(Debug, Clone)]
-pub struct FuncRef {
-    pub(crate) inner: InnerFuncRef,
-}
-impl Drop for FuncRef {
-    fn drop(&mut self) {
-        self.inner.0 = std::ptr::null();
-    }
-}
-impl FuncRef {
-    /// Returns the underlying wasm type of the host function this [FuncRef] points to.
+    /// Creates a [host function](crate::Function) which widens the compatible numeric arguments to the declared types of the parameters before each call.
     ///
-    /// # Errors
+    /// Some guests pass an `i32` where the host function declares an `i64`. Instead of trapping, the arguments are coerced before they reach `real_fn`. The allowed coercions are exactly:
     ///
-    /// If fail to get the function type, then an error is returned.
+    /// * `i32` to `i64`, by sign extension.
     ///
-    pub fn ty(&self) -> WasmEdgeResult<FuncType> {
-        let ty = unsafe { ffi::WasmEdge_FunctionInstanceGetFunctionType(self.inner.0 as *mut _) };
-        match ty.is_null() {
-            true => Err(WasmEdgeError::Func(FuncError::Type)),
-            false => Ok(FuncType {
-                inner: InnerFuncType(ty as *mut _),
-                registered: true,
-            }),
+    /// * `f32` to `f64`, exactly.
+    ///
+    /// Narrowing is never performed, and any other mismatched argument is passed to `real_fn` unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `ty` - The types of the arguments and returns of the target function.
+    ///
+    /// * `real_fn` - The pointer to the target function.
+    ///
+    /// * `cost` - The function cost in the [Statistics](crate::Statistics). Pass 0 if the calculation is not needed.
+    ///
+    /// # Error
+    ///
+    /// If fail to create a [Function], then an error is returned.
+    pub fn create_coercing(ty: &FuncType, real_fn: BoxedFn, cost: u64) -> WasmEdgeResult<Self> {
+        let registry = host_registry();
+        let params = ty.params_type_iter().collect::<Vec<_>>();
+        let func = Self::create(ty, real_fn, cost)?;
+        if let Some(key) = func.key {
+            registry
+                .arg_filters
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .insert(
+                    key,
+                    Arc::new(Box::new(move |input: &mut Vec<WasmValue>| {
+                        coerce_args(&params, input)
+                    })),
+                );
         }
+        Ok(func)
     }
 
-    /// Runs this host function the reference refers to.
+    /// Creates a [host function](crate::Function) which accesses the memory of its caller.
+    ///
+    /// On each call, `real_fn` is given a [MemoryMut] viewing the memory of the calling module instance, through which the host function can write back into guest buffers with bounds checks. If the caller has no memory, then every access through the [MemoryMut] fails. The [MemoryMut] is only valid during the call.
     ///
     /// # Arguments
     ///
-    /// * `engine` - The object implements Engine trait.
+    /// * `ty` - The types of the arguments and returns of the target function.
     ///
-    /// * `args` - The arguments passed to the host function.
+    /// * `real_fn` - The pointer to the target function.
     ///
-    /// # Error
+    /// * `cost` - The function cost in the [Statistics](crate::Statistics). Pass 0 if the calculation is not needed.
     ///
-    /// If fail to run the host function, then an error is returned.
+    /// # Error
     ///
-    pub fn call<E: Engine>(
-        &self,
+    /// If fail to create a [Function], then an error is returned.
+    pub fn create_with_memory(
+        ty: &FuncType,
+        real_fn: BoxedMemFn,
+        cost: u64,
+    ) -> WasmEdgeResult<Self> {
+        Self::create(
+            ty,
+            Box::new(move |input: Vec<WasmValue>| {
+                let mut memory = MemoryMut::from_raw(CALLER_MEMORY.with(|memory| memory.get()));
+                real_fn(&mut memory, input)
+            }),
+            cost,
+        )
+    }
+
+    /// Creates a [host function](crate::Function) which reports its errors with messages.
+    ///
+    /// When `real_fn` returns a [HostFuncError], its code is reported to WasmEdge, and the error is kept in a thread-local slot. The [Executor](crate::Executor) then surfaces it as a `FuncError::Host` error carrying both the code and the message, instead of the error mapped from the bare code.
+    ///
+    /// # Arguments
+    ///
+    /// * `ty` - The types of the arguments and returns of the target function.
+    ///
+    /// * `real_fn` - The pointer to the target function.
+    ///
+    /// * `cost` - The function cost in the [Statistics](crate::Statistics). Pass 0 if the calculation is not needed.
+    ///
+    /// # Error
+    ///
+    /// If fail to create a [Function], then an error is returned.
+    #[allow(clippy::type_complexity)]
+    pub fn create_with_error(
+        ty: &FuncType,
+        real_fn: Box<dyn Fn(Vec<WasmValue>) -> Result<Vec<WasmValue>, HostFuncError> + Send + Sync>,
+        cost: u64,
+    ) -> WasmEdgeResult<Self> {
+        Self::create(
+            ty,
+            Box::new(move |input: Vec<WasmValue>| {
+                real_fn(input).map_err(|HostFuncError { code, message }| {
+                    LAST_HOST_ERROR
+                        .with(|last| *last.borrow_mut() = Some(FuncError::Host { code, message }));
+                    code
+                })
+            }),
+            cost,
+        )
+    }
+
+    /// Creates a [host function](crate::Function) from a closure mutating its captured state, for example, an accumulator or a random seed.
+    ///
+    /// The closure is kept behind a lock, which is held only while the closure runs and is released before its returns are written back to WasmEdge.
+    ///
+    /// # Arguments
+    ///
+    /// * `ty` - The types of the arguments and returns of the target function.
+    ///
+    /// * `real_fn` - The pointer to the target function.
+    ///
+    /// * `cost` - The function cost in the [Statistics](crate::Statistics). Pass 0 if the calculation is not needed.
+    ///
+    /// # Error
+    ///
+    /// If fail to create a [Function], then an error is returned.
+    pub fn create_mut(ty: &FuncType, real_fn: BoxedFnMut, cost: u64) -> WasmEdgeResult<Self> {
+        let real_fn = Mutex::new(real_fn);
+        Self::create(
+            ty,
+            Box::new(move |input: Vec<WasmValue>| {
+                let mut real_fn = real_fn.lock().unwrap_or_else(|e| e.into_inner());
+                real_fn(input)
+            }),
+            cost,
+        )
+    }
+
+    /// Creates a [host function](crate::Function) from a closure returning a future, for example, a future performing HTTP requests or database queries.
+    ///
+    /// WasmEdge calls host functions synchronously, so the call blocks the thread running the wasm code until the future completes; the other threads, for example, the worker threads of an async runtime, keep running. The future is polled on the thread running the wasm code by a minimal executor, which parks the thread while the future is pending and unparks it on wake.
+    ///
+    /// Notice that the executor is not a reactor: it doesn't drive any I/O or timers, and provides no runtime context. Therefore, a future which needs a reactor, for example, a Tokio socket, timer or `spawn`, either panics with "there is no reactor running" or never completes, unless the reactor is driven by an async runtime running on other threads. For Tokio, keep a multi-thread runtime alive and create the future within its context, e.g., by entering [`Handle::enter`](https://docs.rs/tokio/latest/tokio/runtime/struct.Handle.html#method.enter) in the closure, or spawn the work onto the runtime and return the future of its `JoinHandle`. Don't run the wasm code on a thread of the runtime driving the future, in particular of a current-thread runtime, otherwise it deadlocks, since that thread is blocked for the whole call.
+    ///
+    /// # Arguments
+    ///
+    /// * `ty` - The types of the arguments and returns of the target function.
+    ///
+    /// * `real_fn` - The closure returning the future of the returns.
+    ///
+    /// * `cost` - The function cost in the [Statistics](crate::Statistics). Pass 0 if the calculation is not needed.
+    ///
+    /// # Error
+    ///
+    /// If fail to create a [Function], then an error is returned.
+    pub fn create_async(ty: &FuncType, real_fn: BoxedAsyncFn, cost: u64) -> WasmEdgeResult<Self> {
+        Self::create(
+            ty,
+            Box::new(move |input: Vec<WasmValue>| block_on(real_fn(input))),
+            cost,
+        )
+    }
+
+    /// Creates a [host function](crate::Function) which shares the given host data across its calls.
+    ///
+    /// On each call, `real_fn` is given a mutable reference to the data, for example, a database handle or a counter, so no global statics are needed. The data is owned by the host function registry together with `real_fn`, so it stays alive as long as the host function is callable, even after the [Function] is moved into an [import module](crate::ImportModule), and is freed when the host function is removed from the registry, that is, when the [Function] or the module it is moved into is dropped.
+    ///
+    /// Notice that a host function is called on whichever thread drives the engine, so the data must be `Send`. The calls are serialized on the data, so it doesn't have to be `Sync`.
+    ///
+    /// # Arguments
+    ///
+    /// * `ty` - The types of the arguments and returns of the target function.
+    ///
+    /// * `real_fn` - The pointer to the target function.
+    ///
+    /// * `data` - The host data shared across the calls.
+    ///
+    /// * `cost` - The function cost in the [Statistics](crate::Statistics). Pass 0 if the calculation is not needed.
+    ///
+    /// # Error
+    ///
+    /// If fail to create a [Function], then an error is returned.
+    #[allow(clippy::type_complexity)]
+    pub fn create_with_data<T: Send + 'static>(
+        ty: &FuncType,
+        real_fn: Box<dyn Fn(&mut T, Vec<WasmValue>) -> Result<Vec<WasmValue>, u8> + Send + Sync>,
+        data: Box<T>,
+        cost: u64,
+    ) -> WasmEdgeResult<Self> {
+        let data = Mutex::new(data);
+        Self::create(
+            ty,
+            Box::new(move |input: Vec<WasmValue>| {
+                let mut data = data.lock().unwrap_or_else(|e| e.into_inner());
+                real_fn(&mut data, input)
+            }),
+            cost,
+        )
+    }
+
+    /// Creates a [host function](crate::Function) which keeps the arguments of its last calls in a bounded ring buffer for crash forensics.
+    ///
+    /// The arguments of each call are recorded before `real_fn` runs, and the arguments of at most `ring_size` last calls are kept. Use [dump_forensics](crate::dump_forensics) with the [key](crate::Function::key) of the created [Function] to inspect them. Notice that the arguments may be sensitive, and keeping them costs memory, so this is only available with the `forensics` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `ty` - The types of the arguments and returns of the target function.
+    ///
+    /// * `real_fn` - The pointer to the target function.
+    ///
+    /// * `cost` - The function cost in the [Statistics](crate::Statistics). Pass 0 if the calculation is not needed.
+    ///
+    /// * `ring_size` - The number of the last calls to keep the arguments of.
+    ///
+    /// # Error
+    ///
+    /// If fail to create a [Function], then an error is returned.
+    #[cfg(feature = "forensics")]
+    pub fn create_with_forensics(
+        ty: &FuncType,
+        real_fn: BoxedFn,
+        cost: u64,
+        ring_size: usize,
+    ) -> WasmEdgeResult<Self> {
+        let registry = host_registry();
+        let func = Self::create(ty, real_fn, cost)?;
+        if let Some(key) = func.key {
+            registry
+                .forensics
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .insert(
+                    key,
+                    (
+                        ring_size,
+                        std::collections::VecDeque::with_capacity(ring_size),
+                    ),
+                );
+        }
+        Ok(func)
+    }
+
+    /// Creates a [host function](crate::Function) for each of the given signatures, all of which share the same closure.
+    ///
+    /// This is useful for the generic operations, for example, an `add` working for `i32`, `i64`, `f32` and `f64`, of which the closure inspects the types of the arguments at runtime. The returned [Function]s are in the same order as `signatures`, and the cost of each is 0.
+    ///
+    /// # Arguments
+    ///
+    /// * `signatures` - The types of the arguments and returns of each [Function] to create.
+    ///
+    /// * `real_fn` - The pointer to the target function shared by all the signatures.
+    ///
+    /// # Error
+    ///
+    /// If fail to create any of the [Function]s, then an error is returned, and the ones already created are dropped.
+    pub fn create_overloaded(
+        signatures: &[FuncType],
+        real_fn: BoxedFn,
+    ) -> WasmEdgeResult<Vec<Self>> {
+        let real_fn = Arc::new(real_fn);
+        signatures
+            .iter()
+            .map(|ty| {
+                let real_fn = Arc::clone(&real_fn);
+                Self::create(ty, Box::new(move |input: Vec<WasmValue>| real_fn(input)), 0)
+            })
+            .collect()
+    }
+
+    /// Creates a [host function](crate::Function) which forwards each call to the function the given reference refers to.
+    ///
+    /// Invoking the created host function calls `target` with the same arguments on the given executor, and returns its returns. Notice that the function instance `target` refers to, for example, an export of a [module instance](crate::Instance), must outlive the created [Function]; the executor is owned by the created [Function], so it lives as long as the [Function] does. In addition, the executor is locked during a forwarded call, so the target function must not call back into the created [Function], otherwise it deadlocks.
+    ///
+    /// # Arguments
+    ///
+    /// * `ty` - The types of the arguments and returns of the target function.
+    ///
+    /// * `target` - The reference to the function to forward to.
+    ///
+    /// * `executor` - The [Executor](crate::Executor) to run the target function.
+    ///
+    /// * `cost` - The function cost in the [Statistics](crate::Statistics). Pass 0 if the calculation is not needed.
+    ///
+    /// # Error
+    ///
+    /// If fail to create a [Function], then an error is returned.
+    pub fn create_forwarding(
+        ty: &FuncType,
+        target: FuncRef,
+        executor: Executor,
+        cost: u64,
+    ) -> WasmEdgeResult<Self> {
+        let executor = std::sync::Mutex::new(executor);
+        Self::create(
+            ty,
+            Box::new(move |input: Vec<WasmValue>| {
+                let mut executor = executor.lock().unwrap_or_else(|e| e.into_inner());
+                target
+                    .call(&mut *executor, input)
+                    .map_err(|_| ffi::WasmEdge_ErrCode_ExecutionFailed as u8)
+            }),
+            cost,
+        )
+    }
+
+    /// Returns the key of this [Function] in the host function registry.
+    ///
+    /// If this [Function] is not created from a host closure, for example, it is an export of a module instance, then `None` is returned.
+    pub fn key(&self) -> Option<usize> {
+        self.key
+    }
+
+    /// Returns the cost assigned to this [Function] at creation, which is charged in the [Statistics](crate::Statistics) on each call.
+    ///
+    /// The cost is kept by the [Function] created with it, since WasmEdge provides no API to read it back. Therefore, for the [Function]s fetched from a module instance, including the host functions added to it, and the functions defined in WebAssembly, `0` is returned.
+    pub fn cost(&self) -> u64 {
+        self.cost
+    }
+
+    /// Returns the total cost charged by the cost function of this [Function].
+    ///
+    /// If this [Function] is not created by [create_with_cost_fn](crate::Function::create_with_cost_fn), then `0` is returned.
+    pub fn charged_cost(&self) -> u64 {
+        let registry = host_registry();
+        match self.key {
+            Some(key) => registry
+                .cost_fns
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .get(&key)
+                .map(|(_, charged)| *charged)
+                .unwrap_or_default(),
+            None => 0,
+        }
+    }
+
+    /// Attaches a human-readable name to this [Function].
+    ///
+    /// Once named, the errors returned by [call](crate::Function::call) are wrapped in `FuncError::Trapped` carrying the name, for example, `host function "db_query" trapped: ...`, and a [RegistrationEvent::Named] is recorded. If a host function with a different signature was previously named the same, a [RegistrationEvent::SignatureChanged] is recorded as well. Only the functions created from host closures can be named; for the others, this method has no effect.
+    ///
+    /// # Argument
+    ///
+    /// * `name` - The name of this [Function].
+    pub fn set_name(&self, name: &str) {
+        let registry = host_registry();
+        if let Some(key) = self.key {
+            registry
+                .names
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .insert(key, name.to_string());
+            record(RegistrationEvent::Named {
+                key,
+                name: name.to_string(),
+            });
+
+            // compare with the signature of the host function previously registered under the same name
+            let signature = registry
+                .types
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .get(&key)
+                .cloned();
+            if let Some(signature) = signature {
+                let previous = registry
+                    .signatures_by_name
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .insert(name.to_string(), signature.clone());
+                if let Some(previous) = previous.filter(|previous| *previous != signature) {
+                    record(RegistrationEvent::SignatureChanged {
+                        key,
+                        name: name.to_string(),
+                        old: signature_to_func_type(previous),
+                        new: signature_to_func_type(signature),
+                    });
+                }
+            }
+        }
+    }
+
+    /// Puts this [Function] into the given category, for grouping the statistics by [host_stats_by_category](crate::host_stats_by_category).
+    ///
+    /// Only the functions created from host closures can be categorized; for the others, this method has no effect.
+    ///
+    /// # Argument
+    ///
+    /// * `category` - The category of this [Function], for example, `io`.
+    pub fn set_category(&self, category: &str) {
+        let registry = host_registry();
+        if let Some(key) = self.key {
+            registry
+                .categories
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .insert(key, category.to_string());
+        }
+    }
+
+    /// Returns the name attached by [set_name](crate::Function::set_name), if any.
+    pub fn name(&self) -> Option<String> {
+        let registry = host_registry();
+        self.key.and_then(|key| {
+            registry
+                .names
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .get(&key)
+                .cloned()
+        })
+    }
+
+    /// Returns the underlying wasm type of this [Function].
+    ///
+    /// The returned [FuncType] borrows the function type context owned by this [Function], so dropping it never deletes the context. Therefore, calling this method repeatedly neither leaks nor double-frees the context.
+    ///
+    /// # Errors
+    ///
+    /// If fail to get the function type, then an error is returned.
+    ///
+    pub fn ty(&self) -> WasmEdgeResult<FuncType> {
+        let ty = unsafe { ffi::WasmEdge_FunctionInstanceGetFunctionType(self.inner.0) };
+        unsafe { FuncType::from_raw_borrowed(ty) }
+    }
+
+    /// Checks if this [Function] can satisfy an import requiring the given function type.
+    ///
+    /// The check follows [matches_loosely](crate::FuncType::matches_loosely): an import requiring the [wildcard](crate::FuncType::wildcard) is satisfied by any function, otherwise the types of the arguments and returns must be the same.
+    ///
+    /// # Argument
+    ///
+    /// * `expected_ty` - The function type the import requires.
+    ///
+    /// # Error
+    ///
+    /// If this [Function] can't satisfy the import, then a `FuncError::ImportMismatch` error describing the first difference is returned. If fail to get the function type, then a `FuncError::Type` error is returned.
+    pub fn satisfies_import(&self, expected_ty: &FuncType) -> Result<(), FuncError> {
+        let ty = self.ty().map_err(|_| FuncError::Type)?;
+        if ty.matches_loosely(expected_ty) {
+            return Ok(());
+        }
+
+        let diff = |kind: &str, expected: Vec<ValType>, found: Vec<ValType>| {
+            if expected.len() != found.len() {
+                return Some(format!(
+                    "expected {} {}, found {}",
+                    expected.len(),
+                    kind,
+                    found.len()
+                ));
+            }
+            expected
+                .iter()
+                .zip(found.iter())
+                .position(|(expected, found)| expected != found)
+                .map(|idx| {
+                    format!(
+                        "{} {}: expected {:?}, found {:?}",
+                        kind.trim_end_matches('s'),
+                        idx,
+                        expected[idx],
+                        found[idx]
+                    )
+                })
+        };
+        let msg = diff(
+            "arguments",
+            expected_ty.params_type_iter().collect(),
+            ty.params_type_iter().collect(),
+        )
+        .or_else(|| {
+            diff(
+                "returns",
+                expected_ty.returns_type_iter().collect(),
+                ty.returns_type_iter().collect(),
+            )
+        })
+        .unwrap_or_default();
+        Err(FuncError::ImportMismatch(msg))
+    }
+
+    /// Returns a buffer holding one zero value of each return type of this [Function].
+    ///
+    /// Each element is the zero of the corresponding return type, or the null reference for a reference type, so the buffer is sized and typed for the returns of this [Function] and can be reused across calls.
+    ///
+    /// # Error
+    ///
+    /// If fail to get the function type, then an error is returned.
+    pub fn prepare_output(&self) -> WasmEdgeResult<Vec<WasmValue>> {
+        self.ty()?
+            .returns_type_iter()
+            .map(|ty| match ty {
+                ValType::FuncRef => Ok(WasmValue::from_null_ref(RefType::FuncRef)),
+                ValType::ExternRef => Ok(WasmValue::from_null_ref(RefType::ExternRef)),
+                _ => WasmValue::of(ty, 0),
+            })
+            .collect()
+    }
+
+    /// Returns the numbers of the arguments and returns of this [Function].
+    ///
+    /// The numbers are read from the function type the function instance points to, without producing a [FuncType].
+    ///
+    /// # Error
+    ///
+    /// If fail to get the function type, then an error is returned.
+    pub fn signature_arity(&self) -> WasmEdgeResult<(u32, u32)> {
+        let ty = unsafe { ffi::WasmEdge_FunctionInstanceGetFunctionType(self.inner.0) };
+        match ty.is_null() {
+            true => Err(WasmEdgeError::Func(FuncError::Type)),
+            false => Ok(unsafe {
+                (
+                    ffi::WasmEdge_FunctionTypeGetParametersLength(ty),
+                    ffi::WasmEdge_FunctionTypeGetReturnsLength(ty),
+                )
+            }),
+        }
+    }
+
+    /// Asserts that the signature of this [Function] is exactly the given types.
+    ///
+    /// This helper is only available in test and debug builds.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The expected types of the arguments.
+    ///
+    /// * `returns` - The expected types of the returns.
+    ///
+    /// # Panics
+    ///
+    /// If fail to get the function type, or the signature of this [Function] doesn't match the given types, then this function panics with both the expected and the actual signatures.
+    #[cfg(any(test, debug_assertions))]
+    pub fn assert_signature(&self, params: &[ValType], returns: &[ValType]) {
+        let ty = self
+            .ty()
+            .expect("[wasmedge-sys] fail to get the function type");
+        let actual_params = ty.params_type_iter().collect::<Vec<_>>();
+        let actual_returns = ty.returns_type_iter().collect::<Vec<_>>();
+        if actual_params != params || actual_returns != returns {
+            panic!(
+                "[wasmedge-sys] signature mismatch\n expected: {:?} -> {:?}\n   actual: {:?} -> {:?}",
+                params, returns, actual_params, actual_returns
+            );
+        }
+    }
+
+    /// Runs this host function and returns the result.
+    ///
+    /// # Arguments
+    ///
+    /// * `engine` - The object implements Engine trait.
+    ///
+    /// * `args` - The arguments passed to the host function.
+    ///
+    /// # Error
+    ///
+    /// If fail to run the host function, then an error is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use wasmedge_sys::{FuncType, Function, WasmValue, Executor};
+    /// use wasmedge_types::ValType;
+    ///
+    /// fn real_add(input: Vec<WasmValue>) -> Result<Vec<WasmValue>, u8> {
+    ///     println!("Rust: Entering Rust function real_add");
+    ///
+    ///     if input.len() != 2 {
+    ///         return Err(1);
+    ///     }
+    ///
+    ///     let a = if input[0].ty() == ValType::I32 {
+    ///         input[0].to_i32()
+    ///     } else {
+    ///         return Err(2);
+    ///     };
+    ///
+    ///     let b = if input[1].ty() == ValType::I32 {
+    ///         input[1].to_i32()
+    ///     } else {
+    ///         return Err(3);
+    ///     };
+    ///
+    ///     let c = a + b;
+    ///     println!("Rust: calcuating in real_add c: {:?}", c);
+    ///
+    ///     println!("Rust: Leaving Rust function real_add");
+    ///     Ok(vec![WasmValue::from_i32(c)])
+    /// }
+    ///
+    /// // create a FuncType
+    /// let result = FuncType::create(vec![ValType::I32; 2], vec![ValType::I32]);
+    /// assert!(result.is_ok());
+    /// let func_ty = result.unwrap();
+    /// // create a host function
+    /// let result = Function::create(&func_ty, Box::new(real_add), 0);
+    /// assert!(result.is_ok());
+    /// let host_func = result.unwrap();
+    ///
+    /// // create an Executor instance
+    /// let result = Executor::create(None, None);
+    /// assert!(result.is_ok());
+    /// let mut executor = result.unwrap();
+    ///
+    /// // run this function
+    /// let result = host_func.call(
+    ///     &mut executor,
+    ///     vec![WasmValue::from_i32(1), WasmValue::from_i32(2)],
+    /// );
+    /// assert!(result.is_ok());
+    /// let returns = result.unwrap();
+    /// assert_eq!(returns[0].to_i32(), 3);
+    /// ```
+    pub fn call<E: Engine>(
+        &self,
+        engine: &mut E,
+        args: impl IntoIterator<Item = WasmValue>,
+    ) -> WasmEdgeResult<Vec<WasmValue>> {
+        engine
+            .run_func(self, args)
+            .map_err(|error| match self.name() {
+                Some(name) => WasmEdgeError::Func(FuncError::Trapped {
+                    name,
+                    error: Box::new(error),
+                }),
+                None => error,
+            })
+    }
+
+    /// Runs this host function, and returns the results together with the wall-clock time the call took.
+    ///
+    /// The time is measured around the engine call, so it includes the overhead of dispatching the call. This is a lightweight alternative to the [Statistics](crate::Statistics) for simple profiling.
+    ///
+    /// # Arguments
+    ///
+    /// * `engine` - The object implementing the [Engine](crate::Engine) trait.
+    ///
+    /// * `args` - The arguments passed to the host function.
+    ///
+    /// # Error
+    ///
+    /// If fail to run the host function, then an error is returned.
+    pub fn call_timed<E: Engine>(
+        &self,
+        engine: &mut E,
+        args: impl IntoIterator<Item = WasmValue>,
+    ) -> WasmEdgeResult<(Vec<WasmValue>, Duration)> {
+        let start = std::time::Instant::now();
+        let returns = self.call(engine, args)?;
+        Ok((returns, start.elapsed()))
+    }
+
+    /// Runs this host function with the arguments decoded from a JSON array, and returns the returns encoded as a JSON array.
+    ///
+    /// The elements of `args` are decoded according to the types of the arguments of this [Function]: `i32` and `i64` from JSON integers in range, and `f32` and `f64` from JSON numbers. The returns are encoded the same way; a non-finite float is encoded as `null`. `v128` and reference types are not supported.
+    ///
+    /// # Arguments
+    ///
+    /// * `engine` - The object implementing the [Engine](crate::Engine) trait.
+    ///
+    /// * `args` - The JSON array of the arguments.
+    ///
+    /// # Error
+    ///
+    /// If `args` is not an array, its length or the types of its elements don't match the types of the arguments, or the function type contains unsupported types, then a `FuncError::Json` error is returned. If fail to run the host function, then an error is returned.
+    #[cfg(feature = "serde_json")]
+    pub fn call_json<E: Engine>(
+        &self,
+        engine: &mut E,
+        args: &serde_json::Value,
+    ) -> WasmEdgeResult<serde_json::Value> {
+        let json_err = |msg: String| WasmEdgeError::Func(FuncError::Json(msg));
+
+        let ty = self.ty()?;
+        let args = args
+            .as_array()
+            .ok_or_else(|| json_err(format!("expect a JSON array of arguments, found {}", args)))?;
+        if args.len() != ty.params_len() as usize {
+            return Err(json_err(format!(
+                "expect {} arguments, found {}",
+                ty.params_len(),
+                args.len()
+            )));
+        }
+
+        let mut values = Vec::with_capacity(args.len());
+        for (idx, (arg, param)) in args.iter().zip(ty.params_type_iter()).enumerate() {
+            let value = match param {
+                ValType::I32 => arg
+                    .as_i64()
+                    .and_then(|v| i32::try_from(v).ok())
+                    .map(WasmValue::from_i32),
+                ValType::I64 => arg.as_i64().map(WasmValue::from_i64),
+                ValType::F32 => arg.as_f64().map(|v| WasmValue::from_f32(v as f32)),
+                ValType::F64 => arg.as_f64().map(WasmValue::from_f64),
+                _ => {
+                    return Err(json_err(format!(
+                        "unsupported type of the argument {}: {:?}",
+                        idx, param
+                    )))
+                }
+            };
+            values.push(value.ok_or_else(|| {
+                json_err(format!(
+                    "expect a {:?} value for the argument {}, found {}",
+                    param, idx, arg
+                ))
+            })?);
+        }
+
+        let returns = self.call(engine, values)?;
+        returns
+            .into_iter()
+            .map(|value| match value.ty() {
+                ValType::I32 => Ok(serde_json::Value::from(value.to_i32())),
+                ValType::I64 => Ok(serde_json::Value::from(value.to_i64())),
+                ValType::F32 => Ok(serde_json::Value::from(value.to_f32())),
+                ValType::F64 => Ok(serde_json::Value::from(value.to_f64())),
+                ty => Err(json_err(format!(
+                    "unsupported type of the return: {:?}",
+                    ty
+                ))),
+            })
+            .collect::<WasmEdgeResult<Vec<_>>>()
+            .map(serde_json::Value::Array)
+    }
+
+    /// Runs all the registered host functions of which the signature matches the given function type, and returns the results.
+    ///
+    /// Each matching host function is invoked with a copy of the given arguments through the given engine. The order of the invocations, and therefore the order of the results, is unspecified.
+    ///
+    /// # Arguments
+    ///
+    /// * `engine` - The object implements Engine trait.
+    ///
+    /// * `ty` - The function type to match.
+    ///
+    /// * `args` - The arguments passed to each matching host function.
+    pub fn call_all_matching<E: Engine>(
+        engine: &mut E,
+        ty: &FuncType,
+        args: impl IntoIterator<Item = WasmValue>,
+    ) -> Vec<WasmEdgeResult<Vec<WasmValue>>> {
+        let registry = host_registry();
+        let params = ty.params_type_iter().collect::<Vec<_>>();
+        let returns = ty.returns_type_iter().collect::<Vec<_>>();
+        let args = args.into_iter().collect::<Vec<_>>();
+
+        let keys = registry
+            .types
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .filter(|(_, (p, r))| *p == params && *r == returns)
+            .map(|(key, _)| *key)
+            .collect::<Vec<_>>();
+
+        keys.into_iter()
+            .map(|key| {
+                // bind a temporary function instance to the registered host function
+                let ctx = unsafe {
+                    ffi::WasmEdge_FunctionInstanceCreateBinding(
+                        ty.inner.0,
+                        Some(wraper_fn),
+                        key as *const usize as *mut c_void,
+                        std::ptr::null_mut(),
+                        0,
+                    )
+                };
+                if ctx.is_null() {
+                    return Err(WasmEdgeError::Func(FuncError::Create));
+                }
+                let func = Function {
+                    inner: InnerFunc(ctx),
+                    key: None,
+                    registered: false,
+                    cost: 0,
+                };
+                func.call(engine, args.clone())
+            })
+            .collect()
+    }
+
+    /// Checks if the lock of the host function registry is poisoned.
+    ///
+    /// The lock is poisoned if a thread panicked while holding it. A poisoned lock is recovered automatically the next time the registry is locked, which also clears the poison, so registering and calling host functions keep working; this function only reports whether a panic happened since the registry was last locked.
+    pub fn host_funcs_poisoned() -> bool {
+        host_registry().funcs.is_poisoned()
+    }
+
+    /// Returns a reference to this [Function] instance.
+    pub fn as_ref(&self) -> FuncRef {
+        FuncRef {
+            inner: InnerFuncRef(self.inner.0 as *const _),
+            cost: self.cost,
+        }
+    }
+}
+impl Drop for Function {
+    fn drop(&mut self) {
+        // a function moved into a module has a null context, and its closure is released by the module
+        if !self.registered && !self.inner.0.is_null() {
+            unsafe { ffi::WasmEdge_FunctionInstanceDelete(self.inner.0) };
+            if let Some(key) = self.key {
+                release_host_funcs(&[key]);
+            }
+        }
+    }
+}
+
+// Removes the host functions of the given keys from the registry and the side tables, and drops their closures after unlocking the registry.
+//
+// This is called when the owner of the host functions is dropped, that is, the Function itself or the module instance it is moved into.
+pub(crate) fn release_host_funcs(keys: &[usize]) {
+    if keys.is_empty() {
+        return;
+    }
+    let registry = host_registry();
+    let mut host_functions = lock_recovered(&registry.funcs);
+    let mut func_types = registry.types.lock().unwrap_or_else(|e| e.into_inner());
+    // skip the host functions already removed, for example, by retain_host_funcs
+    let keys = keys
+        .iter()
+        .filter(|key| host_functions.contains_key(key))
+        .copied()
+        .collect::<Vec<_>>();
+    let removed = remove_host_funcs(&registry, &mut host_functions, &mut func_types, &keys);
+    drop(func_types);
+    drop(host_functions);
+
+    for key in keys {
+        record(RegistrationEvent::Dropped { key });
+    }
+    // the closures may own other host functions
+    drop(removed);
+}
+
+// The sender of the jobs to a worker thread of the host functions created by `create_with_deadline`.
+type DeadlineWorker = Sender<(Vec<WasmValue>, Sender<Result<Vec<WasmValue>, u8>>)>;
+
+// Spawns a worker thread running the given closure with the arguments of each job it receives, until its sender is dropped.
+fn spawn_deadline_worker(real_fn: Arc<BoxedFn>) -> DeadlineWorker {
+    let (jobs, rx) = std::sync::mpsc::channel::<(Vec<WasmValue>, Sender<_>)>();
+    std::thread::spawn(move || {
+        for (input, tx) in rx {
+            // the receiver is gone if the call has timed out
+            let _ = tx.send(real_fn(input));
+        }
+    });
+    jobs
+}
+
+// Locks the given registry, recovering it and clearing the poison if a thread panicked while holding the lock.
+fn lock_recovered<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| {
+        mutex.clear_poison();
+        e.into_inner()
+    })
+}
+
+// Widens the arguments to the declared types of the parameters. Narrowing is never performed.
+fn coerce_args(params: &[ValType], input: &mut [WasmValue]) {
+    for (arg, param) in input.iter_mut().zip(params) {
+        match (arg.ty(), param) {
+            (ValType::I32, ValType::I64) => *arg = WasmValue::from_i64(arg.to_i32().into()),
+            (ValType::F32, ValType::F64) => *arg = WasmValue::from_f64(arg.to_f32().into()),
+            _ => {}
+        }
+    }
+}
+
+/// Defines the registration events of host functions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegistrationEvent {
+    /// A host function is created.
+    Created {
+        /// The key of the host function in the registry.
+        key: usize,
+        /// The signature of the host function.
+        ty: wasmedge_types::FuncType,
+        /// The function cost in the [Statistics](crate::Statistics).
+        cost: u64,
+    },
+    /// A host function is named by [set_name](crate::Function::set_name).
+    Named {
+        /// The key of the host function in the registry.
+        key: usize,
+        /// The name of the host function.
+        name: String,
+    },
+    /// A host function is named by [set_name](crate::Function::set_name) with the name of a previously registered host function, but their signatures differ, for example, after reloading a plugin with an incompatible update.
+    SignatureChanged {
+        /// The key of the newly named host function in the registry.
+        key: usize,
+        /// The name of the host functions.
+        name: String,
+        /// The signature of the previously registered host function.
+        old: wasmedge_types::FuncType,
+        /// The signature of the newly named host function.
+        new: wasmedge_types::FuncType,
+    },
+    /// A host function is dropped.
+    Dropped {
+        /// The key of the host function in the registry.
+        key: usize,
+    },
+}
+
+/// Sets the handler invoked when the load factor of the host function registry crosses the given threshold.
+///
+/// The load factor is the number of the registered host functions divided by the capacity of the registry. When creating a host function makes the load factor reach `threshold` from below, `handler` is called with the current length and capacity of the registry, so that an application can log it or release host functions before creating a host function fails. The handler replaces the previous one.
+///
+/// # Arguments
+///
+/// * `threshold` - The load factor in `(0, 1]` at which the handler is invoked.
+///
+/// * `handler` - The function called with the length and capacity of the registry.
+pub fn set_capacity_warning_handler(threshold: f64, handler: BoxedCapacityHandler) {
+    let registry = host_registry();
+    *registry
+        .capacity_warning_handler
+        .lock()
+        .unwrap_or_else(|e| e.into_inner()) = Some((threshold, handler));
+}
+
+fn warn_capacity(len: usize, capacity: usize) {
+    let registry = host_registry();
+    let handler = registry
+        .capacity_warning_handler
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    if let Some((threshold, handler)) = handler.as_ref() {
+        let load = |len: usize| len as f64 / capacity as f64;
+        if capacity > 0 && load(len) >= *threshold && load(len - 1) < *threshold {
+            handler(len, capacity);
+        }
+    }
+}
+
+/// Sets the global recorder of the registration events of host functions.
+///
+/// The recorder is invoked on each creation and drop of a [Function], with the key, signature and cost of the host function, but not the closure. This gives a replayable log of what an embedding registered. Pass `None` to remove the recorder.
+///
+/// # Argument
+///
+/// * `recorder` - The recorder to set.
+pub fn set_registration_recorder(recorder: Option<BoxedRecorder>) {
+    let registry = host_registry();
+    *registry.recorder.lock().unwrap_or_else(|e| e.into_inner()) = recorder;
+}
+
+fn record(event: RegistrationEvent) {
+    let registry = host_registry();
+    let recorder = registry.recorder.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(recorder) = recorder.as_ref() {
+        recorder(&event);
+    }
+}
+
+// Converts a FuncType into its counterpart in wasmedge-types without consuming it.
+fn func_type_of(ty: &FuncType) -> wasmedge_types::FuncType {
+    signature_to_func_type((
+        ty.params_type_iter().collect(),
+        ty.returns_type_iter().collect(),
+    ))
+}
+
+fn signature_to_func_type((args, returns): HostFuncSignature) -> wasmedge_types::FuncType {
+    wasmedge_types::FuncType::new(
+        (!args.is_empty()).then_some(args),
+        (!returns.is_empty()).then_some(returns),
+    )
+}
+
+/// Defines the policies for validating the number of the items produced by a [streaming host function](crate::Function::create_streaming).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StreamingPolicy {
+    /// Rejects both under-production and over-production. This is the default policy.
+    #[default]
+    Strict,
+    /// Truncates over-production to the number of the returns, but still rejects under-production.
+    Lenient,
+}
+
+/// The thread pool which [host functions](crate::Function) created by [create_on_pool](crate::Function::create_on_pool) are dispatched to.
+///
+/// The trait is implemented by adapting an existing thread pool, for example, by forwarding the job to its `execute` or `spawn` method.
+pub trait ThreadPool: Send + Sync {
+    /// Runs the given job on a thread of the pool.
+    fn execute(&self, job: Box<dyn FnOnce() + Send>);
+}
+
+// Keeps the given trap as the error of the current host function call, and returns the error code reported to WasmEdge.
+fn raise_host_trap(trap: HostTrap) -> u8 {
+    LAST_HOST_ERROR.with(|last| *last.borrow_mut() = Some(FuncError::HostTrap(trap)));
+    ffi::WasmEdge_ErrCode_ExecutionFailed as u8
+}
+
+/// Defines the error returned by the host functions created by [create_with_error](crate::Function::create_with_error), carrying an error code together with a message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HostFuncError {
+    /// The error code reported to WasmEdge.
+    pub code: u8,
+    /// The message describing what went wrong.
+    pub message: String,
+}
+impl HostFuncError {
+    /// Creates a [HostFuncError] with the given code and message.
+    ///
+    /// # Arguments
+    ///
+    /// * `code` - The error code reported to WasmEdge. Notice that `0` means success, so it should not be used.
+    ///
+    /// * `message` - The message describing what went wrong.
+    pub fn new(code: u8, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+// Replaces the given error with the error of the last failed host function call in the current thread, if any.
+pub(crate) fn take_host_error(error: WasmEdgeError) -> WasmEdgeError {
+    match LAST_HOST_ERROR.with(|last| last.borrow_mut().take()) {
+        Some(host_error) => WasmEdgeError::Func(host_error),
+        None => error,
+    }
+}
+
+// Clears the error of the last failed host function call in the current thread.
+pub(crate) fn clear_host_error() {
+    LAST_HOST_ERROR.with(|last| last.borrow_mut().take());
+}
+
+/// Defines the convention for returning a typed error payload from a host function through a designated externref.
+///
+/// A host function following the convention declares an `ExternRef` value as its last return, which is the designated externref. On success, the designated externref is a null reference created by [ok_ref](crate::HostResult::ok_ref). On error, the designated externref refers to the error object passed to [err_ref](crate::HostResult::err_ref), and the other returns are unspecified. Therefore, the guest distinguishes success from error by checking the designated externref with the `ref.is_null` instruction, and passes the error object back to the host if it is not null. On the host side, [is_err](crate::HostResult::is_err) and [take_err](crate::HostResult::take_err) are used to check and read back the error object.
+///
+/// Notice that the convention is only meaningful when the `reference_types` option is enabled in the [Config](crate::Config).
+#[derive(Debug)]
+pub struct HostResult;
+impl HostResult {
+    /// Returns the designated externref signaling success.
+    pub fn ok_ref() -> WasmValue {
+        WasmValue::from_null_ref(RefType::ExternRef)
+    }
+
+    /// Encodes an error object into the designated externref signaling error.
+    ///
+    /// The error object is moved to the heap, and is owned by the returned externref until it is read back by [take_err](crate::HostResult::take_err). If the error object is never read back, then it is leaked.
+    ///
+    /// # Argument
+    ///
+    /// * `obj` - The error object.
+    pub fn err_ref<T>(obj: T) -> WasmValue {
+        let obj = Box::into_raw(Box::new(obj));
+        WasmValue::from_extern_ref(unsafe { &mut *obj })
+    }
+
+    /// Checks if the designated externref signals error.
+    ///
+    /// # Argument
+    ///
+    /// * `val` - The designated externref.
+    pub fn is_err(val: &WasmValue) -> bool {
+        val.ty() == ValType::ExternRef && !val.is_null_ref()
+    }
+
+    /// Reads back the error object from the designated externref.
+    ///
+    /// If the designated externref signals success, then `None` is returned.
+    ///
+    /// # Argument
+    ///
+    /// * `val` - The designated externref.
+    ///
+    /// # Safety
+    ///
+    /// The designated externref must be created by [err_ref](crate::HostResult::err_ref) with an error object of type `T`, and the error object must be read back at most once.
+    pub unsafe fn take_err<T>(val: &WasmValue) -> Option<T> {
+        match Self::is_err(val) {
+            true => val
+                .extern_ref::<T>()
+                .map(|obj| *Box::from_raw(obj as *const T as *mut T)),
+            false => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct InnerFunc(pub(crate) *mut ffi::WasmEdge_FunctionInstanceContext);
+unsafe impl Send for InnerFunc {}
+unsafe impl Sync for InnerFunc {}
+
+/// Defines the type of a [host function](crate::Function).
+///
+/// A WasmEdge [FuncType] classifies the signature of a [Function], including the type information of both the arguments and the returns.
+#[derive(Debug)]
+pub struct FuncType {
+    pub(crate) inner: InnerFuncType,
+    pub(crate) registered: bool,
+    pub(crate) wildcard: bool,
+}
+impl FuncType {
+    /// Create a new [FuncType] to be associated with the given arguments and returns.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - The agrgument types of a [Function].
+    ///
+    /// * `returns` - The types of the returns of a [Function].
+    ///
+    /// # Error
+    ///
+    /// If fail to create a [FuncType], then an error is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use wasmedge_sys::FuncType;
+    /// use wasmedge_types::ValType;
+    ///
+    /// let func_ty = FuncType::create(vec![ValType::I32;2], vec![ValType::I32]).expect("fail to create a FuncType");
+    /// ```
+    pub fn create<I: IntoIterator<Item = ValType>, R: IntoIterator<Item = ValType>>(
+        args: I,
+        returns: R,
+    ) -> WasmEdgeResult<Self> {
+        let param_tys = args
+            .into_iter()
+            .map(|x| x.into())
+            .collect::<Vec<ffi::WasmEdge_ValType>>();
+        let ret_tys = returns
+            .into_iter()
+            .map(|x| x.into())
+            .collect::<Vec<ffi::WasmEdge_ValType>>();
+
+        let ctx = unsafe {
+            ffi::WasmEdge_FunctionTypeCreate(
+                param_tys.as_ptr() as *const _,
+                param_tys.len() as u32,
+                ret_tys.as_ptr() as *const _,
+                ret_tys.len() as u32,
+            )
+        };
+        match ctx.is_null() {
+            true => Err(WasmEdgeError::FuncTypeCreate),
+            false => Ok(unsafe { Self::from_raw_owned(ctx)? }),
+        }
+    }
+
+    /// Creates a function type from the WasmEdge value type codes of the arguments and returns.
+    ///
+    /// The codes are those of `WasmEdge_ValType`, which are the same as the encodings of the value types in the WebAssembly binary format: `0x7F` for `i32`, `0x7E` for `i64`, `0x7D` for `f32`, `0x7C` for `f64`, `0x7B` for `v128`, `0x70` for `funcref`, and `0x6F` for `externref`.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The type codes of the arguments.
+    ///
+    /// * `returns` - The type codes of the returns.
+    ///
+    /// # Error
+    ///
+    /// If any of the codes is unknown, then a `FuncError::UnknownTypeCode` error is returned. If fail to create a [FuncType], then an error is returned.
+    pub fn from_type_codes(params: &[u8], returns: &[u8]) -> WasmEdgeResult<Self> {
+        let to_types = |codes: &[u8]| {
+            codes
+                .iter()
+                .map(|code| match code {
+                    0x7F => Ok(ValType::I32),
+                    0x7E => Ok(ValType::I64),
+                    0x7D => Ok(ValType::F32),
+                    0x7C => Ok(ValType::F64),
+                    0x7B => Ok(ValType::V128),
+                    0x70 => Ok(ValType::FuncRef),
+                    0x6F => Ok(ValType::ExternRef),
+                    _ => Err(WasmEdgeError::Func(FuncError::UnknownTypeCode(*code))),
+                })
+                .collect::<WasmEdgeResult<Vec<_>>>()
+        };
+        Self::create(to_types(params)?, to_types(returns)?)
+    }
+
+    /// Creates a [FuncType] taking the ownership of the given function type context.
+    ///
+    /// The context is deleted when the returned [FuncType] is dropped. Use this method for the contexts the caller is responsible to free, for example, the ones created by `WasmEdge_FunctionTypeCreate`.
+    ///
+    /// # Argument
+    ///
+    /// * `ctx` - The raw function type context.
+    ///
+    /// # Error
+    ///
+    /// If the given context is null, then an error is returned.
+    ///
+    /// # Safety
+    ///
+    /// The given context must be valid, and must not be owned or deleted by anything else.
+    pub unsafe fn from_raw_owned(
+        ctx: *mut ffi::WasmEdge_FunctionTypeContext,
+    ) -> WasmEdgeResult<Self> {
+        match ctx.is_null() {
+            true => Err(WasmEdgeError::Func(FuncError::Type)),
+            false => {
+                #[cfg(test)]
+                OWNED_FUNC_TYPES.with(|count| count.set(count.get() + 1));
+
+                Ok(Self {
+                    inner: InnerFuncType(ctx),
+                    registered: false,
+                    wildcard: false,
+                })
+            }
+        }
+    }
+
+    /// Creates a [FuncType] borrowing the given function type context.
+    ///
+    /// The context is never deleted by the returned [FuncType]. Use this method for the contexts owned by other objects, for example, the ones returned by `WasmEdge_FunctionInstanceGetFunctionType`, `WasmEdge_ImportTypeGetFunctionType`, `WasmEdge_ExportTypeGetFunctionType`, and `WasmEdge_VMGetFunctionType`.
+    ///
+    /// # Argument
+    ///
+    /// * `ctx` - The raw function type context.
+    ///
+    /// # Error
+    ///
+    /// If the given context is null, then an error is returned.
+    ///
+    /// # Safety
+    ///
+    /// The given context must be valid, and must outlive the returned [FuncType].
+    pub unsafe fn from_raw_borrowed(
+        ctx: *const ffi::WasmEdge_FunctionTypeContext,
+    ) -> WasmEdgeResult<Self> {
+        match ctx.is_null() {
+            true => Err(WasmEdgeError::Func(FuncError::Type)),
+            false => Ok(Self {
+                inner: InnerFuncType(ctx as *mut _),
+                registered: true,
+                wildcard: false,
+            }),
+        }
+    }
+
+    /// Returns the number of the arguments of a [Function].
+    pub fn params_len(&self) -> u32 {
+        unsafe { ffi::WasmEdge_FunctionTypeGetParametersLength(self.inner.0) }
+    }
+
+    /// Returns an Iterator of the arguments of a [Function].
+    ///
+    /// The types are yielded in the declaration order, that is, exactly the order passed to [create](crate::FuncType::create).
+    pub fn params_type_iter(&self) -> impl Iterator<Item = ValType> {
+        let len = self.params_len();
+        let mut types = Vec::with_capacity(len as usize);
+        unsafe {
+            ffi::WasmEdge_FunctionTypeGetParameters(self.inner.0, types.as_mut_ptr(), len);
+            types.set_len(len as usize);
+        }
+
+        types.into_iter().map(Into::into)
+    }
+
+    ///Returns the number of the returns of a [Function].
+    pub fn returns_len(&self) -> u32 {
+        unsafe { ffi::WasmEdge_FunctionTypeGetReturnsLength(self.inner.0) }
+    }
+
+    /// Returns an Iterator of the return types of a [Function].
+    ///
+    /// The types are yielded in the declaration order, that is, exactly the order passed to [create](crate::FuncType::create).
+    pub fn returns_type_iter(&self) -> impl Iterator<Item = ValType> {
+        let len = self.returns_len();
+        let mut types = Vec::with_capacity(len as usize);
+        unsafe {
+            ffi::WasmEdge_FunctionTypeGetReturns(self.inner.0, types.as_mut_ptr(), len);
+            types.set_len(len as usize);
+        }
+
+        types.into_iter().map(Into::into)
+    }
+
+    /// Returns an Iterator of the arguments of a [Function] paired with their indices.
+    ///
+    /// The indices are sequential from zero, in the same order as [params_type_iter](crate::FuncType::params_type_iter).
+    pub fn params_indexed(&self) -> impl Iterator<Item = (u32, ValType)> {
+        (0..).zip(self.params_type_iter())
+    }
+
+    /// Returns an Iterator of the return types of a [Function] paired with their indices.
+    ///
+    /// The indices are sequential from zero, in the same order as [returns_type_iter](crate::FuncType::returns_type_iter).
+    pub fn returns_indexed(&self) -> impl Iterator<Item = (u32, ValType)> {
+        (0..).zip(self.returns_type_iter())
+    }
+
+    /// Returns the wildcard [FuncType] which matches any signature.
+    ///
+    /// The wildcard is a sentinel for generic dispatch tables. It is compatible with everything in [matches_loosely](crate::FuncType::matches_loosely) and [validate_args](crate::FuncType::validate_args), but it doesn't describe a real signature: it reports no arguments and no returns, and it can't be used to create a [Function].
+    ///
+    /// # Error
+    ///
+    /// If fail to create the wildcard [FuncType], then an error is returned.
+    pub fn wildcard() -> WasmEdgeResult<Self> {
+        let mut ty = Self::create([], [])?;
+        ty.wildcard = true;
+        Ok(ty)
+    }
+
+    /// Checks if this [FuncType] is the [wildcard](crate::FuncType::wildcard).
+    pub fn is_wildcard(&self) -> bool {
+        self.wildcard
+    }
+
+    /// Checks if this [FuncType] is compatible with the given one.
+    ///
+    /// Two [FuncType]s are compatible if either of them is the [wildcard](crate::FuncType::wildcard), or they have the same types of the arguments and returns.
+    ///
+    /// # Argument
+    ///
+    /// * `other` - The [FuncType] to compare with.
+    pub fn matches_loosely(&self, other: &FuncType) -> bool {
+        self.wildcard
+            || other.wildcard
+            || (self.params_type_iter().eq(other.params_type_iter())
+                && self.returns_type_iter().eq(other.returns_type_iter()))
+    }
+
+    /// Validates the given arguments against the types of the arguments of this [FuncType].
+    ///
+    /// The [wildcard](crate::FuncType::wildcard) accepts any arguments.
+    ///
+    /// # Argument
+    ///
+    /// * `args` - The arguments to validate.
+    ///
+    /// # Error
+    ///
+    /// If the number or the types of the arguments don't match, then an error is returned.
+    pub fn validate_args(&self, args: &[WasmValue]) -> WasmEdgeResult<()> {
+        match self.wildcard || self.params_type_iter().eq(args.iter().map(|arg| arg.ty())) {
+            true => Ok(()),
+            false => Err(WasmEdgeError::Core(CoreError::Execution {
+                kind: CoreExecutionError::FuncTypeMismatch,
+                trace: vec![],
+            })),
+        }
+    }
+
+    /// Compares this [FuncType] with another one, including the labels of the arguments and returns.
+    ///
+    /// WasmEdge doesn't attach labels to function types yet, so this method is currently the same as the structural comparison by `==`. It is reserved for the labels which the function-references proposal may introduce.
+    ///
+    /// # Argument
+    ///
+    /// * `other` - The [FuncType] to compare with.
+    pub fn eq_with_labels(&self, other: &FuncType) -> bool {
+        self == other
+    }
+
+    /// Checks if this [FuncType] has no arguments.
+    pub fn is_nullary(&self) -> bool {
+        self.params_len() == 0
+    }
+
+    /// Checks if this [FuncType] has no returns.
+    pub fn is_void(&self) -> bool {
+        self.returns_len() == 0
+    }
+
+    /// Returns the numbers of the arguments and returns of this [FuncType].
+    pub fn arity(&self) -> (u32, u32) {
+        (self.params_len(), self.returns_len())
+    }
+
+    /// Encodes this [FuncType] into its binary form in the type section of a WebAssembly module.
+    ///
+    /// The binary form consists of the `0x60` prefix, followed by the vector of the argument types and the vector of the return types. The length of each vector is encoded in unsigned LEB128.
+    pub fn encode_wasm(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.encoded_len());
+        bytes.push(0x60);
+        for tys in [
+            self.params_type_iter().collect::<Vec<_>>(),
+            self.returns_type_iter().collect::<Vec<_>>(),
+        ] {
+            let mut len = tys.len() as u32;
+            loop {
+                let byte = (len & 0x7f) as u8;
+                len >>= 7;
+                match len {
+                    0 => {
+                        bytes.push(byte);
+                        break;
+                    }
+                    _ => bytes.push(byte | 0x80),
+                }
+            }
+            bytes.extend(
+                tys.into_iter()
+                    .map(|ty| ffi::WasmEdge_ValType::from(ty) as u8),
+            );
+        }
+        bytes
+    }
+
+    /// Returns the number of bytes the binary form of this [FuncType] occupies, without encoding it.
+    ///
+    /// The returned value always equals the length of the bytes returned by [encode_wasm](crate::FuncType::encode_wasm).
+    pub fn encoded_len(&self) -> usize {
+        let leb128_len = |mut n: u32| {
+            let mut len = 1;
+            while n >= 0x80 {
+                n >>= 7;
+                len += 1;
+            }
+            len
+        };
+        let params_len = self.params_len();
+        let returns_len = self.returns_len();
+        1 + leb128_len(params_len)
+            + params_len as usize
+            + leb128_len(returns_len)
+            + returns_len as usize
+    }
+
+    /// Returns a deterministic string encoding of this [FuncType], which can be used as part of the mangled symbol name of a function in an AOT-compiled module.
+    ///
+    /// The encoding is `P`, followed by one letter for each argument type, then `R`, followed by one letter for each return type. The letters are `i` for `i32`, `l` for `i64`, `f` for `f32`, `d` for `f64`, `v` for `v128`, `r` for `funcref`, `e` for `externref`, and `n` for the `None` type. For example, `(i32, i32) -> i32` is encoded as `PiiRi`, and `() -> ()` as `PR`.
+    pub fn mangled_suffix(&self) -> String {
+        let letter = |ty: ValType| match ty {
+            ValType::I32 => 'i',
+            ValType::I64 => 'l',
+            ValType::F32 => 'f',
+            ValType::F64 => 'd',
+            ValType::V128 => 'v',
+            ValType::FuncRef => 'r',
+            ValType::ExternRef => 'e',
+            ValType::None => 'n',
+        };
+        std::iter::once('P')
+            .chain(self.params_type_iter().map(letter))
+            .chain(std::iter::once('R'))
+            .chain(self.returns_type_iter().map(letter))
+            .collect()
+    }
+}
+
+/// Compares the structure of two [FuncType]s, that is, the types of the arguments and returns.
+///
+/// The comparison deliberately ignores any label data, for example, the parameter names which the function-references proposal may attach in the future. Use [eq_with_labels](crate::FuncType::eq_with_labels) when labels matter. It also ignores whether the [FuncType] owns its context, which is an ownership detail rather than a part of the wasm signature.
+impl PartialEq for FuncType {
+    fn eq(&self, other: &Self) -> bool {
+        self.wildcard == other.wildcard
+            && self.params_type_iter().eq(other.params_type_iter())
+            && self.returns_type_iter().eq(other.returns_type_iter())
+    }
+}
+impl Eq for FuncType {}
+/// Compares the types of the arguments and returns of a [FuncType] with the ones of a `wasmedge_types::FuncType`, of which the absent types are regarded as empty. The [wildcard](crate::FuncType::wildcard) equals no `wasmedge_types::FuncType`.
+impl PartialEq<wasmedge_types::FuncType> for FuncType {
+    fn eq(&self, other: &wasmedge_types::FuncType) -> bool {
+        !self.wildcard
+            && self
+                .params_type_iter()
+                .eq(other.args().unwrap_or_default().iter().copied())
+            && self
+                .returns_type_iter()
+                .eq(other.returns().unwrap_or_default().iter().copied())
+    }
+}
+impl PartialEq<FuncType> for wasmedge_types::FuncType {
+    fn eq(&self, other: &FuncType) -> bool {
+        other == self
+    }
+}
+/// Renders a [FuncType] as its signature with the canonical wasm names of the value types, for example, `(i32, i32) -> (i32)`, or `() -> ()` for a function without arguments and returns. The [wildcard](crate::FuncType::wildcard) is rendered as `(*) -> (*)`.
+impl std::fmt::Display for FuncType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.wildcard {
+            return write!(f, "(*) -> (*)");
+        }
+        let name = |ty: ValType| match ty {
+            ValType::I32 => "i32",
+            ValType::I64 => "i64",
+            ValType::F32 => "f32",
+            ValType::F64 => "f64",
+            ValType::V128 => "v128",
+            ValType::FuncRef => "funcref",
+            ValType::ExternRef => "externref",
+            ValType::None => "none",
+        };
+        let join = |types: &mut dyn Iterator<Item = ValType>| {
+            types.map(name).collect::<Vec<_>>().join(", ")
+        };
+        write!(
+            f,
+            "({}) -> ({})",
+            join(&mut self.params_type_iter()),
+            join(&mut self.returns_type_iter())
+        )
+    }
+}
+/// Clones a [FuncType] into a fresh function type context owned by the clone, so the clone stays valid after the original is dropped, even if the original borrows its context.
+impl Clone for FuncType {
+    fn clone(&self) -> Self {
+        let mut ty = Self::create(self.params_type_iter(), self.returns_type_iter())
+            .expect("fail to create a FuncType for the clone");
+        ty.wildcard = self.wildcard;
+        ty
+    }
+}
+impl Drop for FuncType {
+    fn drop(&mut self) {
+        if !self.registered && !self.inner.0.is_null() {
+            unsafe { ffi::WasmEdge_FunctionTypeDelete(self.inner.0) };
+
+            #[cfg(test)]
+            OWNED_FUNC_TYPES.with(|count| count.set(count.get() - 1));
+        }
+    }
+}
+impl From<wasmedge_types::FuncType> for FuncType {
+    fn from(ty: wasmedge_types::FuncType) -> Self {
+        let param_tys: Vec<_> = match ty.args() {
+            Some(args) => args.to_vec(),
+            None => Vec::new(),
+        };
+        let ret_tys: Vec<_> = match ty.returns() {
+            Some(returns) => returns.to_vec(),
+            None => Vec::new(),
+        };
+
+        FuncType::create(param_tys, ret_tys).expect("[wasmedge-sys] Failed to convert wasmedge_types::FuncType into wasmedge_sys::FuncType.")
+    }
+}
+impl From<FuncType> for wasmedge_types::FuncType {
+    fn from(ty: FuncType) -> Self {
+        let args = if ty.params_len() > 0 {
+            let mut args = Vec::with_capacity(ty.params_len() as usize);
+            for ty in ty.params_type_iter() {
+                args.push(ty);
+            }
+            Some(args)
+        } else {
+            None
+        };
+
+        let returns = if ty.returns_len() > 0 {
+            let mut returns = Vec::with_capacity(ty.returns_len() as usize);
+            for ty in ty.returns_type_iter() {
+                returns.push(ty);
+            }
+            Some(returns)
+        } else {
+            None
+        };
+
+        wasmedge_types::FuncType::new(args, returns)
+    }
+}
+
+#[cfg(test)]
+thread_local! {
+    // The number of the live function type contexts owned by `FuncType`s in the current thread.
+    static OWNED_FUNC_TYPES: std::cell::Cell<isize> = const { std::cell::Cell::new(0) };
+}
+
+#[derive(Debug)]
+pub(crate) struct InnerFuncType(pub(crate) *mut ffi::WasmEdge_FunctionTypeContext);
+unsafe impl Send for InnerFuncType {}
+unsafe impl Sync for InnerFuncType {}
+
+/// Polls the given future on the current thread until it completes, parking the thread while the future is pending.
+///
+/// This is not a reactor: the I/O and timers awaited by the future must be driven by an async runtime running on other threads, otherwise the future never completes.
+fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    struct ThreadWaker(std::thread::Thread);
+    impl std::task::Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let mut future = Box::pin(future);
+    let waker = std::task::Waker::from(Arc::new(ThreadWaker(std::thread::current())));
+    let mut cx = std::task::Context::from_waker(&waker);
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            std::task::Poll::Ready(output) => return output,
+            std::task::Poll::Pending => std::thread::park(),
+        }
+    }
+}
+
+/// Defines a reference to a [host function](crate::Function).
+#[derive(Debug, Clone)]
+pub struct FuncRef {
+    pub(crate) inner: InnerFuncRef,
+    pub(crate) cost: u64,
+}
+impl Drop for FuncRef {
+    fn drop(&mut self) {
+        self.inner.0 = std::ptr::null();
+    }
+}
+impl FuncRef {
+    /// Returns the underlying wasm type of the host function this [FuncRef] points to.
+    ///
+    /// # Errors
+    ///
+    /// If this [FuncRef] is null, then a `FuncError::NullPointer` error is returned. If fail to get the function type, then an error is returned.
+    ///
+    pub fn ty(&self) -> WasmEdgeResult<FuncType> {
+        if self.inner.0.is_null() {
+            return Err(WasmEdgeError::Func(FuncError::NullPointer));
+        }
+        let ty = unsafe { ffi::WasmEdge_FunctionInstanceGetFunctionType(self.inner.0 as *mut _) };
+        unsafe { FuncType::from_raw_borrowed(ty) }
+    }
+
+    /// Returns the cost assigned to the host function this [FuncRef] points to at creation.
+    ///
+    /// The cost is only known to the [FuncRef]s taken by [as_ref](crate::Function::as_ref) from the [Function] created with it, the same as [cost](crate::Function::cost). For the others, for example, the ones read from a table, `0` is returned.
+    pub fn cost(&self) -> u64 {
+        self.cost
+    }
+
+    /// Runs this host function the reference refers to.
+    ///
+    /// # Arguments
+    ///
+    /// * `engine` - The object implements Engine trait.
+    ///
+    /// * `args` - The arguments passed to the host function.
+    ///
+    /// # Error
+    ///
+    /// If this [FuncRef] is null, then a `FuncError::NullPointer` error is returned. If fail to run the host function, then an error is returned.
+    ///
+    pub fn call<E: Engine>(
+        &self,
         engine: &mut E,
         args: impl IntoIterator<Item = WasmValue>,
     ) -> WasmEdgeResult<Vec<WasmValue>> {
+        if self.inner.0.is_null() {
+            return Err(WasmEdgeError::Func(FuncError::NullPointer));
+        }
         engine.run_func_ref(self, args)
     }
-}
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct InnerFuncRef(pub(crate) *const ffi::WasmEdge_FunctionInstanceContext);
+unsafe impl Send for InnerFuncRef {}
+unsafe impl Sync for InnerFuncRef {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        error::{CoreCommonError, CoreError, CoreExecutionError},
+        host_function,
+        types::WasmValue,
+        Config, Executor, ImportInstance, ImportModule, ImportObject, Loader, NoPadding,
+        Statistics, Store, Validator, Vm,
+    };
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc, Mutex,
+        },
+        thread,
+    };
+    use wasmedge_types::{wat2wasm, ValType};
+
+    #[test]
+    fn test_func_type() {
+        // test FuncType with args and returns
+        {
+            let param_tys = vec![
+                ValType::I32,
+                ValType::I64,
+                ValType::F32,
+                ValType::F64,
+                ValType::V128,
+                ValType::ExternRef,
+            ];
+            let param_len = param_tys.len();
+            let ret_tys = vec![ValType::FuncRef, ValType::ExternRef, ValType::V128];
+            let ret_len = ret_tys.len();
+
+            // create FuncType
+            let result = FuncType::create(param_tys, ret_tys);
+            assert!(result.is_ok());
+            let func_ty = result.unwrap();
+
+            // check parameters
+            assert_eq!(func_ty.params_len(), param_len as u32);
+            let param_tys = func_ty.params_type_iter().collect::<Vec<_>>();
+            assert_eq!(
+                param_tys,
+                vec![
+                    ValType::I32,
+                    ValType::I64,
+                    ValType::F32,
+                    ValType::F64,
+                    ValType::V128,
+                    ValType::ExternRef,
+                ]
+            );
+
+            // check returns
+            assert_eq!(func_ty.returns_len(), ret_len as u32);
+            let return_tys = func_ty.returns_type_iter().collect::<Vec<_>>();
+            assert_eq!(
+                return_tys,
+                vec![ValType::FuncRef, ValType::ExternRef, ValType::V128]
+            );
+        }
+
+        // test FuncType without args and returns
+        {
+            // create FuncType
+            let result = FuncType::create([], []);
+            assert!(result.is_ok());
+            let func_ty = result.unwrap();
+
+            assert_eq!(func_ty.params_len(), 0);
+            assert_eq!(func_ty.returns_len(), 0);
+        }
+    }
+
+    #[test]
+    fn test_func_type_eq() {
+        let result = FuncType::create(vec![ValType::I32; 2], vec![ValType::I32]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+
+        // the same structure compares equal
+        let result = FuncType::create(vec![ValType::I32; 2], vec![ValType::I32]);
+        assert!(result.is_ok());
+        let same_ty = result.unwrap();
+        assert_eq!(func_ty, same_ty);
+        assert!(func_ty.eq_with_labels(&same_ty));
+
+        // the types obtained from host functions carry no labels
+        let result = Function::create(&func_ty, Box::new(real_add), 0);
+        assert!(result.is_ok());
+        let host_func = result.unwrap();
+        let result = host_func.ty();
+        assert!(result.is_ok());
+        let ty = result.unwrap();
+        assert_eq!(ty, func_ty);
+        assert!(ty.eq_with_labels(&func_ty));
+
+        // different structures compare unequal
+        let result = FuncType::create(vec![ValType::I32; 2], vec![ValType::I64]);
+        assert!(result.is_ok());
+        let other_ty = result.unwrap();
+        assert_ne!(func_ty, other_ty);
+        assert!(!func_ty.eq_with_labels(&other_ty));
+    }
+
+    #[test]
+    fn test_func_type_eq_empty_and_registered() {
+        // the empty arguments and returns
+        let result = FuncType::create(vec![], vec![]);
+        assert!(result.is_ok());
+        let empty_ty = result.unwrap();
+        let result = FuncType::create(vec![], vec![]);
+        assert!(result.is_ok());
+        assert_eq!(empty_ty, result.unwrap());
+        let result = FuncType::create(vec![ValType::I32], vec![]);
+        assert!(result.is_ok());
+        assert_ne!(empty_ty, result.unwrap());
+        let result = FuncType::create(vec![], vec![ValType::I32]);
+        assert!(result.is_ok());
+        assert_ne!(empty_ty, result.unwrap());
+
+        // the ownership of the context is not part of the signature
+        let result = Function::create(&empty_ty, Box::new(|_| Ok(vec![])), 0);
+        assert!(result.is_ok());
+        let host_func = result.unwrap();
+        let result = host_func.ty();
+        assert!(result.is_ok());
+        let borrowed_ty = result.unwrap();
+        assert_ne!(borrowed_ty.registered, empty_ty.registered);
+        assert_eq!(borrowed_ty, empty_ty);
+    }
+
+    #[test]
+    fn test_func_type_eq_cross_crate() {
+        let result = FuncType::create(vec![ValType::I32, ValType::F64], vec![ValType::I64]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+
+        let expected = wasmedge_types::FuncType::new(
+            Some(vec![ValType::I32, ValType::F64]),
+            Some(vec![ValType::I64]),
+        );
+        assert!(func_ty == expected);
+        assert!(expected == func_ty);
+
+        // mismatched types, order and lengths
+        let mismatched = [
+            wasmedge_types::FuncType::new(
+                Some(vec![ValType::I32, ValType::F32]),
+                Some(vec![ValType::I64]),
+            ),
+            wasmedge_types::FuncType::new(
+                Some(vec![ValType::F64, ValType::I32]),
+                Some(vec![ValType::I64]),
+            ),
+            wasmedge_types::FuncType::new(Some(vec![ValType::I32, ValType::F64]), None),
+        ];
+        for ty in mismatched {
+            assert!(func_ty != ty);
+            assert!(ty != func_ty);
+        }
+
+        // the absent types are regarded as empty
+        let result = FuncType::create([], []);
+        assert!(result.is_ok());
+        let empty_ty = result.unwrap();
+        assert!(empty_ty == wasmedge_types::FuncType::new(None, Some(vec![])));
+
+        // the wildcard equals nothing
+        let result = FuncType::wildcard();
+        assert!(result.is_ok());
+        assert!(result.unwrap() != wasmedge_types::FuncType::new(None, None));
+    }
+
+    #[test]
+    fn test_func_type_order() {
+        let kinds = [
+            ValType::I32,
+            ValType::I64,
+            ValType::F32,
+            ValType::F64,
+            ValType::V128,
+            ValType::FuncRef,
+            ValType::ExternRef,
+        ];
+        let param_tys = (0..20)
+            .map(|i| kinds[(i * 3) % kinds.len()])
+            .collect::<Vec<_>>();
+        let ret_tys = (0..20)
+            .map(|i| kinds[(i * 5 + 1) % kinds.len()])
+            .collect::<Vec<_>>();
+
+        let result = FuncType::create(param_tys.clone(), ret_tys.clone());
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+
+        assert_eq!(func_ty.params_type_iter().collect::<Vec<_>>(), param_tys);
+        assert_eq!(func_ty.returns_type_iter().collect::<Vec<_>>(), ret_tys);
+    }
+
+    #[test]
+    fn test_func_type_wildcard() {
+        let result = FuncType::wildcard();
+        assert!(result.is_ok());
+        let wildcard = result.unwrap();
+        assert!(wildcard.is_wildcard());
+
+        let signatures = vec![
+            (vec![], vec![]),
+            (vec![ValType::I32; 2], vec![ValType::I32]),
+            (vec![ValType::F64, ValType::ExternRef], vec![ValType::V128]),
+        ];
+        for (params, returns) in signatures {
+            let result = FuncType::create(params, returns);
+            assert!(result.is_ok());
+            let func_ty = result.unwrap();
+            assert!(!func_ty.is_wildcard());
+            assert!(wildcard.matches_loosely(&func_ty));
+            assert!(func_ty.matches_loosely(&wildcard));
+        }
+
+        // the wildcard accepts any arguments
+        assert!(wildcard.validate_args(&[]).is_ok());
+        assert!(wildcard
+            .validate_args(&[WasmValue::from_i32(1), WasmValue::from_f64(2.0)])
+            .is_ok());
+
+        // the concrete types match exactly
+        let result = FuncType::create(vec![ValType::I32; 2], vec![ValType::I32]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+        let result = FuncType::create(vec![ValType::I64], vec![ValType::I32]);
+        assert!(result.is_ok());
+        let other_ty = result.unwrap();
+        assert!(!func_ty.matches_loosely(&other_ty));
+        assert!(func_ty
+            .validate_args(&[WasmValue::from_i32(1), WasmValue::from_i32(2)])
+            .is_ok());
+        assert!(func_ty.validate_args(&[WasmValue::from_i32(1)]).is_err());
+
+        // the wildcard can't be used to create a host function
+        let result = Function::create(&wildcard, Box::new(real_add), 0);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_func_type_nullary_void() {
+        // () -> ()
+        let result = FuncType::create([], []);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+        assert!(func_ty.is_nullary());
+        assert!(func_ty.is_void());
+
+        // () -> (i32)
+        let result = FuncType::create([], [ValType::I32]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+        assert!(func_ty.is_nullary());
+        assert!(!func_ty.is_void());
+
+        // (i32) -> ()
+        let result = FuncType::create([ValType::I32], []);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+        assert!(!func_ty.is_nullary());
+        assert!(func_ty.is_void());
+    }
+
+    #[test]
+    fn test_func_type_encoded_len() {
+        let signatures = vec![
+            (vec![], vec![]),
+            (vec![ValType::I32; 2], vec![ValType::I32]),
+            (vec![ValType::I64, ValType::F32, ValType::V128], vec![]),
+            (vec![], vec![ValType::FuncRef, ValType::ExternRef]),
+            (vec![ValType::F64; 200], vec![ValType::I32; 128]),
+        ];
+        for (params, returns) in signatures {
+            let result = FuncType::create(params, returns);
+            assert!(result.is_ok());
+            let func_ty = result.unwrap();
+            assert_eq!(func_ty.encoded_len(), func_ty.encode_wasm().len());
+        }
+
+        // (i32, i32) -> i32
+        let result = FuncType::create(vec![ValType::I32; 2], vec![ValType::I32]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+        assert_eq!(
+            func_ty.encode_wasm(),
+            vec![0x60, 0x02, 0x7f, 0x7f, 0x01, 0x7f]
+        );
+
+        // 200 arguments require a two-byte length
+        let result = FuncType::create(vec![ValType::F64; 200], []);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+        assert_eq!(func_ty.encoded_len(), 1 + 2 + 200 + 1);
+        assert_eq!(func_ty.encode_wasm()[1..3], [0xc8, 0x01]);
+    }
+
+    #[test]
+    fn test_func_type_validate_signature() {
+        assert!(validate_signature(&[], &[]).is_ok());
+        assert!(validate_signature(&[ValType::I32; 1000], &[ValType::F64; 1000]).is_ok());
+        assert!(
+            validate_signature(&[ValType::V128, ValType::FuncRef], &[ValType::ExternRef]).is_ok()
+        );
+
+        // unsupported types
+        assert_eq!(
+            validate_signature(&[ValType::I32], &[ValType::None]),
+            Err(FuncError::UnsupportedType(ValType::None))
+        );
+        assert_eq!(
+            validate_signature(&[ValType::None], &[]),
+            Err(FuncError::UnsupportedType(ValType::None))
+        );
+    }
+
+    #[test]
+    fn test_func_type_from_type_codes() {
+        let result = FuncType::from_type_codes(&[0x7F, 0x7E, 0x7D, 0x7C, 0x7B], &[0x70, 0x6F]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+        assert!(func_ty.params_type_iter().eq([
+            ValType::I32,
+            ValType::I64,
+            ValType::F32,
+            ValType::F64,
+            ValType::V128
+        ]));
+        assert!(func_ty
+            .returns_type_iter()
+            .eq([ValType::FuncRef, ValType::ExternRef]));
+
+        let result = FuncType::from_type_codes(&[], &[]);
+        assert!(result.is_ok());
+        assert!(result.unwrap().is_nullary());
+
+        // unknown codes
+        let result = FuncType::from_type_codes(&[0x7F, 0x00], &[]);
+        assert_eq!(
+            result.unwrap_err(),
+            WasmEdgeError::Func(FuncError::UnknownTypeCode(0x00))
+        );
+        let result = FuncType::from_type_codes(&[], &[0x40]);
+        assert_eq!(
+            result.unwrap_err(),
+            WasmEdgeError::Func(FuncError::UnknownTypeCode(0x40))
+        );
+    }
+
+    #[test]
+    fn test_func_type_indexed() {
+        let result = FuncType::create(
+            vec![ValType::I32, ValType::F64, ValType::ExternRef],
+            vec![ValType::V128, ValType::I64],
+        );
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+
+        assert_eq!(
+            func_ty.params_indexed().collect::<Vec<_>>(),
+            vec![
+                (0, ValType::I32),
+                (1, ValType::F64),
+                (2, ValType::ExternRef)
+            ]
+        );
+        assert_eq!(
+            func_ty.returns_indexed().collect::<Vec<_>>(),
+            vec![(0, ValType::V128), (1, ValType::I64)]
+        );
+
+        let result = FuncType::create([], []);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+        assert_eq!(func_ty.params_indexed().count(), 0);
+        assert_eq!(func_ty.returns_indexed().count(), 0);
+    }
+
+    #[test]
+    fn test_func_type_clone() {
+        let result = FuncType::create(vec![ValType::I32, ValType::F64], vec![ValType::I64]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+        let cloned = func_ty.clone();
+        assert!(!cloned.registered);
+        assert_ne!(cloned.inner.0, func_ty.inner.0);
+        drop(func_ty);
+        assert_eq!(cloned.params_len(), 2);
+        assert_eq!(cloned.returns_len(), 1);
+        assert_eq!(
+            cloned.params_type_iter().collect::<Vec<_>>(),
+            vec![ValType::I32, ValType::F64]
+        );
+
+        // clone a borrowed type
+        let result = Function::create(&cloned, Box::new(|_| Ok(vec![WasmValue::from_i64(0)])), 0);
+        assert!(result.is_ok());
+        let host_func = result.unwrap();
+        let result = host_func.ty();
+        assert!(result.is_ok());
+        let borrowed_ty = result.unwrap();
+        assert!(borrowed_ty.registered);
+        let cloned_again = borrowed_ty.clone();
+        assert!(!cloned_again.registered);
+        drop(borrowed_ty);
+        drop(host_func);
+        assert_eq!(cloned_again, cloned);
+
+        let result = FuncType::wildcard();
+        assert!(result.is_ok());
+        assert!(result.unwrap().clone().is_wildcard());
+    }
+
+    #[test]
+    fn test_func_type_display() {
+        let result = FuncType::create(vec![ValType::I32; 2], vec![ValType::I32]);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().to_string(), "(i32, i32) -> (i32)");
+
+        let result = FuncType::create(
+            vec![ValType::I64, ValType::F32, ValType::F64, ValType::V128],
+            vec![ValType::FuncRef, ValType::ExternRef],
+        );
+        assert!(result.is_ok());
+        assert_eq!(
+            result.unwrap().to_string(),
+            "(i64, f32, f64, v128) -> (funcref, externref)"
+        );
+
+        // no arguments or returns
+        let result = FuncType::create(vec![], vec![]);
+        assert!(result.is_ok());
+        assert_eq!(format!("{}", result.unwrap()), "() -> ()");
+        let result = FuncType::create(vec![ValType::F64], vec![]);
+        assert!(result.is_ok());
+        assert_eq!(format!("{}", result.unwrap()), "(f64) -> ()");
+
+        let result = FuncType::wildcard();
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().to_string(), "(*) -> (*)");
+    }
+
+    #[test]
+    fn test_func_type_mangled_suffix() {
+        let signatures = vec![
+            (vec![], vec![], "PR"),
+            (vec![ValType::I32; 2], vec![ValType::I32], "PiiRi"),
+            (
+                vec![ValType::I64, ValType::F32, ValType::F64],
+                vec![],
+                "PlfdR",
+            ),
+            (
+                vec![ValType::V128],
+                vec![ValType::FuncRef, ValType::ExternRef],
+                "PvRre",
+            ),
+        ];
+        for (params, returns, expected) in signatures {
+            let result = FuncType::create(params, returns);
+            assert!(result.is_ok());
+            let func_ty = result.unwrap();
+            assert_eq!(func_ty.mangled_suffix(), expected);
+        }
+    }
+
+    #[test]
+    fn test_func_type_ownership() {
+        let live_count = || OWNED_FUNC_TYPES.with(|count| count.get());
+        let start = live_count();
+
+        // create a FuncType owning its context
+        let result = FuncType::create(vec![ValType::I32; 2], vec![ValType::I32]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+        assert_eq!(live_count(), start + 1);
+
+        // create a host function
+        let result = Function::create(&func_ty, Box::new(real_add), 0);
+        assert!(result.is_ok());
+        let host_func = result.unwrap();
+
+        // the FuncTypes returned by `ty` borrow the context
+        for _ in 0..100 {
+            let result = host_func.ty();
+            assert!(result.is_ok());
+            let ty = result.unwrap();
+            assert_eq!(ty.params_len(), 2);
+        }
+        assert_eq!(live_count(), start + 1);
+
+        // take the ownership of a raw context
+        let ctx =
+            unsafe { ffi::WasmEdge_FunctionTypeCreate(std::ptr::null(), 0, std::ptr::null(), 0) };
+        let result = unsafe { FuncType::from_raw_owned(ctx) };
+        assert!(result.is_ok());
+        let owned_ty = result.unwrap();
+        assert_eq!(live_count(), start + 2);
+        drop(owned_ty);
+        assert_eq!(live_count(), start + 1);
+
+        // null contexts are rejected
+        let result = unsafe { FuncType::from_raw_owned(std::ptr::null_mut()) };
+        assert!(result.is_err());
+        let result = unsafe { FuncType::from_raw_borrowed(std::ptr::null()) };
+        assert!(result.is_err());
+
+        drop(func_ty);
+        assert_eq!(live_count(), start);
+    }
+
+    #[test]
+    fn test_func_prepare_output() {
+        let returns = vec![
+            ValType::I32,
+            ValType::I64,
+            ValType::F32,
+            ValType::F64,
+            ValType::V128,
+            ValType::ExternRef,
+        ];
+        let result = FuncType::create([], returns.clone());
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+        let result = Function::create(&func_ty, Box::new(real_add), 0);
+        assert!(result.is_ok());
+        let host_func = result.unwrap();
+
+        let result = host_func.prepare_output();
+        assert!(result.is_ok());
+        let output = result.unwrap();
+        assert_eq!(output.len() as u32, host_func.signature_arity().unwrap().1);
+        assert!(output.iter().map(|v| v.ty()).eq(returns));
+        assert_eq!(output[0].to_i32(), 0);
+        assert_eq!(output[3].to_f64(), 0.0);
+        assert!(output[5].is_null_ref());
+
+        // no returns
+        let result = FuncType::create(vec![ValType::I32], []);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+        let result = Function::create(&func_ty, Box::new(real_add), 0);
+        assert!(result.is_ok());
+        let result = result.unwrap().prepare_output();
+        assert!(result.is_ok());
+        assert!(result.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_func_signature_arity() {
+        let live_count = || OWNED_FUNC_TYPES.with(|count| count.get());
+
+        let result = FuncType::create(vec![ValType::I32; 3], vec![ValType::I64]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+        assert_eq!(func_ty.arity(), (3, 1));
+        let result = Function::create(&func_ty, Box::new(real_add), 0);
+        assert!(result.is_ok());
+        let host_func = result.unwrap();
+
+        let result = host_func.ty();
+        assert!(result.is_ok());
+        let expected = result.unwrap().arity();
+
+        let start = live_count();
+        for _ in 0..100 {
+            let result = host_func.signature_arity();
+            assert!(result.is_ok());
+            assert_eq!(result.unwrap(), expected);
+        }
+        assert_eq!(live_count(), start);
+    }
+
+    #[test]
+    fn test_func_basic() {
+        // create a FuncType
+        let result = FuncType::create(vec![ValType::I32; 2], vec![ValType::I32]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+        // create a host function
+        let result = Function::create(&func_ty, Box::new(real_add), 0);
+        assert!(result.is_ok());
+        let host_func = result.unwrap();
+
+        // get func type
+        let result = host_func.ty();
+        assert!(result.is_ok());
+        let ty = result.unwrap();
+
+        // check parameters
+        assert_eq!(ty.params_len(), 2);
+        let param_tys = ty.params_type_iter().collect::<Vec<_>>();
+        assert_eq!(param_tys, vec![ValType::I32; 2]);
+
+        // check returns
+        assert_eq!(ty.returns_len(), 1);
+        let return_tys = ty.returns_type_iter().collect::<Vec<_>>();
+        assert_eq!(return_tys, vec![ValType::I32]);
+
+        // run this function
+        let result = Executor::create(None, None);
+        assert!(result.is_ok());
+        let mut executor = result.unwrap();
+        let result = host_func.call(
+            &mut executor,
+            vec![WasmValue::from_i32(1), WasmValue::from_i32(2)],
+        );
+        assert!(result.is_ok());
+        let returns = result.unwrap();
+        assert_eq!(returns[0].to_i32(), 3);
+    }
+
+    #[test]
+    fn test_func_send() {
+        // create a FuncType
+        let result = FuncType::create(vec![ValType::I32; 2], vec![ValType::I32]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+        // create a host function
+        let result = Function::create(&func_ty, Box::new(real_add), 0);
+        assert!(result.is_ok());
+        let host_func = result.unwrap();
+
+        let handle = thread::spawn(move || {
+            // get func type
+            let result = host_func.ty();
+            assert!(result.is_ok());
+            let ty = result.unwrap();
+
+            // check parameters
+            assert_eq!(ty.params_len(), 2);
+            let param_tys = ty.params_type_iter().collect::<Vec<_>>();
+            assert_eq!(param_tys, vec![ValType::I32; 2]);
+
+            // check returns
+            assert_eq!(ty.returns_len(), 1);
+            let return_tys = ty.returns_type_iter().collect::<Vec<_>>();
+            assert_eq!(return_tys, vec![ValType::I32]);
+        });
+
+        handle.join().unwrap()
+    }
+
+    #[test]
+    fn test_func_sync() {
+        // create a FuncType
+        let result = FuncType::create(vec![ValType::I32; 2], vec![ValType::I32]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+        // create a host function
+        let result = Function::create(&func_ty, Box::new(real_add), 0);
+        assert!(result.is_ok());
+        let host_func = Arc::new(Mutex::new(result.unwrap()));
+
+        let host_func_cloned = Arc::clone(&host_func);
+        let handle = thread::spawn(move || {
+            let result = host_func_cloned.lock();
+            assert!(result.is_ok());
+            let host_func = result.unwrap();
+
+            // get func type
+            let result = host_func.ty();
+            assert!(result.is_ok());
+            let ty = result.unwrap();
+
+            // check parameters
+            assert_eq!(ty.params_len(), 2);
+            let param_tys = ty.params_type_iter().collect::<Vec<_>>();
+            assert_eq!(param_tys, vec![ValType::I32; 2]);
+
+            // check returns
+            assert_eq!(ty.returns_len(), 1);
+            let return_tys = ty.returns_type_iter().collect::<Vec<_>>();
+            assert_eq!(return_tys, vec![ValType::I32]);
+        });
+
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn test_func_assert_signature() {
+        let result = FuncType::create(vec![ValType::I32; 2], vec![ValType::I32]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+        let result = Function::create(&func_ty, Box::new(real_add), 0);
+        assert!(result.is_ok());
+        let host_func = result.unwrap();
+
+        host_func.assert_signature(&[ValType::I32, ValType::I32], &[ValType::I32]);
+    }
+
+    #[test]
+    #[should_panic(expected = "signature mismatch")]
+    fn test_func_assert_signature_mismatch() {
+        let result = FuncType::create(vec![ValType::I32; 2], vec![ValType::I32]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+        let result = Function::create(&func_ty, Box::new(real_add), 0);
+        assert!(result.is_ok());
+        let host_func = result.unwrap();
+
+        host_func.assert_signature(&[ValType::I64, ValType::I32], &[ValType::I32]);
+    }
+
+    #[test]
+    fn test_func_with_cost_fn() {
+        // create a FuncType
+        let result = FuncType::create(vec![ValType::I32; 2], vec![ValType::I32]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+        // create a host function charging the cost by the magnitude of the first argument
+        let result = Function::create_with_cost_fn(
+            &func_ty,
+            Box::new(real_add),
+            Box::new(|args: &[WasmValue]| args[0].to_i32() as u64 * 2),
+        );
+        assert!(result.is_ok());
+        let host_func = result.unwrap();
+        assert_eq!(host_func.charged_cost(), 0);
+
+        let result = Statistics::create();
+        assert!(result.is_ok());
+        let mut stat = result.unwrap();
+        let result = Executor::create(None, Some(&mut stat));
+        assert!(result.is_ok());
+        let mut executor = result.unwrap();
+
+        // run this function with a small input
+        let result = host_func.call(
+            &mut executor,
+            vec![WasmValue::from_i32(1), WasmValue::from_i32(2)],
+        );
+        assert!(result.is_ok());
+        assert_eq!(host_func.charged_cost(), 2);
+
+        // run this function with a large input
+        let result = host_func.call(
+            &mut executor,
+            vec![WasmValue::from_i32(100), WasmValue::from_i32(2)],
+        );
+        assert!(result.is_ok());
+        assert_eq!(host_func.charged_cost(), 202);
+
+        // the computed cost is charged in the statistics attached to the executor
+        assert_eq!(stat.cost_in_total(), 202);
+        stat.clear();
+        assert_eq!(stat.cost_in_total(), 0);
+
+        // the computed cost is subject to the cost limit, and the body doesn't run beyond it
+        stat.set_cost_limit(100);
+        let result = host_func.call(
+            &mut executor,
+            vec![WasmValue::from_i32(100), WasmValue::from_i32(2)],
+        );
+        assert_eq!(
+            result.unwrap_err(),
+            WasmEdgeError::Core(CoreError::Common(CoreCommonError::CostLimitExceeded))
+        );
+        assert_eq!(host_func.charged_cost(), 402);
+        assert_eq!(stat.cost_remaining(), 0);
+        stat.clear();
+        stat.set_cost_limit(u64::MAX);
+
+        // an executor without statistics charges the function only
+        let result = Executor::create(None, None);
+        assert!(result.is_ok());
+        let mut executor = result.unwrap();
+        let result = host_func.call(
+            &mut executor,
+            vec![WasmValue::from_i32(1), WasmValue::from_i32(2)],
+        );
+        assert!(result.is_ok());
+        assert_eq!(host_func.charged_cost(), 404);
+        assert_eq!(stat.cost_in_total(), 0);
+
+        // the functions created with a static cost charge nothing
+        let result = Function::create(&func_ty, Box::new(real_add), 0);
+        assert!(result.is_ok());
+        let host_func = result.unwrap();
+        assert_eq!(host_func.charged_cost(), 0);
+    }
+
+    #[test]
+    fn test_func_call_all_matching() {
+        let counter = Arc::new(AtomicUsize::new(0));
+
+        // create a FuncType used only by this test
+        let result = FuncType::create(
+            vec![ValType::F64, ValType::I64, ValType::F32],
+            vec![ValType::F64],
+        );
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+
+        // create three host functions with the matching signature
+        let mut host_funcs = vec![];
+        for i in 0..3 {
+            let counter = Arc::clone(&counter);
+            let result = Function::create(
+                &func_ty,
+                Box::new(move |input: Vec<WasmValue>| {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    Ok(vec![WasmValue::from_f64(input[0].to_f64() + i as f64)])
+                }),
+                0,
+            );
+            assert!(result.is_ok());
+            host_funcs.push(result.unwrap());
+        }
+
+        // create a host function with a different signature
+        let result = FuncType::create(vec![ValType::I32; 2], vec![ValType::I32]);
+        assert!(result.is_ok());
+        let other_ty = result.unwrap();
+        let result = Function::create(&other_ty, Box::new(real_add), 0);
+        assert!(result.is_ok());
+        let _other_func = result.unwrap();
+
+        let result = Executor::create(None, None);
+        assert!(result.is_ok());
+        let mut executor = result.unwrap();
+
+        let results = Function::call_all_matching(
+            &mut executor,
+            &func_ty,
+            vec![
+                WasmValue::from_f64(1.0),
+                WasmValue::from_i64(2),
+                WasmValue::from_f32(3.0),
+            ],
+        );
+        assert_eq!(results.len(), 3);
+        assert_eq!(counter.load(Ordering::SeqCst), 3);
+        let mut returns = results
+            .into_iter()
+            .map(|r| r.unwrap()[0].to_f64())
+            .collect::<Vec<_>>();
+        returns.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(returns, vec![1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn test_func_create_marshaled() {
+        #[derive(Debug, PartialEq)]
+        struct Request {
+            a: i32,
+            b: f64,
+        }
+
+        impl HostMarshal for Request {
+            fn val_types() -> Vec<ValType> {
+                vec![ValType::I32, ValType::F64]
+            }
+
+            fn to_values(&self) -> Vec<WasmValue> {
+                vec![WasmValue::from_i32(self.a), WasmValue::from_f64(self.b)]
+            }
+
+            fn from_values(values: &[WasmValue]) -> Option<Self> {
+                match values {
+                    [a, b] if a.ty() == ValType::I32 && b.ty() == ValType::F64 => Some(Request {
+                        a: a.to_i32(),
+                        b: b.to_f64(),
+                    }),
+                    _ => None,
+                }
+            }
+        }
+
+        let result = Function::create_marshaled(
+            |req: Request| {
+                Ok(Request {
+                    a: req.a + 1,
+                    b: req.b * 2.0,
+                })
+            },
+            0,
+        );
+        assert!(result.is_ok());
+        let host_func = result.unwrap();
+        host_func.assert_signature(&[ValType::I32, ValType::F64], &[ValType::I32, ValType::F64]);
+
+        let result = Executor::create(None, None);
+        assert!(result.is_ok());
+        let mut executor = result.unwrap();
+
+        let req = Request { a: 1, b: 1.5 };
+        let result = host_func.call(&mut executor, req.to_values());
+        assert!(result.is_ok());
+        let returns = result.unwrap();
+        let resp = Request::from_values(&returns);
+        assert_eq!(resp, Some(Request { a: 2, b: 3.0 }));
+    }
+
+    #[test]
+    fn test_func_host_result_err_ref() {
+        #[derive(Debug, PartialEq)]
+        struct HostError {
+            code: i32,
+            message: String,
+        }
+
+        // create a host function which fails on negative inputs
+        let result = FuncType::create(vec![ValType::I32], vec![ValType::ExternRef]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+        let result = Function::create(
+            &func_ty,
+            Box::new(|input: Vec<WasmValue>| {
+                let n = input[0].to_i32();
+                match n < 0 {
+                    true => Ok(vec![HostResult::err_ref(HostError {
+                        code: n,
+                        message: String::from("negative input"),
+                    })]),
+                    false => Ok(vec![HostResult::ok_ref()]),
+                }
+            }),
+            0,
+        );
+        assert!(result.is_ok());
+        let host_func = result.unwrap();
+
+        let result = ImportModule::create("extern");
+        assert!(result.is_ok());
+        let mut import = result.unwrap();
+        import.add_func("check", host_func);
+
+        // the guest passes the error object back if the designated externref is not null
+        let result = wat2wasm(
+            br#"
+            (module
+                (import "extern" "check" (func $check (param i32) (result externref)))
+                (func (export "is_err") (param i32) (result i32)
+                    (ref.is_null (call $check (local.get 0)))
+                    (i32.eqz))
+                (func (export "get_err") (param i32) (result externref)
+                    (call $check (local.get 0)))
+            )
+"#,
+        );
+        assert!(result.is_ok());
+        let wasm_bytes = result.unwrap();
+
+        let result = Config::create();
+        assert!(result.is_ok());
+        let mut config = result.unwrap();
+        config.reference_types(true);
+        let result = Vm::create(Some(config), None);
+        assert!(result.is_ok());
+        let mut vm = result.unwrap();
+        let result = vm.register_wasm_from_import(ImportObject::Import(import));
+        assert!(result.is_ok());
+
+        let result = vm.run_wasm_from_bytes(&wasm_bytes, "is_err", [WasmValue::from_i32(1)]);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap()[0].to_i32(), 0);
+
+        let result = vm.run_function("is_err", [WasmValue::from_i32(-1)]);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap()[0].to_i32(), 1);
+
+        let result = vm.run_function("get_err", [WasmValue::from_i32(1)]);
+        assert!(result.is_ok());
+        let returns = result.unwrap();
+        assert!(!HostResult::is_err(&returns[0]));
+        assert!(unsafe { HostResult::take_err::<HostError>(&returns[0]) }.is_none());
+
+        let result = vm.run_function("get_err", [WasmValue::from_i32(-2)]);
+        assert!(result.is_ok());
+        let returns = result.unwrap();
+        assert!(HostResult::is_err(&returns[0]));
+        let err = unsafe { HostResult::take_err::<HostError>(&returns[0]) };
+        assert_eq!(
+            err,
+            Some(HostError {
+                code: -2,
+                message: String::from("negative input"),
+            })
+        );
+    }
+
+    #[test]
+    fn test_func_lock_poisoned() {
+        // poison an isolated lock rather than the one of the global registry
+        let lock = Arc::new(Mutex::new(0));
+        let handle = {
+            let lock = Arc::clone(&lock);
+            thread::spawn(move || {
+                let _guard = lock.lock();
+                panic!("poison the lock");
+            })
+        };
+        assert!(handle.join().is_err());
+        assert!(lock.is_poisoned());
+
+        // recovering the lock clears the poison
+        *lock_recovered(&lock) += 1;
+        assert!(!lock.is_poisoned());
+        assert_eq!(*lock.lock().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_func_create_with_deadline() {
+        let result = FuncType::create(vec![ValType::I32], vec![ValType::I32]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+
+        // the closure sleeps for the given milliseconds
+        let result = Function::create_with_deadline(
+            &func_ty,
+            Box::new(|input: Vec<WasmValue>| {
+                thread::sleep(Duration::from_millis(input[0].to_i32() as u64));
+                Ok(vec![WasmValue::from_i32(input[0].to_i32())])
+            }),
+            0,
+            Duration::from_millis(100),
+        );
+        assert!(result.is_ok());
+        let host_func = result.unwrap();
+
+        let result = Executor::create(None, None);
+        assert!(result.is_ok());
+        let mut executor = result.unwrap();
+
+        // the fast call finishes within the deadline
+        let result = host_func.call(&mut executor, vec![WasmValue::from_i32(0)]);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap()[0].to_i32(), 0);
+
+        // the slow call trips the deadline
+        let result = host_func.call(&mut executor, vec![WasmValue::from_i32(500)]);
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err(),
+            WasmEdgeError::Func(FuncError::HostTrap(HostTrap::Timeout))
+        );
+    }
+
+    #[test]
+    fn test_func_create_with_deadline_worker() {
+        let result = FuncType::create(vec![ValType::I32], vec![ValType::I32]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+
+        // the closure records the threads it runs on, and panics on a negative argument
+        let threads = Arc::new(Mutex::new(std::collections::HashSet::new()));
+        let result = Function::create_with_deadline(
+            &func_ty,
+            Box::new({
+                let threads = Arc::clone(&threads);
+                move |input: Vec<WasmValue>| {
+                    threads.lock().unwrap().insert(thread::current().id());
+                    if input[0].to_i32() < 0 {
+                        panic!("negative argument");
+                    }
+                    Ok(vec![WasmValue::from_i32(input[0].to_i32())])
+                }
+            }),
+            0,
+            Duration::from_secs(10),
+        );
+        assert!(result.is_ok());
+        let host_func = result.unwrap();
+
+        let result = Executor::create(None, None);
+        assert!(result.is_ok());
+        let mut executor = result.unwrap();
+
+        // the sequential calls reuse the same worker thread
+        for i in 0..3 {
+            let result = host_func.call(&mut executor, vec![WasmValue::from_i32(i)]);
+            assert!(result.is_ok());
+            assert_eq!(result.unwrap()[0].to_i32(), i);
+        }
+        assert_eq!(threads.lock().unwrap().len(), 1);
+        assert!(!threads.lock().unwrap().contains(&thread::current().id()));
+
+        // a panic fails the call without waiting for the deadline
+        let start = std::time::Instant::now();
+        let result = host_func.call(&mut executor, vec![WasmValue::from_i32(-1)]);
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err(),
+            WasmEdgeError::Func(FuncError::HostTrap(HostTrap::Panicked))
+        );
+        assert!(start.elapsed() < Duration::from_secs(10));
+
+        // the panicked worker is replaced
+        let result = host_func.call(&mut executor, vec![WasmValue::from_i32(4)]);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap()[0].to_i32(), 4);
+        assert_eq!(threads.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_func_create_streaming_strict() {
+        let result = FuncType::create(vec![ValType::I32], vec![ValType::I32; 2]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+
+        // the closure produces as many items as the given argument
+        let result = Function::create_streaming(
+            &func_ty,
+            Box::new(|input: Vec<WasmValue>| {
+                Ok(Box::new((0..input[0].to_i32()).map(WasmValue::from_i32)))
+            }),
+            0,
+            StreamingPolicy::default(),
+        );
+        assert!(result.is_ok());
+        let host_func = result.unwrap();
+
+        let result = Executor::create(None, None);
+        assert!(result.is_ok());
+        let mut executor = result.unwrap();
+
+        // exact production
+        let result = host_func.call(&mut executor, vec![WasmValue::from_i32(2)]);
+        assert!(result.is_ok());
+        let returns = result.unwrap();
+        assert_eq!(returns[0].to_i32(), 0);
+        assert_eq!(returns[1].to_i32(), 1);
+
+        // under-production
+        let result = host_func.call(&mut executor, vec![WasmValue::from_i32(1)]);
+        assert!(result.is_err());
+
+        // over-production
+        let result = host_func.call(&mut executor, vec![WasmValue::from_i32(3)]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_func_create_streaming_lenient() {
+        let result = FuncType::create(vec![ValType::I32], vec![ValType::I32; 2]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+
+        // the closure produces as many items as the given argument
+        let result = Function::create_streaming(
+            &func_ty,
+            Box::new(|input: Vec<WasmValue>| {
+                Ok(Box::new((0..input[0].to_i32()).map(WasmValue::from_i32)))
+            }),
+            0,
+            StreamingPolicy::Lenient,
+        );
+        assert!(result.is_ok());
+        let host_func = result.unwrap();
+
+        let result = Executor::create(None, None);
+        assert!(result.is_ok());
+        let mut executor = result.unwrap();
+
+        // exact production
+        let result = host_func.call(&mut executor, vec![WasmValue::from_i32(2)]);
+        assert!(result.is_ok());
+
+        // under-production
+        let result = host_func.call(&mut executor, vec![WasmValue::from_i32(1)]);
+        assert!(result.is_err());
+
+        // over-production is truncated
+        let result = host_func.call(&mut executor, vec![WasmValue::from_i32(5)]);
+        assert!(result.is_ok());
+        let returns = result.unwrap();
+        assert_eq!(returns.len(), 2);
+        assert_eq!(returns[0].to_i32(), 0);
+        assert_eq!(returns[1].to_i32(), 1);
+    }
+
+    #[test]
+    fn test_func_create_with_arg_filter() {
+        let observed = Arc::new(Mutex::new(vec![]));
+
+        let result = FuncType::create(vec![ValType::I32; 2], vec![ValType::I32]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+
+        // the filter clamps the first argument into [0, 10]
+        let observed_cloned = Arc::clone(&observed);
+        let result = Function::create_with_arg_filter(
+            &func_ty,
+            Box::new(move |input: Vec<WasmValue>| {
+                observed_cloned.lock().unwrap().push(input[0].to_i32());
+                real_add(input)
+            }),
+            Box::new(|input: &mut Vec<WasmValue>| {
+                input[0] = WasmValue::from_i32(input[0].to_i32().clamp(0, 10));
+            }),
+        );
+        assert!(result.is_ok());
+        let host_func = result.unwrap();
+
+        let result = Executor::create(None, None);
+        assert!(result.is_ok());
+        let mut executor = result.unwrap();
+
+        let result = host_func.call(
+            &mut executor,
+            vec![WasmValue::from_i32(5), WasmValue::from_i32(1)],
+        );
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap()[0].to_i32(), 6);
+
+        let result = host_func.call(
+            &mut executor,
+            vec![WasmValue::from_i32(100), WasmValue::from_i32(1)],
+        );
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap()[0].to_i32(), 11);
+
+        assert_eq!(*observed.lock().unwrap(), vec![5, 10]);
+    }
+
+    #[host_function]
+    fn host_mul(a: i32, b: i64) -> i64 {
+        a as i64 * b
+    }
+
+    #[host_function]
+    fn host_split(x: f64) -> (i32, f64) {
+        (x.trunc() as i32, x.fract())
+    }
+
+    #[host_function]
+    fn host_nop() {}
+
+    #[test]
+    fn test_func_host_function_macro() {
+        let result = Executor::create(None, None);
+        assert!(result.is_ok());
+        let mut executor = result.unwrap();
+
+        let result = host_mul_function(0);
+        assert!(result.is_ok());
+        let host_func = result.unwrap();
+        let result = host_func.ty();
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().to_string(), "(i32, i64) -> (i64)");
+        let result = host_func.call(
+            &mut executor,
+            [WasmValue::from_i32(6), WasmValue::from_i64(7)],
+        );
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap()[0].to_i64(), 42);
+
+        // tuple returns
+        let result = host_split_function(0);
+        assert!(result.is_ok());
+        let host_func = result.unwrap();
+        let result = host_func.call(&mut executor, [WasmValue::from_f64(2.5)]);
+        assert!(result.is_ok());
+        let returns = result.unwrap();
+        assert_eq!(returns[0].to_i32(), 2);
+        assert_eq!(returns[1].to_f64(), 0.5);
+
+        // no arguments or returns
+        let result = host_nop_function(0);
+        assert!(result.is_ok());
+        let host_func = result.unwrap();
+        let result = host_func.ty();
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().to_string(), "() -> ()");
+        let result = host_func.call(&mut executor, []);
+        assert!(result.is_ok());
+        assert!(result.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_func_ref_null() {
+        let result = FuncType::create(vec![ValType::I32; 2], vec![ValType::I32]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+        let result = Function::create(&func_ty, Box::new(real_add), 0);
+        assert!(result.is_ok());
+        let host_func = result.unwrap();
+        let mut func_ref = host_func.as_ref();
+        func_ref.inner.0 = std::ptr::null();
+
+        let result = func_ref.ty();
+        assert_eq!(
+            result.unwrap_err(),
+            WasmEdgeError::Func(FuncError::NullPointer)
+        );
+        let result = Executor::create(None, None);
+        assert!(result.is_ok());
+        let mut executor = result.unwrap();
+        let result = func_ref.call(
+            &mut executor,
+            [WasmValue::from_i32(1), WasmValue::from_i32(2)],
+        );
+        assert_eq!(
+            result.unwrap_err(),
+            WasmEdgeError::Func(FuncError::NullPointer)
+        );
+    }
+
+    #[test]
+    fn test_func_cost() {
+        let result = FuncType::create(vec![ValType::I32; 2], vec![ValType::I32]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+        let result = Function::create(&func_ty, Box::new(real_add), 42);
+        assert!(result.is_ok());
+        let host_func = result.unwrap();
+        assert_eq!(host_func.cost(), 42);
+        assert_eq!(host_func.as_ref().cost(), 42);
+
+        let result = Function::create(&func_ty, Box::new(real_add), 0);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().cost(), 0);
+    }
+
+    #[test]
+    fn test_func_reentrancy() {
+        // the host function called by the guest module
+        let result = FuncType::create(vec![ValType::I32], vec![ValType::I32]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+        let result = Function::create(
+            &func_ty,
+            Box::new(|inputs: Vec<WasmValue>| {
+                Ok(vec![WasmValue::from_i32(inputs[0].to_i32() * 2)])
+            }),
+            0,
+        );
+        assert!(result.is_ok());
+        let host_double = result.unwrap();
+        let result = ImportModule::create("host");
+        assert!(result.is_ok());
+        let mut import = result.unwrap();
+        import.add_func("double", host_double);
+
+        // instantiate a guest module exporting `quad`, which calls the host function twice
+        let result = wat2wasm(
+            br#"
+            (module
+                (import "host" "double" (func $double (param i32) (result i32)))
+                (func (export "quad") (param i32) (result i32)
+                    (call $double (call $double (local.get 0))))
+            )
+"#,
+        );
+        assert!(result.is_ok());
+        let wasm_bytes = result.unwrap();
+        let result = Loader::create(None);
+        assert!(result.is_ok());
+        let loader = result.unwrap();
+        let result = loader.from_bytes(&wasm_bytes);
+        assert!(result.is_ok());
+        let module = result.unwrap();
+        let result = Validator::create(None);
+        assert!(result.is_ok());
+        let validator = result.unwrap();
+        assert!(validator.validate(&module).is_ok());
+        let result = Store::create();
+        assert!(result.is_ok());
+        let mut store = result.unwrap();
+        let result = Executor::create(None, None);
+        assert!(result.is_ok());
+        let mut executor = result.unwrap();
+        let import = ImportObject::Import(import);
+        let result = executor.register_import_object(&mut store, &import);
+        assert!(result.is_ok());
+        let result = executor.register_active_module(&mut store, &module);
+        assert!(result.is_ok());
+        let instance = result.unwrap();
+        let result = instance.get_func("quad");
+        assert!(result.is_ok());
+        let guest_quad = result.unwrap();
+
+        // the host function calling back into the guest export
+        let guest = Mutex::new((executor, guest_quad));
+        let result = Function::create(
+            &func_ty,
+            Box::new(move |inputs: Vec<WasmValue>| {
+                let mut guest = guest.lock().unwrap();
+                let (executor, guest_quad) = &mut *guest;
+                let result = executor.run_func(guest_quad, inputs);
+                result.map_err(|_| ffi::WasmEdge_ErrCode_ExecutionFailed as u8)
+            }),
+            0,
+        );
+        assert!(result.is_ok());
+        let host_func = result.unwrap();
+
+        let result = Executor::create(None, None);
+        assert!(result.is_ok());
+        let mut executor = result.unwrap();
+        let result = host_func.call(&mut executor, [WasmValue::from_i32(3)]);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap()[0].to_i32(), 12);
+    }
+
+    #[test]
+    fn test_func_create_forwarding() {
+        // instantiate a guest module exporting `add`
+        let result = wat2wasm(
+            br#"
+            (module
+                (func (export "add") (param i32 i32) (result i32)
+                    (i32.add (local.get 0) (local.get 1)))
+            )
+"#,
+        );
+        assert!(result.is_ok());
+        let wasm_bytes = result.unwrap();
+        let result = Loader::create(None);
+        assert!(result.is_ok());
+        let loader = result.unwrap();
+        let result = loader.from_bytes(&wasm_bytes);
+        assert!(result.is_ok());
+        let module = result.unwrap();
+        let result = Validator::create(None);
+        assert!(result.is_ok());
+        let validator = result.unwrap();
+        assert!(validator.validate(&module).is_ok());
+        let result = Store::create();
+        assert!(result.is_ok());
+        let mut store = result.unwrap();
+        let result = Executor::create(None, None);
+        assert!(result.is_ok());
+        let mut executor = result.unwrap();
+        let result = executor.register_active_module(&mut store, &module);
+        assert!(result.is_ok());
+        let instance = result.unwrap();
+
+        // get the reference to the guest export
+        let result = instance.get_func("add");
+        assert!(result.is_ok());
+        let guest_add = result.unwrap();
+
+        // create a host function forwarding to the guest export
+        let result = FuncType::create(vec![ValType::I32; 2], vec![ValType::I32]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+        let result = Executor::create(None, None);
+        assert!(result.is_ok());
+        let forward_executor = result.unwrap();
+        let result = Function::create_forwarding(&func_ty, guest_add.as_ref(), forward_executor, 0);
+        assert!(result.is_ok());
+        let host_func = result.unwrap();
+
+        // run the forwarding function
+        let result = host_func.call(
+            &mut executor,
+            vec![WasmValue::from_i32(3), WasmValue::from_i32(4)],
+        );
+        assert!(result.is_ok());
+        let returns = result.unwrap();
+        assert_eq!(returns[0].to_i32(), 7);
+    }
+
+    #[test]
+    fn test_func_retain_host_funcs() {
+        let result = FuncType::create(vec![ValType::I64; 3], vec![ValType::I64]);
+        assert!(result.is_ok());
+        let kept_ty = result.unwrap();
+        let result = FuncType::create(vec![ValType::F32; 3], vec![ValType::F32]);
+        assert!(result.is_ok());
+        let removed_ty = result.unwrap();
+
+        // register a mix of host functions
+        let mut kept_funcs = vec![];
+        let mut removed_funcs = vec![];
+        for _ in 0..2 {
+            let result = Function::create(
+                &kept_ty,
+                Box::new(|input: Vec<WasmValue>| Ok(vec![input[0]])),
+                0,
+            );
+            assert!(result.is_ok());
+            kept_funcs.push(result.unwrap());
+
+            let result = Function::create(
+                &removed_ty,
+                Box::new(|input: Vec<WasmValue>| Ok(vec![input[0]])),
+                0,
+            );
+            assert!(result.is_ok());
+            removed_funcs.push(result.unwrap());
+        }
+        let mine = kept_funcs
+            .iter()
+            .chain(removed_funcs.iter())
+            .filter_map(|f| f.key)
+            .collect::<Vec<_>>();
+
+        // retain only the host functions of `kept_ty` among the registered ones
+        retain_host_funcs(|key, ty| !mine.contains(&key) || *ty == kept_ty);
+
+        let result = Executor::create(None, None);
+        assert!(result.is_ok());
+        let mut executor = result.unwrap();
+        for func in kept_funcs.iter() {
+            let result = func.call(&mut executor, vec![WasmValue::from_i64(1); 3]);
+            assert!(result.is_ok());
+            assert_eq!(result.unwrap()[0].to_i64(), 1);
+        }
+        for func in removed_funcs.iter() {
+            let result = func.call(&mut executor, vec![WasmValue::from_f32(1.0); 3]);
+            assert!(result.is_err());
+        }
+    }
+
+    #[test]
+    fn test_func_registration_recorder() {
+        with_isolated_host_funcs(|| {
+            let events = Arc::new(Mutex::new(vec![]));
+            let events_cloned = Arc::clone(&events);
+            set_registration_recorder(Some(Box::new(move |event: &RegistrationEvent| {
+                events_cloned.lock().unwrap().push(event.clone());
+            })));
+
+            let result = FuncType::create(vec![ValType::I32; 2], vec![ValType::I32]);
+            assert!(result.is_ok());
+            let func_ty = result.unwrap();
+            let result = Function::create(&func_ty, Box::new(real_add), 7);
+            assert!(result.is_ok());
+            let host_func = result.unwrap();
+            let key = host_func.key.unwrap();
+            drop(host_func);
+
+            set_registration_recorder(None);
+
+            // the recorder only sees the host functions of the isolated registry
+            assert_eq!(
+                *events.lock().unwrap(),
+                vec![
+                    RegistrationEvent::Created {
+                        key,
+                        ty: wasmedge_types::FuncType::new(
+                            Some(vec![ValType::I32; 2]),
+                            Some(vec![ValType::I32])
+                        ),
+                        cost: 7,
+                    },
+                    RegistrationEvent::Dropped { key },
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn test_func_create_coercing() {
+        let result = FuncType::create(vec![ValType::I64, ValType::F64], vec![ValType::I64]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+
+        let result = Function::create_coercing(
+            &func_ty,
+            Box::new(|input: Vec<WasmValue>| {
+                if input[0].ty() != ValType::I64 || input[1].ty() != ValType::F64 {
+                    return Err(ffi::WasmEdge_ErrCode_FuncSigMismatch as u8);
+                }
+                Ok(vec![WasmValue::from_i64(
+                    input[0].to_i64() + input[1].to_f64() as i64,
+                )])
+            }),
+            0,
+        );
+        assert!(result.is_ok());
+        let host_func = result.unwrap();
+        let key = host_func.key.unwrap();
+
+        // the executor rejects mismatched arguments, so dispatch the raw call directly
+        let params = [
+            WasmValue::from_i32(-3).as_raw(),
+            WasmValue::from_f32(2.5).as_raw(),
+        ];
+        let mut returns = [WasmValue::from_i64(0).as_raw()];
+        let result = wraper_fn(
+            key as *mut c_void,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            params.as_ptr(),
+            params.len() as u32,
+            returns.as_mut_ptr(),
+            returns.len() as u32,
+        );
+        assert!(crate::utils::check(result).is_ok());
+        assert_eq!(WasmValue::from_raw(returns[0]).to_i64(), -1);
+
+        // narrowing is never performed
+        let mut input = vec![WasmValue::from_i64(1), WasmValue::from_f64(1.0)];
+        coerce_args(&[ValType::I32, ValType::F32], &mut input);
+        assert_eq!(input[0].ty(), ValType::I64);
+        assert_eq!(input[1].ty(), ValType::F64);
+    }
+
+    #[test]
+    fn test_func_from_raw_host_fn() {
+        unsafe extern "C" fn raw_add(
+            _this: *mut c_void,
+            data: *mut c_void,
+            _mem_cxt: *mut ffi::WasmEdge_MemoryInstanceContext,
+            params: *const ffi::WasmEdge_Value,
+            param_len: u32,
+            returns: *mut ffi::WasmEdge_Value,
+            return_len: u32,
+        ) -> ffi::WasmEdge_Result {
+            if param_len != 2 || return_len != 1 {
+                return ffi::WasmEdge_Result {
+                    Code: ffi::WasmEdge_ErrCode_FuncSigMismatch as u8,
+                };
+            }
+            let bias = *(data as *const i32);
+            let a = WasmValue::from_raw(*params).to_i32();
+            let b = WasmValue::from_raw(*params.add(1)).to_i32();
+            *returns = WasmValue::from_i32(a + b + bias).as_raw();
+            ffi::WasmEdge_Result { Code: 0 }
+        }
+
+        let result = FuncType::create(vec![ValType::I32; 2], vec![ValType::I32]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+
+        let mut bias = 100i32;
+        let result = unsafe {
+            Function::from_raw_host_fn(&func_ty, raw_add, &mut bias as *mut i32 as *mut c_void, 0)
+        };
+        assert!(result.is_ok());
+        let host_func = result.unwrap();
+        assert!(host_func.key.is_none());
+
+        let result = Executor::create(None, None);
+        assert!(result.is_ok());
+        let mut executor = result.unwrap();
+
+        let result = host_func.call(
+            &mut executor,
+            vec![WasmValue::from_i32(1), WasmValue::from_i32(2)],
+        );
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap()[0].to_i32(), 103);
+    }
+
+    #[test]
+    fn test_func_with_isolated_host_funcs() {
+        let result = FuncType::create(vec![ValType::I32; 2], vec![ValType::I32]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+        let in_registry = |key: usize| host_registry().funcs.lock().unwrap().contains_key(&key);
+
+        with_isolated_host_funcs(|| {
+            let result = Function::create(&func_ty, Box::new(real_add), 0);
+            assert!(result.is_ok());
+            let outer = result.unwrap();
+            let outer_key = outer.key.unwrap();
+            assert!(in_registry(outer_key));
+
+            let inner_key = with_isolated_host_funcs(|| {
+                // the registry of the outer scope is invisible
+                assert!(!in_registry(outer_key));
+                assert!(host_registry().funcs.lock().unwrap().is_empty());
+
+                let result = Function::create(&func_ty, Box::new(real_add), 0);
+                assert!(result.is_ok());
+                let inner = result.unwrap();
+                let inner_key = inner.key.unwrap();
+                assert!(in_registry(inner_key));
+                inner_key
+            });
+
+            // the registry of the outer scope is restored
+            assert!(in_registry(outer_key));
+            assert!(!in_registry(inner_key));
+
+            // the other threads keep using the global registry
+            let handle = std::thread::spawn(move || {
+                let registry = host_registry();
+                let in_registry = registry.funcs.lock().unwrap().contains_key(&outer_key);
+                (Arc::ptr_eq(&registry, &HOST_REGISTRY), in_registry)
+            });
+            assert_eq!(handle.join().unwrap(), (true, false));
+        });
+
+        // the global registry is restored
+        assert!(Arc::ptr_eq(&host_registry(), &HOST_REGISTRY));
+    }
+
+    #[test]
+    fn test_func_create_overloaded() {
+        let signatures = [ValType::I32, ValType::I64, ValType::F32, ValType::F64]
+            .into_iter()
+            .map(|ty| FuncType::create(vec![ty; 2], vec![ty]))
+            .collect::<WasmEdgeResult<Vec<_>>>();
+        assert!(signatures.is_ok());
+        let signatures = signatures.unwrap();
+
+        let result = Function::create_overloaded(
+            &signatures,
+            Box::new(|input: Vec<WasmValue>| match input[0].ty() {
+                ValType::I32 => Ok(vec![WasmValue::from_i32(
+                    input[0].to_i32() + input[1].to_i32(),
+                )]),
+                ValType::I64 => Ok(vec![WasmValue::from_i64(
+                    input[0].to_i64() + input[1].to_i64(),
+                )]),
+                ValType::F32 => Ok(vec![WasmValue::from_f32(
+                    input[0].to_f32() + input[1].to_f32(),
+                )]),
+                ValType::F64 => Ok(vec![WasmValue::from_f64(
+                    input[0].to_f64() + input[1].to_f64(),
+                )]),
+                _ => Err(ffi::WasmEdge_ErrCode_FuncSigMismatch as u8),
+            }),
+        );
+        assert!(result.is_ok());
+        let funcs = result.unwrap();
+        assert_eq!(funcs.len(), 4);
+
+        let result = Executor::create(None, None);
+        assert!(result.is_ok());
+        let mut executor = result.unwrap();
+
+        let result = funcs[0].call(
+            &mut executor,
+            vec![WasmValue::from_i32(1), WasmValue::from_i32(2)],
+        );
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap()[0].to_i32(), 3);
+
+        let result = funcs[1].call(
+            &mut executor,
+            vec![WasmValue::from_i64(1 << 40), WasmValue::from_i64(2)],
+        );
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap()[0].to_i64(), (1 << 40) + 2);
+
+        let result = funcs[2].call(
+            &mut executor,
+            vec![WasmValue::from_f32(1.5), WasmValue::from_f32(2.0)],
+        );
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap()[0].to_f32(), 3.5);
+
+        let result = funcs[3].call(
+            &mut executor,
+            vec![WasmValue::from_f64(1.25), WasmValue::from_f64(2.0)],
+        );
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap()[0].to_f64(), 3.25);
+
+        // the wildcard can't be used to create functions
+        let result = FuncType::wildcard();
+        assert!(result.is_ok());
+        let result = Function::create_overloaded(&[result.unwrap()], Box::new(real_add));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_func_create_with_memory() {
+        // the host function fills the buffer with the given byte
+        let result = FuncType::create(vec![ValType::I32; 3], vec![]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+        let result = Function::create_with_memory(
+            &func_ty,
+            Box::new(|memory: &mut MemoryMut, input: Vec<WasmValue>| {
+                let offset = input[0].to_i32() as u32;
+                let len = input[1].to_i32() as u32;
+                let buf = memory
+                    .slice_mut(offset, len)
+                    .map_err(|_| ffi::WasmEdge_ErrCode_MemoryOutOfBounds as u8)?;
+                buf.fill(input[2].to_i32() as u8);
+                Ok(vec![])
+            }),
+            0,
+        );
+        assert!(result.is_ok());
+        let host_func = result.unwrap();
+
+        let result = ImportModule::create("extern");
+        assert!(result.is_ok());
+        let mut import = result.unwrap();
+        import.add_func("fill", host_func);
+
+        // the guest reads the buffer back
+        let result = wat2wasm(
+            br#"
+            (module
+                (import "extern" "fill" (func $fill (param i32 i32 i32)))
+                (memory 1)
+                (func (export "fill_and_sum") (param $offset i32) (param $len i32) (result i32)
+                    (local $i i32)
+                    (local $sum i32)
+                    (call $fill (local.get $offset) (local.get $len) (i32.const 7))
+                    (block $done
+                        (loop $next
+                            (br_if $done (i32.ge_u (local.get $i) (local.get $len)))
+                            (local.set $sum
+                                (i32.add
+                                    (local.get $sum)
+                                    (i32.load8_u (i32.add (local.get $offset) (local.get $i)))))
+                            (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                            (br $next)))
+                    (local.get $sum))
+            )
+"#,
+        );
+        assert!(result.is_ok());
+        let wasm_bytes = result.unwrap();
+
+        let result = Vm::create(None, None);
+        assert!(result.is_ok());
+        let mut vm = result.unwrap();
+        let result = vm.register_wasm_from_import(ImportObject::Import(import));
+        assert!(result.is_ok());
+
+        let result = vm.run_wasm_from_bytes(
+            &wasm_bytes,
+            "fill_and_sum",
+            [WasmValue::from_i32(16), WasmValue::from_i32(10)],
+        );
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap()[0].to_i32(), 70);
+
+        // out of bounds
+        let result = vm.run_function(
+            "fill_and_sum",
+            [WasmValue::from_i32(65530), WasmValue::from_i32(10)],
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_func_total_host_calls() {
+        let result = FuncType::create(vec![ValType::I32; 2], vec![ValType::I32]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+        let funcs = (0..3)
+            .map(|_| Function::create(&func_ty, Box::new(real_add), 0))
+            .collect::<WasmEdgeResult<Vec<_>>>();
+        assert!(funcs.is_ok());
+        let funcs = funcs.unwrap();
+
+        let result = Executor::create(None, None);
+        assert!(result.is_ok());
+        let mut executor = result.unwrap();
+
+        let total_start = total_host_calls();
+        let thread_start = host_calls_in_thread();
+        for (idx, func) in funcs.iter().enumerate() {
+            for _ in 0..=idx {
+                let result = func.call(
+                    &mut executor,
+                    vec![WasmValue::from_i32(1), WasmValue::from_i32(2)],
+                );
+                assert!(result.is_ok());
+            }
+        }
+
+        // other tests may call host functions concurrently
+        assert_eq!(host_calls_in_thread() - thread_start, 6);
+        assert!(total_host_calls() - total_start >= 6);
+    }
 
-#[derive(Debug, Clone)]
-pub(crate) struct InnerFuncRef(pub(crate) *const ffi::WasmEdge_FunctionInstanceContext);
-unsafe impl Send for InnerFuncRef {}
-unsafe impl Sync for InnerFuncRef {}
+    #[test]
+    fn test_func_capacity_warning_handler() {
+        let result = FuncType::create(vec![ValType::I32; 2], vec![ValType::I32]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::{types::WasmValue, Executor};
-    use std::{
-        sync::{Arc, Mutex},
-        thread,
-    };
-    use wasmedge_types::ValType;
+        with_isolated_host_funcs(|| {
+            let capacity = host_registry().funcs.lock().unwrap().capacity();
+            let threshold = 0.01;
+            let expected = (capacity as f64 * threshold).ceil() as usize;
+
+            let warnings = Arc::new(Mutex::new(vec![]));
+            let warnings_cloned = Arc::clone(&warnings);
+            set_capacity_warning_handler(
+                threshold,
+                Box::new(move |len, capacity| {
+                    warnings_cloned.lock().unwrap().push((len, capacity));
+                }),
+            );
+
+            let mut funcs = vec![];
+            for _ in 0..expected - 1 {
+                let result = Function::create(&func_ty, Box::new(real_add), 0);
+                assert!(result.is_ok());
+                funcs.push(result.unwrap());
+            }
+            assert!(warnings.lock().unwrap().is_empty());
+
+            let result = Function::create(&func_ty, Box::new(real_add), 0);
+            assert!(result.is_ok());
+            funcs.push(result.unwrap());
+            assert_eq!(*warnings.lock().unwrap(), vec![(expected, capacity)]);
+
+            // the handler fires only when crossing the threshold
+            let result = Function::create(&func_ty, Box::new(real_add), 0);
+            assert!(result.is_ok());
+            assert_eq!(warnings.lock().unwrap().len(), 1);
+
+            *host_registry().capacity_warning_handler.lock().unwrap() = None;
+        });
+    }
 
     #[test]
-    fn test_func_type() {
-        // test FuncType with args and returns
-        {
-            let param_tys = vec![
+    fn test_func_write_struct() {
+        #[repr(C)]
+        #[derive(Clone, Copy)]
+        struct Point {
+            x: i32,
+            y: i32,
+        }
+        unsafe impl NoPadding for Point {}
+
+        // the host function writes a Point through the pointer argument
+        let result = FuncType::create(vec![ValType::I32], vec![]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+        let result = Function::create_with_memory(
+            &func_ty,
+            Box::new(|memory: &mut MemoryMut, input: Vec<WasmValue>| {
+                memory
+                    .write_struct(input[0].to_i32() as u32, &Point { x: 3, y: -4 })
+                    .map_err(|_| ffi::WasmEdge_ErrCode_MemoryOutOfBounds as u8)?;
+                Ok(vec![])
+            }),
+            0,
+        );
+        assert!(result.is_ok());
+        let host_func = result.unwrap();
+
+        let result = ImportModule::create("extern");
+        assert!(result.is_ok());
+        let mut import = result.unwrap();
+        import.add_func("get_point", host_func);
+
+        // the guest reads both fields back
+        let result = wat2wasm(
+            br#"
+            (module
+                (import "extern" "get_point" (func $get_point (param i32)))
+                (memory 1)
+                (func (export "x") (param $ptr i32) (result i32)
+                    (call $get_point (local.get $ptr))
+                    (i32.load (local.get $ptr)))
+                (func (export "y") (param $ptr i32) (result i32)
+                    (call $get_point (local.get $ptr))
+                    (i32.load offset=4 (local.get $ptr)))
+            )
+"#,
+        );
+        assert!(result.is_ok());
+        let wasm_bytes = result.unwrap();
+
+        let result = Vm::create(None, None);
+        assert!(result.is_ok());
+        let mut vm = result.unwrap();
+        let result = vm.register_wasm_from_import(ImportObject::Import(import));
+        assert!(result.is_ok());
+
+        let result = vm.run_wasm_from_bytes(&wasm_bytes, "x", [WasmValue::from_i32(64)]);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap()[0].to_i32(), 3);
+        let result = vm.run_function("y", [WasmValue::from_i32(128)]);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap()[0].to_i32(), -4);
+
+        // the struct doesn't fit at the end of the memory
+        let result = vm.run_function("x", [WasmValue::from_i32(65532)]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_func_host_stats_by_category() {
+        let result = FuncType::create(vec![ValType::I32; 2], vec![ValType::I32]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+
+        let result = Executor::create(None, None);
+        assert!(result.is_ok());
+        let mut executor = result.unwrap();
+
+        with_isolated_host_funcs(|| {
+            let result = Function::create(&func_ty, Box::new(real_add), 2);
+            assert!(result.is_ok());
+            let read = result.unwrap();
+            read.set_category("io");
+            let result = Function::create(&func_ty, Box::new(real_add), 3);
+            assert!(result.is_ok());
+            let write = result.unwrap();
+            write.set_category("io");
+            let result = Function::create_with_cost_fn(
+                &func_ty,
+                Box::new(real_add),
+                Box::new(|input: &[WasmValue]| input[0].to_i32() as u64),
+            );
+            assert!(result.is_ok());
+            let hash = result.unwrap();
+            hash.set_category("crypto");
+            let result = Function::create(&func_ty, Box::new(real_add), 5);
+            assert!(result.is_ok());
+            let uncategorized = result.unwrap();
+
+            for (func, times) in [(&read, 2), (&write, 1), (&hash, 2), (&uncategorized, 1)] {
+                for _ in 0..times {
+                    let result = func.call(
+                        &mut executor,
+                        vec![WasmValue::from_i32(10), WasmValue::from_i32(1)],
+                    );
+                    assert!(result.is_ok());
+                }
+            }
+
+            let stats = host_stats_by_category();
+            assert_eq!(stats.len(), 2);
+            assert_eq!(
+                stats["io"],
+                CategoryStats {
+                    funcs: 2,
+                    calls: 3,
+                    cost: 2 * 2 + 3,
+                }
+            );
+            assert_eq!(
+                stats["crypto"],
+                CategoryStats {
+                    funcs: 1,
+                    calls: 2,
+                    cost: 10 * 2,
+                }
+            );
+        });
+    }
+
+    #[test]
+    fn test_func_signature_changed() {
+        let events = Arc::new(Mutex::new(vec![]));
+
+        with_isolated_host_funcs(|| {
+            let events_cloned = Arc::clone(&events);
+            set_registration_recorder(Some(Box::new(move |event: &RegistrationEvent| {
+                if let RegistrationEvent::SignatureChanged { name, .. } = event {
+                    if name == "plugin.transform" {
+                        events_cloned.lock().unwrap().push(event.clone());
+                    }
+                }
+            })));
+
+            // the first version of the plugin
+            let result = FuncType::create(vec![ValType::I32], vec![ValType::I32]);
+            assert!(result.is_ok());
+            let v1_ty = result.unwrap();
+            let result = Function::create(&v1_ty, Box::new(real_add), 0);
+            assert!(result.is_ok());
+            let v1 = result.unwrap();
+            v1.set_name("plugin.transform");
+            drop(v1);
+
+            // reloading with the same signature is compatible
+            let result = Function::create(&v1_ty, Box::new(real_add), 0);
+            assert!(result.is_ok());
+            let v2 = result.unwrap();
+            v2.set_name("plugin.transform");
+            drop(v2);
+            assert!(events.lock().unwrap().is_empty());
+
+            // reloading with a changed signature
+            let result = FuncType::create(vec![ValType::I64], vec![ValType::I32]);
+            assert!(result.is_ok());
+            let v3_ty = result.unwrap();
+            let result = Function::create(&v3_ty, Box::new(real_add), 0);
+            assert!(result.is_ok());
+            let v3 = result.unwrap();
+            v3.set_name("plugin.transform");
+
+            set_registration_recorder(None);
+
+            assert_eq!(
+                *events.lock().unwrap(),
+                vec![RegistrationEvent::SignatureChanged {
+                    key: v3.key.unwrap(),
+                    name: String::from("plugin.transform"),
+                    old: wasmedge_types::FuncType::new(
+                        Some(vec![ValType::I32]),
+                        Some(vec![ValType::I32])
+                    ),
+                    new: wasmedge_types::FuncType::new(
+                        Some(vec![ValType::I64]),
+                        Some(vec![ValType::I32])
+                    ),
+                }]
+            );
+        });
+    }
+
+    #[test]
+    fn test_func_call_timed() {
+        let result = FuncType::create(vec![ValType::I32; 2], vec![ValType::I32]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+        let result = Function::create(
+            &func_ty,
+            Box::new(|input: Vec<WasmValue>| {
+                thread::sleep(Duration::from_millis(20));
+                real_add(input)
+            }),
+            0,
+        );
+        assert!(result.is_ok());
+        let host_func = result.unwrap();
+
+        let result = Executor::create(None, None);
+        assert!(result.is_ok());
+        let mut executor = result.unwrap();
+
+        let result = host_func.call_timed(
+            &mut executor,
+            vec![WasmValue::from_i32(1), WasmValue::from_i32(2)],
+        );
+        assert!(result.is_ok());
+        let (returns, elapsed) = result.unwrap();
+        assert_eq!(returns[0].to_i32(), 3);
+        assert!(elapsed >= Duration::from_millis(20));
+    }
+
+    #[test]
+    fn test_func_max_host_result_len() {
+        let result = FuncType::create(vec![ValType::I32; 2], vec![ValType::I32]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+        let result = Function::create(
+            &func_ty,
+            Box::new(|_| Ok(vec![WasmValue::from_i32(0); 1 << 16])),
+            0,
+        );
+        assert!(result.is_ok());
+        let host_func = result.unwrap();
+
+        let result = Executor::create(None, None);
+        assert!(result.is_ok());
+        let mut executor = result.unwrap();
+
+        set_max_host_result_len(8);
+        let result = host_func.call(
+            &mut executor,
+            vec![WasmValue::from_i32(1), WasmValue::from_i32(2)],
+        );
+        assert_eq!(
+            result.unwrap_err(),
+            WasmEdgeError::Func(FuncError::HostTrap(HostTrap::ResultTooLarge))
+        );
+
+        // a streaming function producing endlessly is stopped at the cap
+        let result = FuncType::create(vec![], vec![ValType::I32; 16]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+        for policy in [StreamingPolicy::Strict, StreamingPolicy::Lenient] {
+            let result = Function::create_streaming(
+                &func_ty,
+                Box::new(|_| Ok(Box::new(std::iter::repeat(WasmValue::from_i32(0))))),
+                0,
+                policy,
+            );
+            assert!(result.is_ok());
+            let host_func = result.unwrap();
+            let result = host_func.call(&mut executor, []);
+            assert_eq!(
+                result.unwrap_err(),
+                WasmEdgeError::Func(FuncError::HostTrap(HostTrap::ResultTooLarge))
+            );
+        }
+        set_max_host_result_len(usize::MAX);
+    }
+
+    #[test]
+    fn test_func_satisfies_import() {
+        let result = FuncType::create(vec![ValType::I32, ValType::I64], vec![ValType::I32]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+        let result = Function::create(&func_ty, Box::new(real_add), 0);
+        assert!(result.is_ok());
+        let host_func = result.unwrap();
+
+        // exact match
+        let result = FuncType::create(vec![ValType::I32, ValType::I64], vec![ValType::I32]);
+        assert!(result.is_ok());
+        assert!(host_func.satisfies_import(&result.unwrap()).is_ok());
+
+        // the wildcard accepts any function
+        let result = FuncType::wildcard();
+        assert!(result.is_ok());
+        assert!(host_func.satisfies_import(&result.unwrap()).is_ok());
+
+        // incompatible types
+        let result = FuncType::create(vec![ValType::I32, ValType::F64], vec![ValType::I32]);
+        assert!(result.is_ok());
+        assert_eq!(
+            host_func.satisfies_import(&result.unwrap()),
+            Err(FuncError::ImportMismatch(String::from(
+                "argument 1: expected F64, found I64"
+            )))
+        );
+        let result = FuncType::create(vec![ValType::I32, ValType::I64], vec![]);
+        assert!(result.is_ok());
+        assert_eq!(
+            host_func.satisfies_import(&result.unwrap()),
+            Err(FuncError::ImportMismatch(String::from(
+                "expected 0 returns, found 1"
+            )))
+        );
+    }
+
+    #[test]
+    fn test_func_create_on_pool() {
+        // a pool of a single named worker thread
+        struct SingleThreadPool(Mutex<std::sync::mpsc::Sender<Box<dyn FnOnce() + Send>>>);
+        impl ThreadPool for SingleThreadPool {
+            fn execute(&self, job: Box<dyn FnOnce() + Send>) {
+                self.0.lock().unwrap().send(job).unwrap();
+            }
+        }
+        let (tx, rx) = std::sync::mpsc::channel::<Box<dyn FnOnce() + Send>>();
+        let result = thread::Builder::new()
+            .name(String::from("host-pool"))
+            .spawn(move || {
+                for job in rx {
+                    job();
+                }
+            });
+        assert!(result.is_ok());
+        let pool = Arc::new(SingleThreadPool(Mutex::new(tx)));
+
+        let result = FuncType::create(vec![ValType::I32; 2], vec![ValType::I32]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+        let thread_names = Arc::new(Mutex::new(vec![]));
+        let thread_names_cloned = Arc::clone(&thread_names);
+        let result = Function::create_on_pool(
+            &func_ty,
+            Box::new(move |input: Vec<WasmValue>| {
+                thread_names_cloned
+                    .lock()
+                    .unwrap()
+                    .push(thread::current().name().map(String::from));
+                real_add(input)
+            }),
+            0,
+            pool,
+        );
+        assert!(result.is_ok());
+        let host_func = result.unwrap();
+
+        let result = Executor::create(None, None);
+        assert!(result.is_ok());
+        let mut executor = result.unwrap();
+
+        for _ in 0..2 {
+            let result = host_func.call(
+                &mut executor,
+                vec![WasmValue::from_i32(1), WasmValue::from_i32(2)],
+            );
+            assert!(result.is_ok());
+            assert_eq!(result.unwrap()[0].to_i32(), 3);
+        }
+        assert_eq!(
+            *thread_names.lock().unwrap(),
+            vec![Some(String::from("host-pool")); 2]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "forensics")]
+    fn test_func_create_with_forensics() {
+        let result = FuncType::create(vec![ValType::I32; 2], vec![ValType::I32]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+        let result = Function::create_with_forensics(&func_ty, Box::new(real_add), 0, 3);
+        assert!(result.is_ok());
+        let host_func = result.unwrap();
+        let key = host_func.key().unwrap();
+        assert!(dump_forensics(key).is_empty());
+
+        let result = Executor::create(None, None);
+        assert!(result.is_ok());
+        let mut executor = result.unwrap();
+
+        for i in 0..5 {
+            let result = host_func.call(
+                &mut executor,
+                vec![WasmValue::from_i32(i), WasmValue::from_i32(i * 10)],
+            );
+            assert!(result.is_ok());
+        }
+
+        // only the last 3 calls are kept
+        let captured = dump_forensics(key)
+            .into_iter()
+            .map(|args| args.iter().map(|arg| arg.to_i32()).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(captured, vec![vec![2, 20], vec![3, 30], vec![4, 40]]);
+
+        // the functions created otherwise keep nothing
+        let result = Function::create(&func_ty, Box::new(real_add), 0);
+        assert!(result.is_ok());
+        assert!(dump_forensics(result.unwrap().key().unwrap()).is_empty());
+    }
+
+    #[test]
+    fn test_func_invocation_encoding() {
+        let result = FuncType::create(
+            vec![
                 ValType::I32,
                 ValType::I64,
                 ValType::F32,
                 ValType::F64,
                 ValType::V128,
-                ValType::ExternRef,
-            ];
-            let param_len = param_tys.len();
-            let ret_tys = vec![ValType::FuncRef, ValType::ExternRef, ValType::V128];
-            let ret_len = ret_tys.len();
+            ],
+            vec![],
+        );
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+        let result = Function::create(&func_ty, Box::new(|_| Ok(vec![])), 0);
+        assert!(result.is_ok());
+        let host_func = result.unwrap();
+
+        let args = [
+            WasmValue::from_i32(-1),
+            WasmValue::from_i64(i64::MAX),
+            WasmValue::from_f32(3.5),
+            WasmValue::from_f64(-13.14),
+            WasmValue::from_v128(1 << 100),
+        ];
+        let result = encode_invocation(&host_func, &args);
+        assert!(result.is_ok());
+        let bytes = result.unwrap();
+        assert_eq!(bytes.len(), 12 + 5 + 4 + 8 + 4 + 8 + 16);
+
+        let result = decode_invocation(&bytes);
+        assert!(result.is_ok());
+        let (key, decoded) = result.unwrap();
+        assert_eq!(Some(key as usize), host_func.key());
+        assert_eq!(decoded.len(), 5);
+        assert_eq!(decoded[0].to_i32(), -1);
+        assert_eq!(decoded[1].to_i64(), i64::MAX);
+        assert_eq!(decoded[2].to_f32(), 3.5);
+        assert_eq!(decoded[3].to_f64(), -13.14);
+        assert_eq!(decoded[4].to_v128(), 1 << 100);
+
+        // mismatched arguments
+        let result = encode_invocation(&host_func, &args[..2]);
+        assert!(result.is_err());
+
+        // truncated and trailing bytes
+        let result = decode_invocation(&bytes[..bytes.len() - 1]);
+        assert!(matches!(
+            result,
+            Err(WasmEdgeError::Func(FuncError::Invocation(_)))
+        ));
+        let mut extended = bytes.clone();
+        extended.push(0);
+        let result = decode_invocation(&extended);
+        assert!(matches!(
+            result,
+            Err(WasmEdgeError::Func(FuncError::Invocation(_)))
+        ));
+
+        // unknown type code
+        let mut corrupted = bytes;
+        corrupted[12] = 0x00;
+        let result = decode_invocation(&corrupted);
+        assert_eq!(
+            result.unwrap_err(),
+            WasmEdgeError::Func(FuncError::UnknownTypeCode(0x00))
+        );
+    }
+
+    #[test]
+    fn test_func_swap_host_funcs() {
+        with_isolated_host_funcs(|| {
+            let result = FuncType::create(vec![ValType::I32; 2], vec![ValType::I32]);
+            assert!(result.is_ok());
+            let func_ty = result.unwrap();
+            let result = Function::create(&func_ty, Box::new(real_add), 0);
+            assert!(result.is_ok());
+            let host_func = result.unwrap();
+            let key = host_func.key().unwrap();
+
+            let result = Executor::create(None, None);
+            assert!(result.is_ok());
+            let mut executor = result.unwrap();
+            let args = [WasmValue::from_i32(2), WasmValue::from_i32(3)];
+
+            let result = host_func.call(&mut executor, args);
+            assert!(result.is_ok());
+            assert_eq!(result.unwrap()[0].to_i32(), 5);
+
+            // swap in a set multiplying the arguments
+            let mut new_set = FunctionSet::new();
+            assert!(new_set.is_empty());
+            let real_mul = |inputs: Vec<WasmValue>| {
+                Ok(vec![WasmValue::from_i32(
+                    inputs[0].to_i32() * inputs[1].to_i32(),
+                )])
+            };
+            assert!(new_set.insert(key, Box::new(real_mul)).is_none());
+            assert!(new_set.contains(key));
+            assert_eq!(new_set.len(), 1);
+            let old_set = swap_host_funcs(new_set);
+            assert!(old_set.contains(key));
+            assert_eq!(old_set.keys(), vec![key]);
+
+            let result = host_func.call(&mut executor, args);
+            assert!(result.is_ok());
+            assert_eq!(result.unwrap()[0].to_i32(), 6);
+
+            // swap in an empty set
+            let mut mul_set = swap_host_funcs(FunctionSet::new());
+            assert!(mul_set.remove(key).is_some());
+            assert!(mul_set.is_empty());
+            let result = host_func.call(&mut executor, args);
+            assert!(result.is_err());
+
+            // swap the original set back
+            let empty_set = swap_host_funcs(old_set);
+            assert!(empty_set.is_empty());
+            let result = host_func.call(&mut executor, args);
+            assert!(result.is_ok());
+            assert_eq!(result.unwrap()[0].to_i32(), 5);
+        })
+    }
+
+    #[test]
+    fn test_func_host_func_capacity() {
+        let result = FuncType::create(vec![ValType::I32; 2], vec![ValType::I32]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
 
-            // create FuncType
-            let result = FuncType::create(param_tys, ret_tys);
-            assert!(result.is_ok());
-            let func_ty = result.unwrap();
+        with_isolated_host_funcs(|| {
+            let funcs = (0..3)
+                .map(|_| Function::create(&func_ty, Box::new(real_add), 0))
+                .collect::<WasmEdgeResult<Vec<_>>>();
+            assert!(funcs.is_ok());
 
-            // check parameters
-            assert_eq!(func_ty.params_len(), param_len as u32);
-            let param_tys = func_ty.params_type_iter().collect::<Vec<_>>();
+            // shrinking below the length is rejected
+            let capacity = host_func_capacity();
+            let result = set_host_func_capacity(2);
             assert_eq!(
-                param_tys,
-                vec![
-                    ValType::I32,
-                    ValType::I64,
-                    ValType::F32,
-                    ValType::F64,
-                    ValType::V128,
-                    ValType::ExternRef,
-                ]
+                result.unwrap_err(),
+                WasmEdgeError::Func(FuncError::CapacityBelowLength(2, 3))
             );
+            assert_eq!(host_func_capacity(), capacity);
 
-            // check returns
-            assert_eq!(func_ty.returns_len(), ret_len as u32);
-            let return_tys = func_ty.returns_type_iter().collect::<Vec<_>>();
-            assert_eq!(
-                return_tys,
-                vec![ValType::FuncRef, ValType::ExternRef, ValType::V128]
-            );
-        }
+            // raise the ceiling
+            let result = set_host_func_capacity(capacity + 1000);
+            assert!(result.is_ok());
+            assert!(host_func_capacity() >= capacity + 1000);
 
-        // test FuncType without args and returns
-        {
-            // create FuncType
-            let result = FuncType::create([], []);
+            // shrink to the length
+            let result = set_host_func_capacity(3);
             assert!(result.is_ok());
-            let func_ty = result.unwrap();
+            assert!(host_func_capacity() >= 3);
+            assert!(host_func_capacity() < capacity + 1000);
+        });
+    }
 
-            assert_eq!(func_ty.params_len(), 0);
-            assert_eq!(func_ty.returns_len(), 0);
-        }
+    #[test]
+    fn test_func_create_with_error() {
+        let result = FuncType::create(vec![ValType::I32], vec![ValType::I32]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+        let result = Function::create_with_error(
+            &func_ty,
+            Box::new(|inputs: Vec<WasmValue>| match inputs[0].to_i32() {
+                n if n >= 0 => Ok(vec![WasmValue::from_i32(n * 2)]),
+                n => Err(HostFuncError::new(0x04, format!("negative input: {}", n))),
+            }),
+            0,
+        );
+        assert!(result.is_ok());
+        let host_func = result.unwrap();
+
+        let result = Executor::create(None, None);
+        assert!(result.is_ok());
+        let mut executor = result.unwrap();
+
+        let result = host_func.call(&mut executor, [WasmValue::from_i32(-3)]);
+        assert!(result.is_err());
+        let error = result.unwrap_err();
+        assert_eq!(
+            error,
+            WasmEdgeError::Func(FuncError::Host {
+                code: 0x04,
+                message: String::from("negative input: -3"),
+            })
+        );
+        assert_eq!(
+            error.to_string(),
+            "host function failed with code 0x4: negative input: -3"
+        );
+
+        // the message doesn't leak into the later calls
+        let result = host_func.call(&mut executor, [WasmValue::from_i32(3)]);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap()[0].to_i32(), 6);
+        let result = Function::create(&func_ty, Box::new(|_| Err(0x04)), 0);
+        assert!(result.is_ok());
+        let result = result
+            .unwrap()
+            .call(&mut executor, [WasmValue::from_i32(3)]);
+        assert!(!matches!(
+            result,
+            Err(WasmEdgeError::Func(FuncError::Host { .. }))
+        ));
     }
 
     #[test]
-    fn test_func_basic() {
-        // create a FuncType
+    fn test_func_panic() {
+        let result = FuncType::create(vec![], vec![ValType::I32]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+        let result = Function::create(&func_ty, Box::new(|_| panic!("host function panics")), 0);
+        assert!(result.is_ok());
+        let host_func = result.unwrap();
+
+        let result = Executor::create(None, None);
+        assert!(result.is_ok());
+        let mut executor = result.unwrap();
+
+        // the panic is turned into a trap carrying the panic message
+        let result = host_func.call(&mut executor, []);
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err(),
+            WasmEdgeError::Func(FuncError::Host {
+                code: ffi::WasmEdge_ErrCode_ExecutionFailed as u8,
+                message: format!(
+                    "host function {:#x} panicked: host function panics",
+                    host_func.key.unwrap()
+                ),
+            })
+        );
+
+        // the registry stays usable
         let result = FuncType::create(vec![ValType::I32; 2], vec![ValType::I32]);
         assert!(result.is_ok());
         let func_ty = result.unwrap();
-        // create a host function
         let result = Function::create(&func_ty, Box::new(real_add), 0);
         assert!(result.is_ok());
         let host_func = result.unwrap();
+        let result = host_func.call(
+            &mut executor,
+            [WasmValue::from_i32(1), WasmValue::from_i32(2)],
+        );
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap()[0].to_i32(), 3);
+    }
 
-        // get func type
-        let result = host_func.ty();
+    #[test]
+    fn test_func_returns_mismatch() {
+        let result = FuncType::create(vec![], vec![ValType::I32]);
         assert!(result.is_ok());
-        let ty = result.unwrap();
+        let func_ty = result.unwrap();
+        // the closure returns two values for the single declared return
+        let result = Function::create(
+            &func_ty,
+            Box::new(|_| Ok(vec![WasmValue::from_i32(1), WasmValue::from_i32(2)])),
+            0,
+        );
+        assert!(result.is_ok());
+        let host_func = result.unwrap();
 
-        // check parameters
-        assert_eq!(ty.params_len(), 2);
-        let param_tys = ty.params_type_iter().collect::<Vec<_>>();
-        assert_eq!(param_tys, vec![ValType::I32; 2]);
+        let result = Executor::create(None, None);
+        assert!(result.is_ok());
+        let mut executor = result.unwrap();
 
-        // check returns
-        assert_eq!(ty.returns_len(), 1);
-        let return_tys = ty.returns_type_iter().collect::<Vec<_>>();
-        assert_eq!(return_tys, vec![ValType::I32]);
+        let result = host_func.call(&mut executor, []);
+        assert!(result.is_err());
+        match result.unwrap_err() {
+            WasmEdgeError::Func(FuncError::Host { code, message }) => {
+                assert_eq!(code, ffi::WasmEdge_ErrCode_FuncSigMismatch as u8);
+                assert!(message.ends_with("returned 2 values, but 1 are declared"));
+            }
+            error => panic!("unexpected error: {}", error),
+        }
+    }
+
+    #[test]
+    fn test_func_callbacks_panic() {
+        let result = FuncType::create(vec![ValType::I32], vec![ValType::I32]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+        let identity = || -> BoxedFn { Box::new(Ok) };
 
-        // run this function
         let result = Executor::create(None, None);
         assert!(result.is_ok());
         let mut executor = result.unwrap();
-        let result = host_func.call(
-            &mut executor,
-            vec![WasmValue::from_i32(1), WasmValue::from_i32(2)],
+
+        // the panics of the argument filter and the cost function are turned into traps as well
+        let result = Function::create_with_arg_filter(
+            &func_ty,
+            identity(),
+            Box::new(|_| panic!("argument filter panics")),
         );
         assert!(result.is_ok());
-        let returns = result.unwrap();
-        assert_eq!(returns[0].to_i32(), 3);
+        let host_func = result.unwrap();
+        let result = host_func.call(&mut executor, [WasmValue::from_i32(1)]);
+        match result.unwrap_err() {
+            WasmEdgeError::Func(FuncError::Host { message, .. }) => {
+                assert!(message.ends_with("panicked: argument filter panics"))
+            }
+            err => panic!("unexpected error: {}", err),
+        }
+
+        let result = Function::create_with_cost_fn(
+            &func_ty,
+            identity(),
+            Box::new(|_| panic!("cost function panics")),
+        );
+        assert!(result.is_ok());
+        let host_func = result.unwrap();
+        let result = host_func.call(&mut executor, [WasmValue::from_i32(1)]);
+        match result.unwrap_err() {
+            WasmEdgeError::Func(FuncError::Host { message, .. }) => {
+                assert!(message.ends_with("panicked: cost function panics"))
+            }
+            err => panic!("unexpected error: {}", err),
+        }
     }
 
     #[test]
-    fn test_func_send() {
-        // create a FuncType
-        let result = FuncType::create(vec![ValType::I32; 2], vec![ValType::I32]);
+    fn test_func_create_mut() {
+        let result = FuncType::create(vec![ValType::I32], vec![ValType::I32]);
         assert!(result.is_ok());
         let func_ty = result.unwrap();
-        // create a host function
-        let result = Function::create(&func_ty, Box::new(real_add), 0);
+
+        // accumulate the arguments across the calls
+        let mut sum = 0;
+        let result = Function::create_mut(
+            &func_ty,
+            Box::new(move |inputs: Vec<WasmValue>| {
+                sum += inputs[0].to_i32();
+                Ok(vec![WasmValue::from_i32(sum)])
+            }),
+            0,
+        );
         assert!(result.is_ok());
         let host_func = result.unwrap();
 
-        let handle = thread::spawn(move || {
-            // get func type
-            let result = host_func.ty();
+        let result = Executor::create(None, None);
+        assert!(result.is_ok());
+        let mut executor = result.unwrap();
+        for (arg, expected) in [(1, 1), (2, 3), (3, 6)] {
+            let result = host_func.call(&mut executor, [WasmValue::from_i32(arg)]);
             assert!(result.is_ok());
-            let ty = result.unwrap();
+            assert_eq!(result.unwrap()[0].to_i32(), expected);
+        }
+    }
 
-            // check parameters
-            assert_eq!(ty.params_len(), 2);
-            let param_tys = ty.params_type_iter().collect::<Vec<_>>();
-            assert_eq!(param_tys, vec![ValType::I32; 2]);
+    #[test]
+    fn test_func_create_async() {
+        // a timer future completed by another thread
+        type DelayState = Arc<Mutex<(bool, Option<std::task::Waker>)>>;
+        struct Delay {
+            duration: std::time::Duration,
+            state: Option<DelayState>,
+        }
+        impl std::future::Future for Delay {
+            type Output = ();
+            fn poll(
+                mut self: std::pin::Pin<&mut Self>,
+                cx: &mut std::task::Context<'_>,
+            ) -> std::task::Poll<()> {
+                if let Some(state) = &self.state {
+                    let mut state = state.lock().unwrap();
+                    if state.0 {
+                        return std::task::Poll::Ready(());
+                    }
+                    state.1 = Some(cx.waker().clone());
+                    return std::task::Poll::Pending;
+                }
 
-            // check returns
-            assert_eq!(ty.returns_len(), 1);
-            let return_tys = ty.returns_type_iter().collect::<Vec<_>>();
-            assert_eq!(return_tys, vec![ValType::I32]);
-        });
+                let state = Arc::new(Mutex::new((false, Some(cx.waker().clone()))));
+                let state_cloned = Arc::clone(&state);
+                let duration = self.duration;
+                thread::spawn(move || {
+                    thread::sleep(duration);
+                    let mut state = state_cloned.lock().unwrap();
+                    state.0 = true;
+                    if let Some(waker) = state.1.take() {
+                        waker.wake();
+                    }
+                });
+                self.state = Some(state);
+                std::task::Poll::Pending
+            }
+        }
 
-        handle.join().unwrap()
+        let result = FuncType::create([ValType::I32], [ValType::I32]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+        let result = Function::create_async(
+            &func_ty,
+            Box::new(|inputs: Vec<WasmValue>| {
+                Box::pin(async move {
+                    Delay {
+                        duration: std::time::Duration::from_millis(20),
+                        state: None,
+                    }
+                    .await;
+                    Ok(vec![WasmValue::from_i32(inputs[0].to_i32() * 2)])
+                })
+            }),
+            0,
+        );
+        assert!(result.is_ok());
+        let host_func = result.unwrap();
+
+        // the call blocks until the future completes
+        let result = Executor::create(None, None);
+        assert!(result.is_ok());
+        let mut executor = result.unwrap();
+        let start = std::time::Instant::now();
+        let result = host_func.call(&mut executor, [WasmValue::from_i32(21)]);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap()[0].to_i32(), 42);
+        assert!(start.elapsed() >= std::time::Duration::from_millis(20));
+
+        // the error of the future is returned
+        let result = Function::create_async(&func_ty, Box::new(|_| Box::pin(async { Err(7) })), 0);
+        assert!(result.is_ok());
+        let host_func = result.unwrap();
+        let result = host_func.call(&mut executor, [WasmValue::from_i32(21)]);
+        assert!(result.is_err());
     }
 
+    #[cfg(feature = "async")]
     #[test]
-    fn test_func_sync() {
-        // create a FuncType
-        let result = FuncType::create(vec![ValType::I32; 2], vec![ValType::I32]);
+    fn test_func_create_async_tokio() {
+        // a multi-thread runtime drives the timers on its worker threads
+        let result = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .enable_time()
+            .build();
+        assert!(result.is_ok());
+        let runtime = result.unwrap();
+        let handle = runtime.handle().clone();
+
+        let result = FuncType::create([ValType::I32], [ValType::I32]);
         assert!(result.is_ok());
         let func_ty = result.unwrap();
-        // create a host function
-        let result = Function::create(&func_ty, Box::new(real_add), 0);
+        let result = Function::create_async(
+            &func_ty,
+            Box::new(move |inputs: Vec<WasmValue>| {
+                // the timer is registered with the runtime entered on creation
+                let _guard = handle.enter();
+                let sleep = tokio::time::sleep(std::time::Duration::from_millis(20));
+                Box::pin(async move {
+                    sleep.await;
+                    Ok(vec![WasmValue::from_i32(inputs[0].to_i32() * 2)])
+                })
+            }),
+            0,
+        );
         assert!(result.is_ok());
-        let host_func = Arc::new(Mutex::new(result.unwrap()));
+        let host_func = result.unwrap();
 
-        let host_func_cloned = Arc::clone(&host_func);
+        // the wasm code runs on a thread outside the runtime
         let handle = thread::spawn(move || {
-            let result = host_func_cloned.lock();
+            let result = Executor::create(None, None);
             assert!(result.is_ok());
-            let host_func = result.unwrap();
+            let mut executor = result.unwrap();
+            let start = std::time::Instant::now();
+            let result = host_func.call(&mut executor, [WasmValue::from_i32(21)]);
+            assert!(result.is_ok());
+            assert_eq!(result.unwrap()[0].to_i32(), 42);
+            assert!(start.elapsed() >= std::time::Duration::from_millis(20));
+        });
+        assert!(handle.join().is_ok());
+    }
 
-            // get func type
-            let result = host_func.ty();
+    #[test]
+    fn test_func_create_with_data() {
+        let result = FuncType::create(vec![], vec![ValType::I32]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+
+        // count the calls with the host data
+        let result = Function::create_with_data(
+            &func_ty,
+            Box::new(|count: &mut i32, _: Vec<WasmValue>| {
+                *count += 1;
+                Ok(vec![WasmValue::from_i32(*count)])
+            }),
+            Box::new(0),
+            0,
+        );
+        assert!(result.is_ok());
+        let host_func = result.unwrap();
+
+        let result = Executor::create(None, None);
+        assert!(result.is_ok());
+        let mut executor = result.unwrap();
+        for expected in 1..=3 {
+            let result = host_func.call(&mut executor, []);
             assert!(result.is_ok());
-            let ty = result.unwrap();
+            assert_eq!(result.unwrap()[0].to_i32(), expected);
+        }
+    }
 
-            // check parameters
-            assert_eq!(ty.params_len(), 2);
-            let param_tys = ty.params_type_iter().collect::<Vec<_>>();
-            assert_eq!(param_tys, vec![ValType::I32; 2]);
+    #[test]
+    fn test_func_drop_frees_slot() {
+        let result = FuncType::create(vec![ValType::I32; 2], vec![ValType::I32]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+        let len = || host_registry().funcs.lock().unwrap().len();
 
-            // check returns
-            assert_eq!(ty.returns_len(), 1);
-            let return_tys = ty.returns_type_iter().collect::<Vec<_>>();
-            assert_eq!(return_tys, vec![ValType::I32]);
+        with_isolated_host_funcs(|| {
+            let baseline = len();
+            assert_eq!(baseline, 0);
+            // creating more functions than the capacity succeeds only if the slots are freed
+            for _ in 0..2 * host_func_capacity() {
+                let result = Function::create(&func_ty, Box::new(real_add), 0);
+                assert!(result.is_ok());
+            }
+            assert_eq!(len(), baseline);
+
+            // the closure of a function moved into a module is kept as long as the module lives
+            let result = ImportModule::create("extern");
+            assert!(result.is_ok());
+            let mut import = result.unwrap();
+            let result = Function::create(&func_ty, Box::new(real_add), 0);
+            assert!(result.is_ok());
+            let host_func = result.unwrap();
+            let key = host_func.key.unwrap();
+            import.add_func("add", host_func);
+            assert_eq!(len(), baseline + 1);
+            assert!(host_registry().funcs.lock().unwrap().contains_key(&key));
+
+            // and is freed together with the module
+            drop(import);
+            assert_eq!(len(), baseline);
+            assert!(!host_registry().funcs.lock().unwrap().contains_key(&key));
         });
+    }
 
-        handle.join().unwrap();
+    #[test]
+    fn test_func_set_name() {
+        let result = FuncType::create(vec![ValType::I32; 2], vec![ValType::I32]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+        let result = Function::create(
+            &func_ty,
+            Box::new(|_| Err(ffi::WasmEdge_ErrCode_ExecutionFailed as u8)),
+            0,
+        );
+        assert!(result.is_ok());
+        let host_func = result.unwrap();
+        assert!(host_func.name().is_none());
+
+        let result = Executor::create(None, None);
+        assert!(result.is_ok());
+        let mut executor = result.unwrap();
+
+        // the error of an unnamed function is returned as is
+        let result = host_func.call(
+            &mut executor,
+            vec![WasmValue::from_i32(1), WasmValue::from_i32(2)],
+        );
+        assert_eq!(
+            result.unwrap_err(),
+            WasmEdgeError::Core(CoreError::Execution {
+                kind: CoreExecutionError::ExecutionFailed,
+                trace: vec![],
+            })
+        );
+
+        host_func.set_name("db_query");
+        assert_eq!(host_func.name(), Some(String::from("db_query")));
+
+        let result = host_func.call(
+            &mut executor,
+            vec![WasmValue::from_i32(1), WasmValue::from_i32(2)],
+        );
+        assert!(result.is_err());
+        let err = result.unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("host function \"db_query\" trapped: "));
+        assert_eq!(
+            err,
+            WasmEdgeError::Func(FuncError::Trapped {
+                name: String::from("db_query"),
+                error: Box::new(WasmEdgeError::Core(CoreError::Execution {
+                    kind: CoreExecutionError::ExecutionFailed,
+                    trace: vec![],
+                })),
+            })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde_json")]
+    fn test_func_call_json() {
+        let result = FuncType::create(vec![ValType::I32; 2], vec![ValType::I32]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+        let result = Function::create(&func_ty, Box::new(real_add), 0);
+        assert!(result.is_ok());
+        let host_func = result.unwrap();
+
+        let result = Executor::create(None, None);
+        assert!(result.is_ok());
+        let mut executor = result.unwrap();
+
+        let result = host_func.call_json(&mut executor, &serde_json::json!([1, 2]));
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), serde_json::json!([3]));
+
+        // not an array
+        let result = host_func.call_json(&mut executor, &serde_json::json!({"a": 1}));
+        assert!(matches!(
+            result.unwrap_err(),
+            WasmEdgeError::Func(FuncError::Json(_))
+        ));
+
+        // wrong number of arguments
+        let result = host_func.call_json(&mut executor, &serde_json::json!([1]));
+        assert!(matches!(
+            result.unwrap_err(),
+            WasmEdgeError::Func(FuncError::Json(_))
+        ));
+
+        // a float or an out-of-range integer for an i32 argument
+        let result = host_func.call_json(&mut executor, &serde_json::json!([1.5, 2]));
+        assert!(matches!(
+            result.unwrap_err(),
+            WasmEdgeError::Func(FuncError::Json(_))
+        ));
+        let result = host_func.call_json(&mut executor, &serde_json::json!([1, 1u64 << 40]));
+        assert!(matches!(
+            result.unwrap_err(),
+            WasmEdgeError::Func(FuncError::Json(_))
+        ));
     }
 
     fn real_add(input: Vec<WasmValue>) -> Result<Vec<WasmValue>, u8> {
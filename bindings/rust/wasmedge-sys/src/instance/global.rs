@@ -69,7 +69,7 @@ impl Global {
     ///
     /// # Errors
     ///
-    /// If fail to set value, then an error is returned.
+    /// If the [Global] is immutable, then a [GlobalError::ModifyConst](wasmedge_types::error::GlobalError::ModifyConst) error is returned; if the type of `val` doesn't match the value type of the [Global], then a [GlobalError::UnmatchedValType](wasmedge_types::error::GlobalError::UnmatchedValType) error is returned. In both cases, the value of the [Global] is left unchanged.
     ///
     /// # Example
     ///
@@ -204,6 +204,34 @@ mod tests {
         assert_eq!(global_ty.mutability(), Mutability::Const);
     }
 
+    #[test]
+    fn test_global_type_var_f64() {
+        // create a mutable f64 GlobalType instance
+        let result = GlobalType::create(ValType::F64, Mutability::Var);
+        assert!(result.is_ok());
+        let global_ty = result.unwrap();
+        assert_eq!(global_ty.value_type(), ValType::F64);
+        assert_eq!(global_ty.mutability(), Mutability::Var);
+
+        // round-trip through wasmedge_types::GlobalType
+        let ty: wasmedge_types::GlobalType = global_ty.into();
+        assert_eq!(ty.value_ty(), ValType::F64);
+        assert_eq!(ty.mutability(), Mutability::Var);
+        let global_ty: GlobalType = ty.into();
+        assert_eq!(global_ty.value_type(), ValType::F64);
+        assert_eq!(global_ty.mutability(), Mutability::Var);
+
+        // the type of a Global created with it
+        let result = Global::create(&global_ty, WasmValue::from_f64(2.5));
+        assert!(result.is_ok());
+        let global = result.unwrap();
+        let result = global.ty();
+        assert!(result.is_ok());
+        let ty = result.unwrap();
+        assert_eq!(ty.value_type(), ValType::F64);
+        assert_eq!(ty.mutability(), Mutability::Var);
+    }
+
     #[test]
     fn test_global_const_i32() {
         // create a GlobalType instance
@@ -221,6 +249,11 @@ mod tests {
         assert_eq!(global_const.get_value().to_i32(), 99);
         let result = global_const.set_value(WasmValue::from_i32(0));
         assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err(),
+            WasmEdgeError::Global(GlobalError::ModifyConst)
+        );
+        assert_eq!(global_const.get_value().to_i32(), 99);
 
         // access the global type
         let result = global_const.ty();
@@ -261,6 +294,39 @@ mod tests {
         assert_eq!(ty.mutability(), Mutability::Var);
     }
 
+    #[test]
+    fn test_global_var_i32() {
+        // create a mutable i32 Global instance
+        let ty =
+            GlobalType::create(ValType::I32, Mutability::Var).expect("fail to create a GlobalType");
+        let mut global =
+            Global::create(&ty, WasmValue::from_i32(1)).expect("fail to create a Global");
+        assert_eq!(global.get_value().to_i32(), 1);
+
+        // set new values
+        let result = global.set_value(WasmValue::from_i32(-7));
+        assert!(result.is_ok());
+        assert_eq!(global.get_value().to_i32(), -7);
+        let result = global.set_value(WasmValue::from_i32(i32::MAX));
+        assert!(result.is_ok());
+        assert_eq!(global.get_value().to_i32(), i32::MAX);
+
+        // reject a value of another type
+        let result = global.set_value(WasmValue::from_i64(1));
+        assert_eq!(
+            result.unwrap_err(),
+            WasmEdgeError::Global(GlobalError::UnmatchedValType)
+        );
+        assert_eq!(global.get_value().to_i32(), i32::MAX);
+
+        // the type of the global
+        let result = global.ty();
+        assert!(result.is_ok());
+        let ty = result.unwrap();
+        assert_eq!(ty.value_type(), ValType::I32);
+        assert_eq!(ty.mutability(), Mutability::Var);
+    }
+
     #[test]
     fn test_global_conflict() {
         {
@@ -290,6 +356,10 @@ mod tests {
             // set a new value of mis-matched Value::I32 type
             let result = global_var.set_value(WasmValue::from_i32(1314));
             assert!(result.is_err());
+            assert_eq!(
+                result.unwrap_err(),
+                WasmEdgeError::Global(GlobalError::UnmatchedValType)
+            );
             assert_eq!(global_var.get_value().to_f32(), 13.14);
 
             // set a new value of Value::F32 type
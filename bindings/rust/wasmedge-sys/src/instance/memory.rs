@@ -13,6 +13,9 @@ use crate::{
 };
 use std::ops::RangeInclusive;
 
+/// The size of a WebAssembly page in bytes.
+const WASM_PAGE_SIZE: u64 = 65536;
+
 /// Defines a WebAssembly memory instance, which is a linear memory described by its [type](crate::MemType). Each memory instance consists of a vector of bytes and an optional maximum size, and its size is a multiple of the WebAssembly page size (*64KiB* of each page).
 #[derive(Debug)]
 pub struct Memory {
@@ -81,9 +84,11 @@ impl Memory {
     ///
     /// # Errors
     ///
-    /// If the `offset + len` is larger than the data size in the [Memory], then an error is returned.
+    /// If the `offset + len` is larger than the data size in the [Memory], then a [MemError::OutOfBounds](wasmedge_types::error::MemError::OutOfBounds) error is returned.
     ///
     pub fn get_data(&self, offset: u32, len: u32) -> WasmEdgeResult<Vec<u8>> {
+        self.check_bounds(offset, len)?;
+
         let mut data = Vec::with_capacity(len as usize);
         unsafe {
             check(ffi::WasmEdge_MemoryInstanceGetData(
@@ -109,11 +114,11 @@ impl Memory {
     /// # Errors
     ///
     /// If the sum of the `offset` and the data length is larger than the size of the [Memory],
-    /// then an error is returned.
+    /// then a [MemError::OutOfBounds](wasmedge_types::error::MemError::OutOfBounds) error is returned, and the [Memory] is left untouched.
     ///
     /// ```
     /// use wasmedge_sys::{Memory, MemType};
-    /// use wasmedge_types::error::{CoreError, CoreExecutionError, WasmEdgeError};
+    /// use wasmedge_types::error::{MemError, WasmEdgeError};
     ///
     /// // create a Memory: the min size 1 and the max size 2
     /// let ty = MemType::create(1..=2).expect("fail to create a memory type");
@@ -122,7 +127,7 @@ impl Memory {
     /// // set data and the data length is larger than the data size in the memory
     /// let result = mem.set_data(vec![1; 10], u32::pow(2, 16) - 9);
     /// assert!(result.is_err());
-    /// assert_eq!(result.unwrap_err(), WasmEdgeError::Core(CoreError::Execution(CoreExecutionError::MemoryOutOfBounds)));
+    /// assert_eq!(result.unwrap_err(), WasmEdgeError::Mem(MemError::OutOfBounds));
     /// ```
     ///
     /// # Example
@@ -151,6 +156,8 @@ impl Memory {
         offset: u32,
     ) -> WasmEdgeResult<()> {
         let data = data.into_iter().collect::<Vec<u8>>();
+        self.check_bounds(offset, data.len() as u32)?;
+
         unsafe {
             check(ffi::WasmEdge_MemoryInstanceSetData(
                 self.inner.0,
@@ -161,6 +168,60 @@ impl Memory {
         }
     }
 
+    /// Reads a value of the [Pod] type `T` in the little-endian byte order from the [Memory].
+    ///
+    /// The value is read byte by byte, so the `offset` is not required to be aligned to the size of `T`.
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - The data start offset in the [Memory].
+    ///
+    /// # Errors
+    ///
+    /// If the `offset + size_of::<T>()` is larger than the data size in the [Memory], then a [MemError::OutOfBounds](wasmedge_types::error::MemError::OutOfBounds) error is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wasmedge_sys::{MemType, Memory};
+    ///
+    /// let ty = MemType::create(1..=1).expect("fail to create a memory type");
+    /// let mut mem = Memory::create(&ty).expect("fail to create a Memory");
+    ///
+    /// mem.write_value(3, 0x1234_u16).expect("fail to write the value");
+    /// assert_eq!(mem.get_data(3, 2).expect("fail to get data"), vec![0x34, 0x12]);
+    /// assert_eq!(mem.read_value::<u16>(3).expect("fail to read the value"), 0x1234);
+    /// ```
+    pub fn read_value<T: Pod>(&self, offset: u32) -> WasmEdgeResult<T> {
+        let data = self.get_data(offset, std::mem::size_of::<T>() as u32)?;
+        Ok(T::from_le_slice(&data))
+    }
+
+    /// Writes a value of the [Pod] type `T` in the little-endian byte order into the [Memory].
+    ///
+    /// The value is written byte by byte, so the `offset` is not required to be aligned to the size of `T`.
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - The data start offset in the [Memory].
+    ///
+    /// * `value` - The value to write.
+    ///
+    /// # Errors
+    ///
+    /// If the `offset + size_of::<T>()` is larger than the data size in the [Memory], then a [MemError::OutOfBounds](wasmedge_types::error::MemError::OutOfBounds) error is returned, and the [Memory] is left untouched.
+    pub fn write_value<T: Pod>(&mut self, offset: u32, value: T) -> WasmEdgeResult<()> {
+        self.set_data(value.to_le_vec(), offset)
+    }
+
+    /// Checks if the data range starting at `offset` with the length `len` is within the current size of the [Memory].
+    fn check_bounds(&self, offset: u32, len: u32) -> WasmEdgeResult<()> {
+        match offset as u64 + len as u64 > self.data_len() {
+            true => Err(WasmEdgeError::Mem(MemError::OutOfBounds)),
+            false => Ok(()),
+        }
+    }
+
     /// Returns the const data pointer to the [Memory].
     ///
     /// # Arguments
@@ -220,7 +281,12 @@ impl Memory {
         unsafe { ffi::WasmEdge_MemoryInstanceGetPageSize(self.inner.0) as u32 }
     }
 
-    /// Grows this WebAssembly memory by `count` pages.
+    /// Returns the size, in bytes, of this wasm memory, which is always a multiple of the WebAssembly page size (64 KiB).
+    pub fn data_len(&self) -> u64 {
+        self.size() as u64 * WASM_PAGE_SIZE
+    }
+
+    /// Grows this WebAssembly memory by `count` pages, and returns the page count before growing.
     ///
     /// # Arguments
     ///
@@ -228,7 +294,7 @@ impl Memory {
     ///
     /// # Errors
     ///
-    /// If fail to grow the page count, then an error is returned.
+    /// If fail to grow the page count, for example, the new page count exceeds the maximum size declared by the [type](crate::MemType) of the [Memory], then an error is returned and the size of the [Memory] is left unchanged.
     ///
     /// # Example
     ///
@@ -242,13 +308,16 @@ impl Memory {
     /// let count = mem.size();
     /// assert_eq!(count, 10);
     ///
-    /// // grow 5 pages
-    /// mem.grow(10).expect("fail to grow the page count");
+    /// // grow 10 pages
+    /// let previous = mem.grow(10).expect("fail to grow the page count");
+    /// assert_eq!(previous, 10);
     /// assert_eq!(mem.size(), 20);
     /// ```
     ///
-    pub fn grow(&mut self, count: u32) -> WasmEdgeResult<()> {
-        unsafe { check(ffi::WasmEdge_MemoryInstanceGrowPage(self.inner.0, count)) }
+    pub fn grow(&mut self, count: u32) -> WasmEdgeResult<u32> {
+        let previous = self.size();
+        unsafe { check(ffi::WasmEdge_MemoryInstanceGrowPage(self.inner.0, count))? };
+        Ok(previous)
     }
 }
 impl Drop for Memory {
@@ -259,11 +328,198 @@ impl Drop for Memory {
     }
 }
 
+mod private {
+    pub trait Sealed {}
+}
+
+/// Defines the plain fixed-size types which can be [read](crate::Memory::read_value) from and [written](crate::Memory::write_value) into a [Memory] in the little-endian byte order.
+///
+/// The trait is sealed, and implemented for `u8`, `u16`, `u32`, `u64`, `i32`, `i64`, `f32`, and `f64`.
+pub trait Pod: private::Sealed + Copy {
+    #[doc(hidden)]
+    fn from_le_slice(data: &[u8]) -> Self;
+
+    #[doc(hidden)]
+    fn to_le_vec(self) -> Vec<u8>;
+}
+
+macro_rules! impl_pod {
+    ($($ty:ty),*) => {
+        $(
+            impl private::Sealed for $ty {}
+            impl Pod for $ty {
+                fn from_le_slice(data: &[u8]) -> Self {
+                    let mut bytes = [0u8; std::mem::size_of::<$ty>()];
+                    bytes.copy_from_slice(data);
+                    <$ty>::from_le_bytes(bytes)
+                }
+
+                fn to_le_vec(self) -> Vec<u8> {
+                    self.to_le_bytes().to_vec()
+                }
+            }
+        )*
+    };
+}
+
+impl_pod!(u8, u16, u32, u64, i32, i64, f32, f64);
+
+/// Marks the types without padding bytes, which can be [written](crate::MemoryMut::write_struct) into a memory byte by byte.
+///
+/// The trait is implemented for the primitive integer and floating-point types, and for the arrays of the types implementing it.
+///
+/// # Safety
+///
+/// The implementing type must be `#[repr(C)]` or `#[repr(transparent)]`, and every byte of it must be initialized, that is, it must have no padding bytes, and its fields must implement [NoPadding] as well.
+///
+/// # Example
+///
+/// ```
+/// use wasmedge_sys::NoPadding;
+///
+/// #[repr(C)]
+/// #[derive(Clone, Copy)]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// // two i32 fields leave no room for padding
+/// unsafe impl NoPadding for Point {}
+/// ```
+pub unsafe trait NoPadding: Copy {}
+
+macro_rules! impl_no_padding {
+    ($($ty:ty),*) => {
+        $(
+            unsafe impl NoPadding for $ty {}
+        )*
+    };
+}
+
+impl_no_padding!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+unsafe impl<T: NoPadding, const N: usize> NoPadding for [T; N] {}
+
 #[derive(Debug)]
 pub(crate) struct InnerMemory(pub(crate) *mut ffi::WasmEdge_MemoryInstanceContext);
 unsafe impl Send for InnerMemory {}
 unsafe impl Sync for InnerMemory {}
 
+/// Defines a mutable view of the memory of the caller of a host function.
+///
+/// A [MemoryMut] is provided to the closures of the host functions created by [create_with_memory](crate::Function::create_with_memory), through which the host functions taking a pointer and a length into the linear memory [read](crate::MemoryMut::read) and [write](crate::MemoryMut::write) the buffers of the guest. Since [slice_mut](crate::MemoryMut::slice_mut) borrows the [MemoryMut] mutably, at most one mutable slice of the memory exists at a time.
+#[derive(Debug)]
+pub struct MemoryMut {
+    ctx: *mut ffi::WasmEdge_MemoryInstanceContext,
+}
+impl MemoryMut {
+    pub(crate) fn from_raw(ctx: *mut ffi::WasmEdge_MemoryInstanceContext) -> Self {
+        Self { ctx }
+    }
+
+    /// Checks if the data range starting at `offset` with the length `len` is within the current size of the memory.
+    fn check_bounds(&self, offset: u32, len: u32) -> WasmEdgeResult<()> {
+        let data_len =
+            unsafe { ffi::WasmEdge_MemoryInstanceGetPageSize(self.ctx) } as u64 * WASM_PAGE_SIZE;
+        match offset as u64 + len as u64 > data_len {
+            true => Err(WasmEdgeError::Mem(MemError::OutOfBounds)),
+            false => Ok(()),
+        }
+    }
+
+    /// Returns a bounds-checked mutable slice of the memory.
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - The start offset of the slice in the memory.
+    ///
+    /// * `len` - The length of the slice.
+    ///
+    /// # Error
+    ///
+    /// If the caller has no memory, then a `MemError::MutPtr` error is returned. If `offset` + `len` exceeds the data size of the memory, then a `MemError::OutOfBounds` error is returned.
+    pub fn slice_mut(&mut self, offset: u32, len: u32) -> WasmEdgeResult<&mut [u8]> {
+        if self.ctx.is_null() {
+            return Err(WasmEdgeError::Mem(MemError::MutPtr));
+        }
+        self.check_bounds(offset, len)?;
+        let ptr = unsafe { ffi::WasmEdge_MemoryInstanceGetPointer(self.ctx, offset, len) };
+        match ptr.is_null() {
+            true => Err(WasmEdgeError::Mem(MemError::MutPtr)),
+            false => Ok(unsafe { std::slice::from_raw_parts_mut(ptr, len as usize) }),
+        }
+    }
+
+    /// Reads the bytes of the memory, with bounds checking.
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - The start offset of the bytes to read in the memory.
+    ///
+    /// * `len` - The number of the bytes to read.
+    ///
+    /// # Error
+    ///
+    /// If the caller has no memory, then a `MemError::ConstPtr` error is returned. If `offset` + `len` exceeds the data size of the memory, then a `MemError::OutOfBounds` error is returned.
+    pub fn read(&self, offset: u32, len: u32) -> WasmEdgeResult<Vec<u8>> {
+        if self.ctx.is_null() {
+            return Err(WasmEdgeError::Mem(MemError::ConstPtr));
+        }
+        self.check_bounds(offset, len)?;
+        let ptr = unsafe { ffi::WasmEdge_MemoryInstanceGetPointerConst(self.ctx, offset, len) };
+        match ptr.is_null() {
+            true => Err(WasmEdgeError::Mem(MemError::ConstPtr)),
+            false => Ok(unsafe { std::slice::from_raw_parts(ptr, len as usize) }.to_vec()),
+        }
+    }
+
+    /// Writes the given bytes into the memory, with bounds checking.
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - The offset in the memory to write the bytes at.
+    ///
+    /// * `data` - The bytes to write.
+    ///
+    /// # Error
+    ///
+    /// If the caller has no memory, then a `MemError::MutPtr` error is returned. If the bytes don't fit in the memory at `offset`, then a `MemError::OutOfBounds` error is returned.
+    pub fn write(&mut self, offset: u32, data: &[u8]) -> WasmEdgeResult<()> {
+        let len =
+            u32::try_from(data.len()).map_err(|_| WasmEdgeError::Mem(MemError::OutOfBounds))?;
+        self.slice_mut(offset, len)?.copy_from_slice(data);
+        Ok(())
+    }
+
+    /// Writes the bytes of the given value into the memory, with bounds checking.
+    ///
+    /// This is intended for the host functions returning a `#[repr(C)]` struct through a pointer argument, which is common in C-style ABIs. The bytes are copied in the byte order of the host, which matches the little-endian byte order of WebAssembly on little-endian hosts. Since the padding bytes are uninitialized, copying them is undefined behavior, so `T` is restricted to the types implementing [NoPadding]; add explicit fields in place of the padding of a struct if needed.
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - The offset in the memory to write the value at.
+    ///
+    /// * `value` - The value to write.
+    ///
+    /// # Error
+    ///
+    /// If the caller has no memory, then a `MemError::MutPtr` error is returned. If the value doesn't fit in the memory at `offset`, then a `MemError::OutOfBounds` error is returned.
+    pub fn write_struct<T: NoPadding>(&mut self, offset: u32, value: &T) -> WasmEdgeResult<()> {
+        let len = u32::try_from(std::mem::size_of::<T>())
+            .map_err(|_| WasmEdgeError::Mem(MemError::OutOfBounds))?;
+        let buf = self.slice_mut(offset, len)?;
+        // safe since every byte of `T` is initialized, as guaranteed by `NoPadding`
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                value as *const T as *const u8,
+                buf.as_mut_ptr(),
+                buf.len(),
+            )
+        };
+        Ok(())
+    }
+}
+
 /// Defines the type of a wasm memory instance
 #[derive(Debug)]
 pub struct MemType {
@@ -345,12 +601,81 @@ unsafe impl Sync for InnerMemType {}
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::error::{CoreError, CoreExecutionError, WasmEdgeError};
+    use crate::error::WasmEdgeError;
     use std::{
         sync::{Arc, Mutex},
         thread,
     };
 
+    #[test]
+    fn test_memory_mut() {
+        let result = MemType::create(1..=2);
+        assert!(result.is_ok());
+        let ty = result.unwrap();
+        let result = Memory::create(&ty);
+        assert!(result.is_ok());
+        let memory = result.unwrap();
+
+        let mut view = MemoryMut::from_raw(memory.inner.0);
+        let result = view.slice_mut(10, 4);
+        assert!(result.is_ok());
+        result.unwrap().copy_from_slice(&[1, 2, 3, 4]);
+        let result = memory.get_data(10, 4);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), vec![1, 2, 3, 4]);
+
+        // out of bounds
+        let result = view.slice_mut(65535, 2);
+        assert_eq!(
+            result.unwrap_err(),
+            WasmEdgeError::Mem(MemError::OutOfBounds)
+        );
+
+        // no memory
+        let mut view = MemoryMut::from_raw(std::ptr::null_mut());
+        let result = view.slice_mut(0, 1);
+        assert_eq!(result.unwrap_err(), WasmEdgeError::Mem(MemError::MutPtr));
+    }
+
+    #[test]
+    fn test_memory_mut_read_write() {
+        let result = MemType::create(1..=2);
+        assert!(result.is_ok());
+        let ty = result.unwrap();
+        let result = Memory::create(&ty);
+        assert!(result.is_ok());
+        let memory = result.unwrap();
+
+        let mut view = MemoryMut::from_raw(memory.inner.0);
+        let result = view.write(100, b"hello");
+        assert!(result.is_ok());
+        let result = view.read(100, 5);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), b"hello");
+        let result = memory.get_data(100, 5);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), b"hello");
+
+        // out of bounds
+        let result = view.write(65534, b"abc");
+        assert_eq!(
+            result.unwrap_err(),
+            WasmEdgeError::Mem(MemError::OutOfBounds)
+        );
+        let result = view.read(65534, 3);
+        assert_eq!(
+            result.unwrap_err(),
+            WasmEdgeError::Mem(MemError::OutOfBounds)
+        );
+
+        // no memory
+        let mut view = MemoryMut::from_raw(std::ptr::null_mut());
+        let result = view.read(0, 1);
+        assert_eq!(result.unwrap_err(), WasmEdgeError::Mem(MemError::ConstPtr));
+        let result = view.write(0, b"a");
+        assert_eq!(result.unwrap_err(), WasmEdgeError::Mem(MemError::MutPtr));
+    }
+
     #[test]
     fn test_memory_type() {
         // case 1
@@ -399,14 +724,43 @@ mod tests {
         let count = mem.size();
         assert_eq!(count, 10);
 
-        // grow 5 pages
+        // grow 10 pages
         let result = mem.grow(10);
         assert!(result.is_ok());
+        assert_eq!(result.unwrap(), 10);
         assert_eq!(mem.size(), 20);
 
         // grow additional  pages, which causes a failure
         let result = mem.grow(1);
         assert!(result.is_err());
+        assert_eq!(mem.size(), 20);
+    }
+
+    #[test]
+    fn test_memory_data_len() {
+        // create a Memory with a limit range [1, 3]
+        let ty = MemType::create(1..=3).expect("fail to create a memory type");
+        let mut mem = Memory::create(&ty).expect("fail to create a Memory");
+        assert_eq!(mem.size(), 1);
+        assert_eq!(mem.data_len(), 65536);
+
+        // grow 1 page
+        let result = mem.grow(1);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), 1);
+        assert_eq!(mem.size(), 2);
+        assert_eq!(mem.data_len(), 2 * 65536);
+        assert_eq!(mem.data_len() % 65536, 0);
+
+        // the newly grown page is accessible
+        let result = mem.set_data([1; 4], 65536);
+        assert!(result.is_ok());
+
+        // grow beyond the maximum
+        let result = mem.grow(2);
+        assert!(result.is_err());
+        assert_eq!(mem.size(), 2);
+        assert_eq!(mem.data_len(), 2 * 65536);
     }
 
     #[test]
@@ -445,7 +799,7 @@ mod tests {
         assert!(result.is_err());
         assert_eq!(
             result.unwrap_err(),
-            WasmEdgeError::Core(CoreError::Execution(CoreExecutionError::MemoryOutOfBounds))
+            WasmEdgeError::Mem(MemError::OutOfBounds)
         );
 
         // grow the memory size
@@ -456,6 +810,99 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_memory_data_bounds() {
+        // create a Memory with a single page
+        let ty = MemType::create(1..=1).expect("fail to create a memory type");
+        let mut mem = Memory::create(&ty).expect("fail to create a Memory");
+
+        // write a string and read it back
+        let hello = "hello, wasmedge".as_bytes();
+        let result = mem.set_data(hello.iter().copied(), 100);
+        assert!(result.is_ok());
+        let result = mem.get_data(100, hello.len() as u32);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), hello);
+
+        // the last byte of the memory is accessible
+        let result = mem.set_data([0xff], 65535);
+        assert!(result.is_ok());
+        let result = mem.get_data(65535, 1);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), [0xff]);
+
+        // out-of-range reads and writes fail
+        let result = mem.get_data(65535, 2);
+        assert_eq!(
+            result.unwrap_err(),
+            WasmEdgeError::Mem(MemError::OutOfBounds)
+        );
+        let result = mem.get_data(u32::MAX, u32::MAX);
+        assert_eq!(
+            result.unwrap_err(),
+            WasmEdgeError::Mem(MemError::OutOfBounds)
+        );
+        let result = mem.set_data(hello.iter().copied(), 65536);
+        assert_eq!(
+            result.unwrap_err(),
+            WasmEdgeError::Mem(MemError::OutOfBounds)
+        );
+
+        // the failed write doesn't corrupt the memory
+        let result = mem.get_data(65535, 1);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), [0xff]);
+        let result = mem.get_data(100, hello.len() as u32);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), hello);
+    }
+
+    #[test]
+    fn test_memory_pod() {
+        let ty = MemType::create(1..=1).expect("fail to create a memory type");
+        let mut mem = Memory::create(&ty).expect("fail to create a Memory");
+
+        // write a u64 at an unaligned offset and read it back
+        let value = 0x0123_4567_89ab_cdef_u64;
+        let result = mem.write_value(13, value);
+        assert!(result.is_ok());
+        let result = mem.read_value::<u64>(13);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), value);
+        let result = mem.get_data(13, 8);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), value.to_le_bytes());
+
+        // floats are bit-identical
+        let value = f64::from_bits(0x7ff8_0000_0000_0001);
+        let result = mem.write_value(100, value);
+        assert!(result.is_ok());
+        let result = mem.read_value::<f64>(100);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().to_bits(), value.to_bits());
+
+        // signed values
+        let result = mem.write_value(200, -2_i32);
+        assert!(result.is_ok());
+        assert_eq!(mem.read_value::<i32>(200).unwrap(), -2);
+        assert_eq!(mem.read_value::<u32>(200).unwrap(), u32::MAX - 1);
+        assert_eq!(mem.read_value::<u8>(203).unwrap(), 0xff);
+
+        // out of bounds
+        let result = mem.read_value::<u64>(65529);
+        assert_eq!(
+            result.unwrap_err(),
+            WasmEdgeError::Mem(MemError::OutOfBounds)
+        );
+        let result = mem.write_value(65533, 1_f32);
+        assert_eq!(
+            result.unwrap_err(),
+            WasmEdgeError::Mem(MemError::OutOfBounds)
+        );
+        let result = mem.read_value::<u64>(65528);
+        assert!(result.is_ok());
+    }
+
     #[test]
     fn test_memory_send() {
         {
@@ -3,7 +3,12 @@
 use crate::{
     error::{InstanceError, WasmEdgeError},
     ffi,
-    instance::{function::InnerFunc, global::InnerGlobal, memory::InnerMemory, table::InnerTable},
+    instance::{
+        function::{release_host_funcs, InnerFunc},
+        global::InnerGlobal,
+        memory::InnerMemory,
+        table::InnerTable,
+    },
     types::WasmEdgeString,
     utils::string_to_c_char,
     Function, Global, Memory, Table, WasmEdgeResult,
@@ -70,7 +75,9 @@ impl Instance {
             ))),
             false => Ok(Function {
                 inner: InnerFunc(func_ctx),
+                key: None,
                 registered: true,
+                cost: 0,
             }),
         }
     }
@@ -423,6 +430,8 @@ pub struct ImportModule {
     pub(crate) inner: InnerInstance,
     pub(crate) registered: bool,
     name: String,
+    // the keys of the host functions added to this module, which are owned by this module
+    pub(crate) host_funcs: Vec<usize>,
 }
 impl Drop for ImportModule {
     fn drop(&mut self) {
@@ -430,6 +439,7 @@ impl Drop for ImportModule {
             unsafe {
                 ffi::WasmEdge_ModuleInstanceDelete(self.inner.0);
             }
+            release_host_funcs(&self.host_funcs);
         }
     }
 }
@@ -453,6 +463,7 @@ impl ImportModule {
                 inner: InnerInstance(ctx),
                 registered: false,
                 name: name.as_ref().to_string(),
+                host_funcs: Vec::new(),
             }),
         }
     }
@@ -469,6 +480,8 @@ impl ImportInstance for ImportModule {
             ffi::WasmEdge_ModuleInstanceAddFunction(self.inner.0, func_name.as_raw(), func.inner.0);
         }
         func.inner.0 = std::ptr::null_mut();
+        // the closure of the host function moves into this module together with the function instance
+        self.host_funcs.extend(func.key);
     }
 
     fn add_table(&mut self, name: impl AsRef<str>, mut table: Table) {
@@ -514,6 +527,8 @@ impl ImportInstance for ImportModule {
 pub struct WasiModule {
     pub(crate) inner: InnerInstance,
     pub(crate) registered: bool,
+    // the keys of the host functions added to this module, which are owned by this module
+    pub(crate) host_funcs: Vec<usize>,
 }
 impl Drop for WasiModule {
     fn drop(&mut self) {
@@ -521,6 +536,7 @@ impl Drop for WasiModule {
             unsafe {
                 ffi::WasmEdge_ModuleInstanceDelete(self.inner.0);
             }
+            release_host_funcs(&self.host_funcs);
         }
     }
 }
@@ -533,7 +549,7 @@ impl WasiModule {
     ///
     /// * `envs` - The environment variables in the format `ENV_VAR_NAME=VALUE`.
     ///
-    /// * `preopens` - The directories to pre-open. The required format is `DIR1:DIR2`.
+    /// * `preopens` - The directories to pre-open. The required format is `GUEST_PATH:HOST_PATH`, which maps the directory `HOST_PATH` on the host to `GUEST_PATH` in the guest; a single `PATH` maps the directory to the same path. The pre-opened directories are assigned the file descriptors from `3` on, in order.
     ///
     /// # Error
     ///
@@ -579,6 +595,7 @@ impl WasiModule {
             false => Ok(Self {
                 inner: InnerInstance(ctx),
                 registered: false,
+                host_funcs: Vec::new(),
             }),
         }
     }
@@ -639,7 +656,7 @@ impl WasiModule {
 
     /// Returns the WASI exit code.
     ///
-    /// The WASI exit code can be accessed after running the "_start" function of a `wasm32-wasi` program.
+    /// The WASI exit code can be accessed after running the "_start" function of a `wasm32-wasi` program. Notice that a program terminated by `proc_exit` is regarded as a successful run, that is, running the function returns `Ok`, whatever the exit code is; therefore, check the exit code to get the exit status of the program.
     pub fn exit_code(&self) -> u32 {
         unsafe { ffi::WasmEdge_ModuleInstanceWASIGetExitCode(self.inner.0 as *const _) }
     }
@@ -656,7 +673,9 @@ impl AsInstance for WasiModule {
             ))),
             false => Ok(Function {
                 inner: InnerFunc(func_ctx),
+                key: None,
                 registered: true,
+                cost: 0,
             }),
         }
     }
@@ -836,6 +855,8 @@ impl ImportInstance for WasiModule {
             ffi::WasmEdge_ModuleInstanceAddFunction(self.inner.0, func_name.as_raw(), func.inner.0);
         }
         func.inner.0 = std::ptr::null_mut();
+        // the closure of the host function moves into this module together with the function instance
+        self.host_funcs.extend(func.key);
     }
 
     fn add_table(&mut self, name: impl AsRef<str>, mut table: Table) {
@@ -895,6 +916,8 @@ impl ImportInstance for WasiModule {
 pub struct WasmEdgeProcessModule {
     pub(crate) inner: InnerInstance,
     pub(crate) registered: bool,
+    // the keys of the host functions added to this module, which are owned by this module
+    pub(crate) host_funcs: Vec<usize>,
 }
 impl Drop for WasmEdgeProcessModule {
     fn drop(&mut self) {
@@ -902,6 +925,7 @@ impl Drop for WasmEdgeProcessModule {
             unsafe {
                 ffi::WasmEdge_ModuleInstanceDelete(self.inner.0);
             }
+            release_host_funcs(&self.host_funcs);
         }
     }
 }
@@ -937,6 +961,7 @@ impl WasmEdgeProcessModule {
             false => Ok(Self {
                 inner: InnerInstance(ctx),
                 registered: false,
+                host_funcs: Vec::new(),
             }),
         }
     }
@@ -977,7 +1002,9 @@ impl AsInstance for WasmEdgeProcessModule {
             ))),
             false => Ok(Function {
                 inner: InnerFunc(func_ctx),
+                key: None,
                 registered: true,
+                cost: 0,
             }),
         }
     }
@@ -1157,6 +1184,8 @@ impl ImportInstance for WasmEdgeProcessModule {
             ffi::WasmEdge_ModuleInstanceAddFunction(self.inner.0, func_name.as_raw(), func.inner.0);
         }
         func.inner.0 = std::ptr::null_mut();
+        // the closure of the host function moves into this module together with the function instance
+        self.host_funcs.extend(func.key);
     }
 
     fn add_table(&mut self, name: impl AsRef<str>, mut table: Table) {
@@ -1252,14 +1281,14 @@ impl ImportObject {
 mod tests {
     use super::*;
     use crate::{
-        utils, Config, Executor, FuncType, GlobalType, ImportModule, MemType, Store, TableType, Vm,
-        WasmValue,
+        utils, Config, Engine, Executor, FuncType, GlobalType, ImportModule, Loader, MemType,
+        Module, Store, TableType, Validator, Vm, WasmValue,
     };
     use std::{
         sync::{Arc, Mutex},
         thread,
     };
-    use wasmedge_types::{Mutability, RefType, ValType};
+    use wasmedge_types::{wat2wasm, Mutability, RefType, ValType};
 
     #[test]
     fn test_instance_add_instance() {
@@ -1502,6 +1531,138 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_instance_wasi_env_preopen() {
+        // the host directory to pre-open
+        let host_dir =
+            std::env::temp_dir().join(format!("wasmedge-sys-wasi-{}", std::process::id()));
+        let result = std::fs::create_dir_all(&host_dir);
+        assert!(result.is_ok());
+        let preopen = format!("/sandbox:{}", host_dir.display());
+
+        // create a wasi module instance
+        let result = WasiModule::create(
+            Some(vec!["env_to_file"]),
+            Some(vec!["GREETING=hello"]),
+            Some(vec![preopen.as_str()]),
+        );
+        assert!(result.is_ok());
+        let wasi = result.unwrap();
+
+        // a wasi program writing the value of `GREETING` to `out.txt` in the pre-opened directory
+        let module = load_module(
+            br#"
+            (module
+                (import "wasi_snapshot_preview1" "environ_sizes_get"
+                    (func $environ_sizes_get (param i32 i32) (result i32)))
+                (import "wasi_snapshot_preview1" "environ_get"
+                    (func $environ_get (param i32 i32) (result i32)))
+                (import "wasi_snapshot_preview1" "path_open"
+                    (func $path_open (param i32 i32 i32 i32 i32 i64 i64 i32 i32) (result i32)))
+                (import "wasi_snapshot_preview1" "fd_write"
+                    (func $fd_write (param i32 i32 i32 i32) (result i32)))
+                (import "wasi_snapshot_preview1" "fd_close"
+                    (func $fd_close (param i32) (result i32)))
+                (memory (export "memory") 1)
+                (data (i32.const 0x300) "out.txt")
+
+                (func $check (param i32)
+                    (if (local.get 0) (then unreachable)))
+
+                (func (export "_start")
+                    ;; the count of the environment variables at 0x0, and the size of the strings at 0x4
+                    (call $check (call $environ_sizes_get (i32.const 0x0) (i32.const 0x4)))
+                    ;; the pointers at 0x100, and the strings "GREETING=hello " at 0x200
+                    (call $check (call $environ_get (i32.const 0x100) (i32.const 0x200)))
+                    ;; create `out.txt` with the `fd_write` right in the first pre-opened directory
+                    (call $check (call $path_open
+                        (i32.const 3) (i32.const 0) (i32.const 0x300) (i32.const 7)
+                        (i32.const 9) (i64.const 64) (i64.const 0) (i32.const 0) (i32.const 0x8)))
+                    ;; write the value after "GREETING=" without the trailing NUL
+                    (i32.store (i32.const 0x10) (i32.const 0x209))
+                    (i32.store (i32.const 0x14) (i32.sub (i32.load (i32.const 0x4)) (i32.const 10)))
+                    (call $check (call $fd_write
+                        (i32.load (i32.const 0x8)) (i32.const 0x10) (i32.const 1) (i32.const 0x18)))
+                    (call $check (call $fd_close (i32.load (i32.const 0x8)))))
+            )
+"#,
+        );
+
+        // register the wasi module instance like any other import object, and run the program
+        let result = Store::create();
+        assert!(result.is_ok());
+        let mut store = result.unwrap();
+        let result = Executor::create(None, None);
+        assert!(result.is_ok());
+        let mut executor = result.unwrap();
+        let import = ImportObject::Wasi(wasi);
+        let result = executor.register_import_object(&mut store, &import);
+        assert!(result.is_ok());
+        let result = executor.register_active_module(&mut store, &module);
+        assert!(result.is_ok());
+        let instance = result.unwrap();
+        let result = instance.get_func("_start");
+        assert!(result.is_ok());
+        let start = result.unwrap();
+        let result = executor.run_func(&start, []);
+        assert!(result.is_ok());
+
+        // check the file written by the program
+        let result = std::fs::read_to_string(host_dir.join("out.txt"));
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), "hello");
+
+        let result = std::fs::remove_dir_all(&host_dir);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_instance_wasi_exit_code() {
+        let result = WasiModule::create(None, None, None);
+        assert!(result.is_ok());
+        let wasi = result.unwrap();
+
+        // a wasi program exiting with the code 42
+        let module = load_module(
+            br#"
+            (module
+                (import "wasi_snapshot_preview1" "proc_exit" (func $proc_exit (param i32)))
+                (memory (export "memory") 1)
+                (func (export "_start")
+                    (call $proc_exit (i32.const 42))
+                    unreachable)
+            )
+"#,
+        );
+
+        let result = Store::create();
+        assert!(result.is_ok());
+        let mut store = result.unwrap();
+        let result = Executor::create(None, None);
+        assert!(result.is_ok());
+        let mut executor = result.unwrap();
+        let import = ImportObject::Wasi(wasi);
+        let result = executor.register_import_object(&mut store, &import);
+        assert!(result.is_ok());
+        let result = executor.register_active_module(&mut store, &module);
+        assert!(result.is_ok());
+        let instance = result.unwrap();
+        let result = instance.get_func("_start");
+        assert!(result.is_ok());
+        let start = result.unwrap();
+
+        // the termination by `proc_exit` doesn't reach `unreachable`, and is reported as a successful run
+        let result = executor.run_func(&start, []);
+        assert!(result.is_ok());
+        assert!(result.unwrap().is_empty());
+
+        // the exit code is kept by the wasi module instance
+        match &import {
+            ImportObject::Wasi(wasi) => assert_eq!(wasi.exit_code(), 42),
+            _ => unreachable!(),
+        }
+    }
+
     #[test]
     fn test_instance_wasmedge_process() {
         // load plugins
@@ -1607,6 +1768,47 @@ mod tests {
         assert_eq!(global.mutability(), Mutability::Const);
     }
 
+    #[test]
+    fn test_instance_find_not_found() {
+        let vm = create_vm();
+        let result = vm.store_mut();
+        assert!(result.is_ok());
+        let mut store = result.unwrap();
+        let result = store.module("extern_module");
+        assert!(result.is_ok());
+        let instance = result.unwrap();
+
+        // the existing exports are found as registered instances
+        let result = instance.get_func("add");
+        assert!(result.is_ok());
+        assert!(result.unwrap().registered);
+        let result = instance.get_memory("mem");
+        assert!(result.is_ok());
+        assert!(result.unwrap().registered);
+
+        // the misspelled names are reported
+        let result = instance.get_func("ad");
+        assert_eq!(
+            result.unwrap_err(),
+            WasmEdgeError::Instance(InstanceError::NotFoundFunc("ad".into()))
+        );
+        let result = instance.get_table("tabel");
+        assert_eq!(
+            result.unwrap_err(),
+            WasmEdgeError::Instance(InstanceError::NotFoundTable("tabel".into()))
+        );
+        let result = instance.get_memory("memory");
+        assert_eq!(
+            result.unwrap_err(),
+            WasmEdgeError::Instance(InstanceError::NotFoundMem("memory".into()))
+        );
+        let result = instance.get_global("globl");
+        assert_eq!(
+            result.unwrap_err(),
+            WasmEdgeError::Instance(InstanceError::NotFoundGlobal("globl".into()))
+        );
+    }
+
     #[test]
     fn test_instance_find_names() {
         let vm = create_vm();
@@ -1644,6 +1846,46 @@ mod tests {
         assert_eq!(result.unwrap(), ["global"]);
     }
 
+    #[test]
+    fn test_instance_export_names() {
+        // instantiate a module with two exported functions
+        let module = load_module(
+            br#"
+            (module
+                (func (export "add") (param i32 i32) (result i32)
+                    (i32.add (local.get 0) (local.get 1)))
+                (func (export "sub") (param i32 i32) (result i32)
+                    (i32.sub (local.get 0) (local.get 1)))
+                (memory (export "memory") 1)
+            )
+"#,
+        );
+        let result = Store::create();
+        assert!(result.is_ok());
+        let mut store = result.unwrap();
+        let result = Executor::create(None, None);
+        assert!(result.is_ok());
+        let mut executor = result.unwrap();
+        let result = executor.register_active_module(&mut store, &module);
+        assert!(result.is_ok());
+        let instance = result.unwrap();
+
+        // both exported functions are listed
+        assert_eq!(instance.func_len(), 2);
+        let result = instance.func_names();
+        assert!(result.is_some());
+        let names = result.unwrap();
+        assert!(names.contains(&String::from("add")));
+        assert!(names.contains(&String::from("sub")));
+
+        // the exported memory is listed, and no table or global is exported
+        assert_eq!(instance.mem_names(), Some(vec![String::from("memory")]));
+        assert_eq!(instance.table_len(), 0);
+        assert!(instance.table_names().is_none());
+        assert_eq!(instance.global_len(), 0);
+        assert!(instance.global_names().is_none());
+    }
+
     #[test]
     fn test_instance_get() {
         let module_name = "extern_module";
@@ -1726,6 +1968,24 @@ mod tests {
         assert_eq!(ty.limit(), 10..=20);
     }
 
+    // compiles the module in the WAT format to wasm, and returns the loaded and validated module
+    fn load_module(wat: &[u8]) -> Module {
+        let result = wat2wasm(wat);
+        assert!(result.is_ok());
+        let wasm_bytes = result.unwrap();
+        let result = Loader::create(None);
+        assert!(result.is_ok());
+        let loader = result.unwrap();
+        let result = loader.from_bytes(wasm_bytes);
+        assert!(result.is_ok());
+        let module = result.unwrap();
+        let result = Validator::create(None);
+        assert!(result.is_ok());
+        let validator = result.unwrap();
+        assert!(validator.validate(&module).is_ok());
+        module
+    }
+
     fn create_vm() -> Vm {
         let module_name = "extern_module";
 
@@ -82,8 +82,10 @@ impl Table {
     ///
     /// # Error
     ///
-    /// If fail to get the data, then an error is returned.
+    /// If `idx` is not less than the [capacity](crate::Table::capacity) of the [Table], then a [TableError::OutOfBounds](wasmedge_types::error::TableError::OutOfBounds) error is returned. If fail to get the data, then an error is returned.
     pub fn get_data(&self, idx: u32) -> WasmEdgeResult<WasmValue> {
+        self.check_bounds(idx)?;
+
         let raw_val = unsafe {
             let mut data = ffi::WasmEdge_ValueGenI32(0);
             check(ffi::WasmEdge_TableInstanceGetData(
@@ -106,8 +108,11 @@ impl Table {
     ///
     /// # Error
     ///
-    /// If fail to set data, then an error is returned.
+    /// If `idx` is not less than the [capacity](crate::Table::capacity) of the [Table], then a [TableError::OutOfBounds](wasmedge_types::error::TableError::OutOfBounds) error is returned; if the type of `data` doesn't match the element type of the [Table], then a [TableError::UnmatchedRefType](wasmedge_types::error::TableError::UnmatchedRefType) error is returned. If fail to set data, then an error is returned.
     pub fn set_data(&mut self, data: WasmValue, idx: u32) -> WasmEdgeResult<()> {
+        self.check_bounds(idx)?;
+        self.check_elem_ty(&data)?;
+
         unsafe {
             check(ffi::WasmEdge_TableInstanceSetData(
                 self.inner.0,
@@ -117,6 +122,32 @@ impl Table {
         }
     }
 
+    /// Checks if `idx` is within the capacity of the [Table].
+    fn check_bounds(&self, idx: u32) -> WasmEdgeResult<()> {
+        let size = self.capacity() as u32;
+        match idx >= size {
+            true => Err(WasmEdgeError::Table(TableError::OutOfBounds {
+                index: idx,
+                size,
+            })),
+            false => Ok(()),
+        }
+    }
+
+    /// Checks if the type of `data` matches the element type of the [Table].
+    fn check_elem_ty(&self, data: &WasmValue) -> WasmEdgeResult<()> {
+        // the value types and the reference types share the same type codes
+        match u32::from(data.ty()) != u32::from(self.ty()?.elem_ty()) {
+            true => Err(WasmEdgeError::Table(TableError::UnmatchedRefType)),
+            false => Ok(()),
+        }
+    }
+
+    /// Returns the size, that is, the number of the elements, of the [Table].
+    pub fn size(&self) -> u32 {
+        unsafe { ffi::WasmEdge_TableInstanceGetSize(self.inner.0) }
+    }
+
     /// Returns the capacity of the [Table].
     ///
     /// # Example
@@ -137,19 +168,44 @@ impl Table {
         unsafe { ffi::WasmEdge_TableInstanceGetSize(self.inner.0) as usize }
     }
 
-    /// Increases the capacity of the [Table].
+    /// Increases the capacity of the [Table], initializes the new elements with `init`, and returns the size before growing.
     ///
     /// After growing, the new capacity must be in the range defined by `limit` when the table is created.
     ///
-    /// # Argument
+    /// # Arguments
     ///
     /// - `size` specifies the size to be added to the [Table].
     ///
+    /// - `init` specifies the value of the new elements, for example, `WasmValue::from_null_ref(RefType::FuncRef)` for a table of function references.
+    ///
     /// # Error
     ///
-    /// If fail to increase the size of the [Table], then an error is returned.
-    pub fn grow(&mut self, size: u32) -> WasmEdgeResult<()> {
-        unsafe { check(ffi::WasmEdge_TableInstanceGrow(self.inner.0, size)) }
+    /// If the type of `init` doesn't match the element type of the [Table], then a [TableError::UnmatchedRefType](wasmedge_types::error::TableError::UnmatchedRefType) error is returned. If fail to increase the size of the [Table], for example, the new size exceeds the maximum size of the [Table], then an error is returned. In both cases, the [Table] is left unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wasmedge_sys::{TableType, Table, WasmValue};
+    /// use wasmedge_types::RefType;
+    ///
+    /// let ty = TableType::create(RefType::FuncRef, 2..=4).expect("fail to create a TableType");
+    /// let mut table = Table::create(&ty).expect("fail to create a Table");
+    ///
+    /// let previous = table.grow(2, WasmValue::from_null_ref(RefType::FuncRef)).expect("fail to grow the table");
+    /// assert_eq!(previous, 2);
+    /// assert_eq!(table.size(), 4);
+    /// ```
+    pub fn grow(&mut self, size: u32, init: WasmValue) -> WasmEdgeResult<u32> {
+        self.check_elem_ty(&init)?;
+
+        let previous = self.size();
+        unsafe { check(ffi::WasmEdge_TableInstanceGrow(self.inner.0, size))? };
+        if !init.is_null_ref() {
+            for idx in previous..previous + size {
+                self.set_data(init, idx)?;
+            }
+        }
+        Ok(previous)
     }
 }
 impl Drop for Table {
@@ -262,7 +318,7 @@ unsafe impl Sync for InnerTableType {}
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{FuncType, Function};
+    use crate::{Executor, FuncType, Function};
     use std::{
         sync::{Arc, Mutex},
         thread,
@@ -311,10 +367,64 @@ mod tests {
         assert_eq!(ty.elem_ty(), RefType::FuncRef);
 
         // grow the capacity of table
-        let result = table.grow(5);
+        let result = table.grow(5, WasmValue::from_null_ref(RefType::FuncRef));
         assert!(result.is_ok());
+        assert_eq!(result.unwrap(), 10);
         // check capacity
         assert_eq!(table.capacity(), 15);
+        assert_eq!(table.size(), 15);
+    }
+
+    #[test]
+    fn test_table_grow_init() {
+        // create a host function
+        let func_ty = FuncType::create(vec![ValType::I32; 2], vec![ValType::I32])
+            .expect("fail to create a FuncType");
+        let host_func = Function::create(&func_ty, Box::new(real_add), 0)
+            .expect("fail to create a host function");
+
+        // create a funcref table with a limit range [2, 5]
+        let ty = TableType::create(RefType::FuncRef, 2..=5).expect("fail to create a TableType");
+        let mut table = Table::create(&ty).expect("fail to create a Table");
+        assert_eq!(table.size(), 2);
+
+        // grow with a function reference
+        let result = table.grow(2, WasmValue::from_func_ref(host_func.as_ref()));
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), 2);
+        assert_eq!(table.size(), 4);
+
+        // the original slots are null, and the new slots hold the init value
+        for idx in 0..2 {
+            let value = table.get_data(idx).expect("fail to get data");
+            assert!(value.is_null_ref());
+        }
+        for idx in 2..4 {
+            let value = table.get_data(idx).expect("fail to get data");
+            assert!(!value.is_null_ref());
+            let func_ref = value.func_ref().expect("not a function reference");
+            let ty = func_ref.ty().expect("fail to get the function type");
+            assert_eq!(ty.params_len(), 2);
+        }
+
+        // mismatched init value
+        let result = table.grow(1, WasmValue::from_null_ref(RefType::ExternRef));
+        assert_eq!(
+            result.unwrap_err(),
+            WasmEdgeError::Table(TableError::UnmatchedRefType)
+        );
+        assert_eq!(table.size(), 4);
+
+        // grow beyond the maximum
+        let result = table.grow(2, WasmValue::from_null_ref(RefType::FuncRef));
+        assert!(result.is_err());
+        assert_eq!(table.size(), 4);
+
+        // grow up to the maximum
+        let result = table.grow(1, WasmValue::from_null_ref(RefType::FuncRef));
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), 4);
+        assert_eq!(table.size(), 5);
     }
 
     #[test]
@@ -371,6 +481,90 @@ mod tests {
         assert_eq!(return_tys, [ValType::I32]);
     }
 
+    #[test]
+    fn test_table_data_swap() {
+        // create two host functions of the same type
+        let func_ty = FuncType::create(vec![ValType::I32; 2], vec![ValType::I32])
+            .expect("fail to create a FuncType");
+        let add = Function::create(
+            &func_ty,
+            Box::new(|input: Vec<WasmValue>| {
+                Ok(vec![WasmValue::from_i32(
+                    input[0].to_i32() + input[1].to_i32(),
+                )])
+            }),
+            0,
+        )
+        .expect("fail to create a host function");
+        let sub = Function::create(
+            &func_ty,
+            Box::new(|input: Vec<WasmValue>| {
+                Ok(vec![WasmValue::from_i32(
+                    input[0].to_i32() - input[1].to_i32(),
+                )])
+            }),
+            0,
+        )
+        .expect("fail to create a host function");
+
+        // create a funcref table
+        let ty = TableType::create(RefType::FuncRef, 4..=4).expect("fail to create a TableType");
+        let mut table = Table::create(&ty).expect("fail to create a Table");
+        let mut executor = Executor::create(None, None).expect("fail to create an Executor");
+
+        // store `add` and call it through the table
+        let result = table.set_data(WasmValue::from_func_ref(add.as_ref()), 1);
+        assert!(result.is_ok());
+        let value = table.get_data(1).expect("fail to get data");
+        let func_ref = value.func_ref().expect("not a function reference");
+        let result = func_ref.call(
+            &mut executor,
+            [WasmValue::from_i32(5), WasmValue::from_i32(3)],
+        );
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap()[0].to_i32(), 8);
+
+        // swap in `sub` and call the new target
+        let result = table.set_data(WasmValue::from_func_ref(sub.as_ref()), 1);
+        assert!(result.is_ok());
+        let value = table.get_data(1).expect("fail to get data");
+        let func_ref = value.func_ref().expect("not a function reference");
+        let result = func_ref.call(
+            &mut executor,
+            [WasmValue::from_i32(5), WasmValue::from_i32(3)],
+        );
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap()[0].to_i32(), 2);
+
+        // out-of-bounds access
+        let result = table.get_data(4);
+        assert_eq!(
+            result.unwrap_err(),
+            WasmEdgeError::Table(TableError::OutOfBounds { index: 4, size: 4 })
+        );
+        let result = table.set_data(WasmValue::from_func_ref(add.as_ref()), 4);
+        assert_eq!(
+            result.unwrap_err(),
+            WasmEdgeError::Table(TableError::OutOfBounds { index: 4, size: 4 })
+        );
+
+        // mismatched element types
+        let result = table.set_data(WasmValue::from_i32(1), 0);
+        assert_eq!(
+            result.unwrap_err(),
+            WasmEdgeError::Table(TableError::UnmatchedRefType)
+        );
+        let mut data = 0_i32;
+        let result = table.set_data(WasmValue::from_extern_ref(&mut data), 0);
+        assert_eq!(
+            result.unwrap_err(),
+            WasmEdgeError::Table(TableError::UnmatchedRefType)
+        );
+        let result = table.get_data(0);
+        assert!(result.is_ok());
+        assert!(result.unwrap().is_null_ref());
+    }
+
     #[test]
     fn test_table_send() {
         // create a TableType instance
@@ -87,6 +87,9 @@
 
 #[macro_use]
 extern crate lazy_static;
+// allows the code generated by the macros in the tests to refer to this crate by name
+#[cfg(test)]
+extern crate self as wasmedge_sys;
 
 use std::{
     collections::HashMap,
@@ -125,7 +128,7 @@ pub mod validator;
 pub mod vm;
 
 #[doc(inline)]
-pub use ast_module::{ExportType, ImportType, Module};
+pub use ast_module::{diff_imports, ExportType, ImportDiff, ImportType, Module};
 #[doc(inline)]
 #[cfg(feature = "aot")]
 pub use compiler::Compiler;
@@ -134,10 +137,22 @@ pub use config::Config;
 #[doc(inline)]
 pub use executor::Executor;
 #[doc(inline)]
+#[cfg(feature = "forensics")]
+pub use instance::function::dump_forensics;
+#[doc(inline)]
+#[cfg(any(test, debug_assertions))]
+pub use instance::function::with_isolated_host_funcs;
+#[doc(inline)]
 pub use instance::{
-    function::{FuncRef, FuncType, Function},
+    function::{
+        decode_invocation, encode_invocation, host_func_capacity, host_stats_by_category,
+        retain_host_funcs, set_capacity_warning_handler, set_host_func_capacity,
+        set_max_host_result_len, set_registration_recorder, swap_host_funcs, total_host_calls,
+        validate_signature, CategoryStats, FuncRef, FuncType, Function, FunctionSet, HostFuncError,
+        HostResult, RegistrationEvent, StreamingPolicy, ThreadPool,
+    },
     global::{Global, GlobalType},
-    memory::{MemType, Memory},
+    memory::{MemType, Memory, MemoryMut, NoPadding, Pod},
     module::{
         AsInstance, ImportInstance, ImportModule, ImportObject, Instance, WasiModule,
         WasmEdgeProcessModule,
@@ -147,30 +162,132 @@ pub use instance::{
 #[doc(inline)]
 pub use loader::Loader;
 #[doc(inline)]
-pub use statistics::Statistics;
+pub use statistics::{statistics_supported, Statistics, StatsDelta, StatsSnapshot};
 #[doc(inline)]
 pub use store::Store;
 #[doc(inline)]
-pub use types::WasmValue;
+pub use types::{FromWasmValues, HostMarshal, IntoWasmValues, UnpackWasmValues, WasmValue};
 #[doc(inline)]
 pub use validator::Validator;
 #[doc(inline)]
-pub use vm::Vm;
+pub use vm::{InterruptHandle, Vm};
+#[doc(inline)]
+pub use wasmedge_macro::host_function;
 
-use wasmedge_types::{error, WasmEdgeResult};
+#[doc(inline)]
+pub use wasmedge_types::error::HostTrap;
+use wasmedge_types::{error, ValType, WasmEdgeResult};
 
 /// Type alias for a boxed native function.
 pub type BoxedFn = Box<dyn Fn(Vec<WasmValue>) -> Result<Vec<WasmValue>, u8> + Send + Sync>;
 
+/// Type alias for a boxed native function mutating its captured state.
+pub type BoxedFnMut = Box<dyn FnMut(Vec<WasmValue>) -> Result<Vec<WasmValue>, u8> + Send>;
+
+/// Type alias for a boxed native function returning a future of its returns.
+pub type BoxedAsyncFn = Box<
+    dyn Fn(
+            Vec<WasmValue>,
+        ) -> std::pin::Pin<
+            Box<dyn std::future::Future<Output = Result<Vec<WasmValue>, u8>> + Send>,
+        > + Send
+        + Sync,
+>;
+
+/// Type alias for a boxed native function producing its returns as an iterator.
+pub type BoxedStreamFn =
+    Box<dyn Fn(Vec<WasmValue>) -> Result<Box<dyn Iterator<Item = WasmValue>>, u8> + Send + Sync>;
+
+/// Type alias for a boxed native function accessing the memory of its caller.
+pub type BoxedMemFn =
+    Box<dyn Fn(&mut MemoryMut, Vec<WasmValue>) -> Result<Vec<WasmValue>, u8> + Send + Sync>;
+
+/// Type alias for a boxed function computing the cost of a host function call from its arguments.
+pub type BoxedCostFn = Box<dyn Fn(&[WasmValue]) -> u64 + Send + Sync>;
+
+/// Type alias for a C-compatible host function following the ABI of WasmEdge's host-function binding.
+pub type RawHostFn = unsafe extern "C" fn(
+    *mut std::os::raw::c_void,
+    *mut std::os::raw::c_void,
+    *mut ffi::WasmEdge_MemoryInstanceContext,
+    *const ffi::WasmEdge_Value,
+    u32,
+    *mut ffi::WasmEdge_Value,
+    u32,
+) -> ffi::WasmEdge_Result;
+
+/// Type alias for a boxed function recording the registration events of host functions.
+pub type BoxedRecorder = Box<dyn Fn(&RegistrationEvent) + Send + Sync>;
+
+/// Type alias for a boxed function notified with the length and capacity of the host function registry.
+pub type BoxedCapacityHandler = Box<dyn Fn(usize, usize) + Send + Sync>;
+
+/// Type alias for a boxed function rewriting the arguments of a host function call.
+pub type BoxedArgFilter = Box<dyn Fn(&mut Vec<WasmValue>) + Send + Sync>;
+
+/// The types of the arguments and returns of a registered host function.
+type HostFuncSignature = (Vec<ValType>, Vec<ValType>);
+
+/// The capacity and the arguments of the last calls of a registered host function.
+type ForensicsRing = (usize, std::collections::VecDeque<Vec<WasmValue>>);
+
+/// The cost function of a registered host function, and the total cost charged by it.
+type HostCostFn = (Arc<BoxedCostFn>, u64);
+
+/// The statistics of the calls of a registered host function.
+#[derive(Debug, Default)]
+pub(crate) struct HostFuncStats {
+    // The static cost of each call.
+    static_cost: u64,
+    // The number of the calls.
+    calls: u64,
+    // The total cost of the calls, including the costs computed by the cost functions.
+    cost: u64,
+}
+
+/// The registry of the host functions, together with the side tables keyed by the keys of the host functions, and the hooks observing the registry.
+#[derive(Default)]
+pub(crate) struct HostRegistry {
+    // The closures of the host functions.
+    funcs: Mutex<HashMap<usize, Arc<BoxedFn>>>,
+    // The signatures of the host functions.
+    types: Mutex<HashMap<usize, HostFuncSignature>>,
+    // The filters rewriting the arguments of the host functions.
+    arg_filters: Mutex<HashMap<usize, Arc<BoxedArgFilter>>>,
+    // The names attached to the host functions.
+    names: Mutex<HashMap<usize, String>>,
+    // The categories the host functions are put into.
+    categories: Mutex<HashMap<usize, String>>,
+    // The statistics of the calls of the host functions.
+    stats: Mutex<HashMap<usize, HostFuncStats>>,
+    // The signatures of the host functions last named with each name.
+    signatures_by_name: Mutex<HashMap<String, HostFuncSignature>>,
+    // The arguments of the last calls of the host functions keeping them.
+    forensics: Mutex<HashMap<usize, ForensicsRing>>,
+    // The cost functions of the host functions.
+    cost_fns: Mutex<HashMap<usize, HostCostFn>>,
+    // The recorder of the registration events.
+    recorder: Mutex<Option<BoxedRecorder>>,
+    // The handler invoked when the load factor of the registry crosses its threshold.
+    capacity_warning_handler: Mutex<Option<(f64, BoxedCapacityHandler)>>,
+}
+impl HostRegistry {
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        Self {
+            funcs: Mutex::new(HashMap::with_capacity(capacity)),
+            ..Default::default()
+        }
+    }
+}
+
 lazy_static! {
-    static ref HOST_FUNCS: Arc<Mutex<HashMap<usize, BoxedFn>>> =
-        Arc::new(Mutex::new(HashMap::with_capacity(
-            std::env::var("MAX_HOST_FUNC_LENGTH")
-                .map(|s| s
-                    .parse::<usize>()
-                    .expect("MAX_HOST_FUNC_LENGTH should be a positive integer."))
-                .unwrap_or(500)
-        )));
+    static ref HOST_REGISTRY: Arc<HostRegistry> = Arc::new(HostRegistry::with_capacity(
+        std::env::var("MAX_HOST_FUNC_LENGTH")
+            .map(|s| s
+                .parse::<usize>()
+                .expect("MAX_HOST_FUNC_LENGTH should be a positive integer."))
+            .unwrap_or(500)
+    ));
 }
 
 /// The object that is used to perform a [host function](crate::Function) is required to implement this trait.
@@ -185,7 +302,7 @@ pub trait Engine {
     ///
     /// # Erros
     ///
-    /// If fail to run the host function, then an error is returned.
+    /// If fail to run the host function, then an error is returned. If the function traps, then a [CoreError::Execution](crate::error::CoreError::Execution) error is returned, of which the `kind` tells the kind of the trap, for example, `DivideByZero`, and the `trace` holds the frames of the trapped call. Notice that WasmEdge provides no API to retrieve the frames for now, so the `trace` is always empty.
     fn run_func(
         &mut self,
         func: &Function,
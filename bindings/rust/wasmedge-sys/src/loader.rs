@@ -1,13 +1,13 @@
 //! Defines WasmEdge Loader struct.
 
 use crate::{
-    ast_module::{InnerModule, Module},
+    ast_module::{is_aot_source, Module},
     error::WasmEdgeError,
     ffi, utils,
     utils::check,
     Config, WasmEdgeResult,
 };
-use std::path::Path;
+use std::{io::BufReader, path::Path};
 
 /// [Loader](crate::Loader) is used to load WASM modules from the given WASM files or buffers.
 #[derive(Debug)]
@@ -73,9 +73,18 @@ impl Loader {
 
         match mod_ctx.is_null() {
             true => Err(WasmEdgeError::ModuleCreate),
-            false => Ok(Module {
-                inner: InnerModule(mod_ctx),
-            }),
+            false => {
+                // the module is dropped if the source fails to be read
+                let mut module = Module::from_raw(mod_ctx, &[]);
+                let io_error = |e: std::io::Error| {
+                    WasmEdgeError::Io(format!("{}: {}", file.as_ref().display(), e))
+                };
+                // read only the size and the section headers, which is cheap even for large AOT artifacts
+                let source = std::fs::File::open(file.as_ref()).map_err(io_error)?;
+                module.bytes_len = source.metadata().map_err(io_error)?.len() as usize;
+                module.aot = is_aot_source(&mut BufReader::new(source));
+                Ok(module)
+            }
         }
     }
 
@@ -128,9 +137,7 @@ impl Loader {
 
         match mod_ctx.is_null() {
             true => Err(WasmEdgeError::ModuleCreate),
-            false => Ok(Module {
-                inner: InnerModule(mod_ctx),
-            }),
+            false => Ok(Module::from_raw(mod_ctx, bytes.as_ref())),
         }
     }
 }
@@ -1,14 +1,53 @@
 //! Defines WasmEdge Statistics struct.
 
-use crate::{error::WasmEdgeError, ffi, WasmEdgeResult};
+use crate::{
+    error::{StatisticsError, WasmEdgeError},
+    ffi,
+    instance::function::host_calls_in_thread,
+    Config, Executor, FuncType, Function, WasmEdgeResult,
+};
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+
+lazy_static! {
+    static ref STATISTICS_SUPPORTED: bool = probe_statistics();
+}
+
+/// Checks if the linked WasmEdge library accounts the costs of the executions in [Statistics].
+///
+/// The `cost` of [host functions](crate::Function) and the cost measuring of [Statistics] only take effect if the library is built with the statistics enabled. The result is detected by running a host function with a nonzero cost under cost measuring once, and is cached afterward.
+pub fn statistics_supported() -> bool {
+    *STATISTICS_SUPPORTED
+}
+
+fn probe_statistics() -> bool {
+    let probe = || -> WasmEdgeResult<bool> {
+        let mut config = Config::create()?;
+        config.measure_cost(true);
+        let mut stat = Statistics::create()?;
+        let mut executor = Executor::create(Some(config), Some(&mut stat))?;
+        let ty = FuncType::create([], [])?;
+        let func = Function::create(&ty, Box::new(|_| Ok(vec![])), 1)?;
+        func.call(&mut executor, [])?;
+        Ok(stat.cost_in_total() > 0)
+    };
+    probe().unwrap_or(false)
+}
 
 #[derive(Debug)]
 /// Struct of WasmEdge Statistics.
 pub struct Statistics {
     pub(crate) inner: InnerStat,
     pub(crate) registered: bool,
+    // the accounting of the costs computed by the host functions, shared with the attached executors
+    pub(crate) host_cost: Arc<HostCost>,
 }
 impl Statistics {
+    /// The number of the entries of a cost table, which is indexed by the 16-bit opcodes of the instructions.
+    pub const COST_TABLE_LEN: usize = u16::MAX as usize + 1;
+
     /// Creates a new [Statistics].
     ///
     /// # Error
@@ -21,6 +60,7 @@ impl Statistics {
             false => Ok(Statistics {
                 inner: InnerStat(ctx),
                 registered: false,
+                host_cost: Arc::new(HostCost::new(ctx)),
             }),
         }
     }
@@ -55,38 +95,97 @@ impl Statistics {
         unsafe { ffi::WasmEdge_StatisticsGetInstrPerSecond(self.inner.0) }
     }
 
-    /// Returns the total cost in execution.
+    /// Returns the total cost in execution, including the costs computed by the host functions created by [create_with_cost_fn](crate::Function::create_with_cost_fn).
     pub fn cost_in_total(&self) -> u64 {
-        unsafe { ffi::WasmEdge_StatisticsGetTotalCost(self.inner.0) }
+        self.host_cost.total()
     }
 
     /// Sets the cost of instructions.
     ///
+    /// The cost table is indexed by the opcodes of the instructions, for example, the cost of `i32.add` is at the index `0x6A`, and the cost of the prefixed `i32x4.add` is at the index `0xFDAE`. By default, the cost of every instruction is 1.
+    ///
     /// # Arguments
     ///
-    /// * `cost_table` - The slice of cost table.
-    pub fn set_cost_table(&mut self, cost_table: impl AsRef<[u64]>) {
+    /// * `cost_table` - The slice of cost table, which covers all the [COST_TABLE_LEN](crate::Statistics::COST_TABLE_LEN) opcodes.
+    ///
+    /// # Error
+    ///
+    /// If the cost table has less than [COST_TABLE_LEN](crate::Statistics::COST_TABLE_LEN) entries, then a `StatisticsError::CostTableTooShort` error is returned, and the cost table in use is left unchanged.
+    pub fn set_cost_table(&mut self, cost_table: impl AsRef<[u64]>) -> WasmEdgeResult<()> {
+        let cost_table = cost_table.as_ref();
+        if cost_table.len() < Self::COST_TABLE_LEN {
+            return Err(WasmEdgeError::Statistics(
+                StatisticsError::CostTableTooShort {
+                    len: cost_table.len(),
+                    required: Self::COST_TABLE_LEN,
+                },
+            ));
+        }
+
         unsafe {
             ffi::WasmEdge_StatisticsSetCostTable(
                 self.inner.0,
-                cost_table.as_ref().as_ptr() as *mut _,
-                cost_table.as_ref().len() as u32,
+                cost_table.as_ptr() as *mut _,
+                cost_table.len() as u32,
             )
         }
+        Ok(())
     }
 
     /// Sets the cost limit in execution.
     ///
+    /// If the total cost exceeds the limit during a run, then the run traps with a `CoreCommonError::CostLimitExceeded` error. The limit is enforced by WasmEdge on the cost of the instructions, and by the host functions created by [create_with_cost_fn](crate::Function::create_with_cost_fn) on the computed costs, which fail with the same error before their bodies run. Notice that the costs of the instructions are measured only if [measure_cost](crate::Config::measure_cost) is enabled in the [configuration](crate::Config) of the [Executor](crate::Executor) or [Vm](crate::Vm).
+    ///
     /// # Arguments
     ///
     /// * `limit` - The cost limit.
     pub fn set_cost_limit(&mut self, limit: u64) {
         unsafe { ffi::WasmEdge_StatisticsSetCostLimit(self.inner.0, limit) }
+        self.host_cost.limit.store(limit, Ordering::Relaxed);
+    }
+
+    /// Returns the cost limit in execution, which is `u64::MAX` if no limit is [set](crate::Statistics::set_cost_limit).
+    ///
+    /// Notice that the limit is tracked on the Rust side rather than read from WasmEdge, so only the limits set through [set_cost_limit](crate::Statistics::set_cost_limit) are seen.
+    pub fn cost_limit(&self) -> u64 {
+        self.host_cost.limit.load(Ordering::Relaxed)
+    }
+
+    /// Returns the cost left before reaching the [cost limit](crate::Statistics::cost_limit), that is, the cost limit minus the [total cost](crate::Statistics::cost_in_total), saturating at zero.
+    pub fn cost_remaining(&self) -> u64 {
+        self.cost_limit().saturating_sub(self.cost_in_total())
     }
 
     /// Clears the data in this statistics.
     pub fn clear(&mut self) {
         unsafe { ffi::WasmEdge_StatisticsClear(self.inner.0) }
+        self.host_cost.charged.store(0, Ordering::Relaxed);
+    }
+
+    /// Takes a snapshot of the current data in this statistics.
+    ///
+    /// Take snapshots before and after any call, and use [delta](crate::StatsSnapshot::delta) to measure the call.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wasmedge_sys::Statistics;
+    ///
+    /// let stat = Statistics::create().expect("fail to create a Statistics");
+    ///
+    /// let before = stat.snapshot();
+    /// // run some functions here
+    /// let after = stat.snapshot();
+    ///
+    /// let delta = before.delta(&after);
+    /// assert_eq!(delta.instr_count, 0);
+    /// ```
+    pub fn snapshot(&self) -> StatsSnapshot {
+        StatsSnapshot {
+            instr_count: self.instr_count(),
+            cost_in_total: self.cost_in_total(),
+            host_calls: host_calls_in_thread(),
+        }
     }
 }
 impl Drop for Statistics {
@@ -97,18 +196,318 @@ impl Drop for Statistics {
     }
 }
 
+/// Defines a snapshot of the data in a [Statistics].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatsSnapshot {
+    /// The instruction count in execution.
+    pub instr_count: u64,
+    /// The total cost in execution.
+    pub cost_in_total: u64,
+    /// The number of the host function calls made in the current thread.
+    pub host_calls: u64,
+}
+impl StatsSnapshot {
+    /// Returns the differences between this snapshot and a later one.
+    ///
+    /// # Argument
+    ///
+    /// * `later` - The snapshot taken after this one.
+    pub fn delta(&self, later: &StatsSnapshot) -> StatsDelta {
+        StatsDelta {
+            instr_count: later.instr_count.saturating_sub(self.instr_count),
+            cost_in_total: later.cost_in_total.saturating_sub(self.cost_in_total),
+            host_calls: later.host_calls.saturating_sub(self.host_calls),
+        }
+    }
+}
+
+/// Defines the differences between two [StatsSnapshot]s.
+///
+/// If the [Statistics] is cleared between the snapshots, then the differences saturate at zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct StatsDelta {
+    /// The instruction count in execution.
+    pub instr_count: u64,
+    /// The total cost in execution.
+    pub cost_in_total: u64,
+    /// The number of the host function calls made in the current thread.
+    pub host_calls: u64,
+}
+
 #[derive(Debug)]
 pub(crate) struct InnerStat(pub(crate) *mut ffi::WasmEdge_StatisticsContext);
+
+// Accounts the costs computed by host functions, which the C API can't add to the total cost, and enforces the cost limit on them.
+#[derive(Debug)]
+pub(crate) struct HostCost {
+    // the statistics the cost of the instructions is read from, which isn't owned
+    stat: InnerStat,
+    // the costs computed by the host functions
+    pub(crate) charged: AtomicU64,
+    // the C API has no getter for the cost limit, so the limit set through the statistics is kept here
+    pub(crate) limit: AtomicU64,
+}
+impl HostCost {
+    pub(crate) fn new(stat: *mut ffi::WasmEdge_StatisticsContext) -> Self {
+        Self {
+            stat: InnerStat(stat),
+            charged: AtomicU64::new(0),
+            limit: AtomicU64::new(u64::MAX),
+        }
+    }
+
+    // Returns the cost of the instructions plus the costs computed by the host functions.
+    pub(crate) fn total(&self) -> u64 {
+        let cost = match self.stat.0.is_null() {
+            true => 0,
+            false => unsafe { ffi::WasmEdge_StatisticsGetTotalCost(self.stat.0) },
+        };
+        cost.saturating_add(self.charged.load(Ordering::Relaxed))
+    }
+
+    // Charges the given cost, and returns whether the total cost is still within the limit.
+    pub(crate) fn charge(&self, cost: u64) -> bool {
+        self.charged.fetch_add(cost, Ordering::Relaxed);
+        self.total() <= self.limit.load(Ordering::Relaxed)
+    }
+}
 unsafe impl Send for InnerStat {}
 unsafe impl Sync for InnerStat {}
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        error::{CoreCommonError, CoreError},
+        Engine, Loader, Store, Validator, WasmValue,
+    };
     use std::{
         sync::{Arc, Mutex},
         thread,
     };
+    use wasmedge_types::{wat2wasm, ValType};
+
+    #[test]
+    fn test_stat_supported() {
+        // the result is cached
+        assert_eq!(statistics_supported(), statistics_supported());
+
+        let result = Config::create();
+        assert!(result.is_ok());
+        let mut config = result.unwrap();
+        config.measure_cost(true);
+        let result = Statistics::create();
+        assert!(result.is_ok());
+        let mut stat = result.unwrap();
+        let result = Executor::create(Some(config), Some(&mut stat));
+        assert!(result.is_ok());
+        let mut executor = result.unwrap();
+
+        // a metered call charges the cost only if the statistics are supported
+        let result = FuncType::create(vec![ValType::I32; 2], vec![ValType::I32]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+        let result = Function::create(
+            &func_ty,
+            Box::new(|input: Vec<WasmValue>| {
+                Ok(vec![WasmValue::from_i32(
+                    input[0].to_i32() + input[1].to_i32(),
+                )])
+            }),
+            7,
+        );
+        assert!(result.is_ok());
+        let host_func = result.unwrap();
+        let result = host_func.call(
+            &mut executor,
+            vec![WasmValue::from_i32(1), WasmValue::from_i32(2)],
+        );
+        assert!(result.is_ok());
+        assert_eq!(
+            stat.cost_in_total(),
+            if statistics_supported() { 7 } else { 0 }
+        );
+    }
+
+    #[test]
+    fn test_stat_snapshot() {
+        // create a Config context
+        let result = Config::create();
+        assert!(result.is_ok());
+        let mut config = result.unwrap();
+        config.count_instructions(true);
+        config.measure_cost(true);
+
+        // create a Statistics context
+        let result = Statistics::create();
+        assert!(result.is_ok());
+        let mut stat = result.unwrap();
+
+        // create an Executor context
+        let result = Executor::create(Some(config), Some(&mut stat));
+        assert!(result.is_ok());
+        let mut executor = result.unwrap();
+
+        // create a host function
+        let result = FuncType::create(vec![ValType::I32; 2], vec![ValType::I32]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+        let result = Function::create(
+            &func_ty,
+            Box::new(|input: Vec<WasmValue>| {
+                Ok(vec![WasmValue::from_i32(
+                    input[0].to_i32() + input[1].to_i32(),
+                )])
+            }),
+            0,
+        );
+        assert!(result.is_ok());
+        let host_func = result.unwrap();
+
+        let before = stat.snapshot();
+        let result = host_func.call(
+            &mut executor,
+            vec![WasmValue::from_i32(1), WasmValue::from_i32(2)],
+        );
+        assert!(result.is_ok());
+        let after = stat.snapshot();
+
+        let delta = before.delta(&after);
+        assert_eq!(delta.host_calls, 1);
+        assert_eq!(before.delta(&before), StatsDelta::default());
+    }
+
+    #[test]
+    fn test_stat_cost_limit() {
+        let result = Config::create();
+        assert!(result.is_ok());
+        let mut config = result.unwrap();
+        config.measure_cost(true);
+        let result = Statistics::create();
+        assert!(result.is_ok());
+        let mut stat = result.unwrap();
+        assert_eq!(stat.cost_limit(), u64::MAX);
+        assert_eq!(stat.cost_remaining(), u64::MAX);
+        stat.set_cost_limit(1000);
+        assert_eq!(stat.cost_limit(), 1000);
+        assert_eq!(stat.cost_remaining(), 1000);
+
+        let result = Store::create();
+        assert!(result.is_ok());
+        let mut store = result.unwrap();
+        let result = Executor::create(Some(config), Some(&mut stat));
+        assert!(result.is_ok());
+        let mut executor = result.unwrap();
+        let sum = create_sum_func(&mut executor, &mut store);
+
+        // a short run within the limit
+        let result = executor.run_func(&sum, [WasmValue::from_i32(10)]);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap()[0].to_i32(), 55);
+        let cost = stat.cost_in_total();
+        assert!(cost > 0);
+        assert!(cost <= 1000);
+        assert_eq!(stat.cost_remaining(), 1000 - cost);
+
+        // a loop-heavy run exceeding the limit traps
+        let result = executor.run_func(&sum, [WasmValue::from_i32(100_000)]);
+        assert_eq!(
+            result.unwrap_err(),
+            WasmEdgeError::Core(CoreError::Common(CoreCommonError::CostLimitExceeded))
+        );
+        assert!(stat.cost_in_total() > cost);
+        assert!(stat.cost_remaining() < 1000 - cost);
+    }
+
+    #[test]
+    fn test_stat_instr_count() {
+        let result = Config::create();
+        assert!(result.is_ok());
+        let mut config = result.unwrap();
+        config.count_instructions(true);
+        config.measure_cost(true);
+        let result = Statistics::create();
+        assert!(result.is_ok());
+        let mut stat = result.unwrap();
+        assert_eq!(stat.instr_count(), 0);
+        assert_eq!(stat.cost_in_total(), 0);
+
+        let result = Store::create();
+        assert!(result.is_ok());
+        let mut store = result.unwrap();
+        let result = Executor::create(Some(config), Some(&mut stat));
+        assert!(result.is_ok());
+        let mut executor = result.unwrap();
+        let sum = create_sum_func(&mut executor, &mut store);
+
+        // the counters accumulate over the runs
+        let result = executor.run_func(&sum, [WasmValue::from_i32(100)]);
+        assert!(result.is_ok());
+        let first = stat.instr_count();
+        assert!(first > 0);
+        assert!(stat.cost_in_total() > 0);
+        let result = executor.run_func(&sum, [WasmValue::from_i32(100)]);
+        assert!(result.is_ok());
+        let second = stat.instr_count();
+        assert!(second > first);
+
+        // clear the counters between the runs
+        stat.clear();
+        assert_eq!(stat.instr_count(), 0);
+        assert_eq!(stat.cost_in_total(), 0);
+        let result = executor.run_func(&sum, [WasmValue::from_i32(100)]);
+        assert!(result.is_ok());
+        assert_eq!(stat.instr_count(), first);
+    }
+
+    #[test]
+    fn test_stat_cost_table() {
+        let result = Config::create();
+        assert!(result.is_ok());
+        let mut config = result.unwrap();
+        config.measure_cost(true);
+        let result = Statistics::create();
+        assert!(result.is_ok());
+        let mut stat = result.unwrap();
+
+        // the tables not covering all the opcodes are rejected
+        let result = stat.set_cost_table([]);
+        assert_eq!(
+            result.unwrap_err(),
+            WasmEdgeError::Statistics(StatisticsError::CostTableTooShort {
+                len: 0,
+                required: Statistics::COST_TABLE_LEN
+            })
+        );
+        let result = stat.set_cost_table(vec![1; 512]);
+        assert!(result.is_err());
+
+        let result = Store::create();
+        assert!(result.is_ok());
+        let mut store = result.unwrap();
+        let result = Executor::create(Some(config), Some(&mut stat));
+        assert!(result.is_ok());
+        let mut executor = result.unwrap();
+        let sum = create_sum_func(&mut executor, &mut store);
+
+        // the uniform costs
+        let mut cost_table = vec![1; Statistics::COST_TABLE_LEN];
+        let result = stat.set_cost_table(&cost_table);
+        assert!(result.is_ok());
+        let result = executor.run_func(&sum, [WasmValue::from_i32(10)]);
+        assert!(result.is_ok());
+        let uniform_cost = stat.cost_in_total();
+        assert!(uniform_cost > 0);
+
+        // make `i32.add` expensive, which runs once per iteration
+        stat.clear();
+        cost_table[0x6A] = 1001;
+        let result = stat.set_cost_table(&cost_table);
+        assert!(result.is_ok());
+        let result = executor.run_func(&sum, [WasmValue::from_i32(10)]);
+        assert!(result.is_ok());
+        assert_eq!(stat.cost_in_total(), uniform_cost + 10 * 1000);
+    }
 
     #[test]
     fn test_stat_send() {
@@ -141,4 +540,40 @@ mod tests {
 
         handle.join().unwrap();
     }
+
+    // instantiates a module summing up 1..=n in a loop, and returns the exported function `sum`
+    fn create_sum_func(executor: &mut Executor, store: &mut Store) -> Function {
+        let result = wat2wasm(
+            br#"
+            (module
+                (func (export "sum") (param $n i32) (result i32) (local $acc i32)
+                    (block
+                        (loop
+                            (br_if 1 (i32.eqz (local.get $n)))
+                            (local.set $acc (i32.add (local.get $acc) (local.get $n)))
+                            (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                            (br 0)))
+                    (local.get $acc))
+            )
+"#,
+        );
+        assert!(result.is_ok());
+        let wasm_bytes = result.unwrap();
+        let result = Loader::create(None);
+        assert!(result.is_ok());
+        let loader = result.unwrap();
+        let result = loader.from_bytes(&wasm_bytes);
+        assert!(result.is_ok());
+        let module = result.unwrap();
+        let result = Validator::create(None);
+        assert!(result.is_ok());
+        let validator = result.unwrap();
+        assert!(validator.validate(&module).is_ok());
+        let result = executor.register_active_module(store, &module);
+        assert!(result.is_ok());
+        let instance = result.unwrap();
+        let result = instance.get_func("sum");
+        assert!(result.is_ok());
+        result.unwrap()
+    }
 }
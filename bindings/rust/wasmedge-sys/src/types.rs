@@ -1,6 +1,11 @@
 //! Defines the WebAssembly primitive types.
 
-use crate::{ffi, instance::function::InnerFuncRef, FuncRef};
+use crate::{
+    error::{self, WasmEdgeError},
+    ffi,
+    instance::function::InnerFuncRef,
+    FuncRef, WasmEdgeResult,
+};
 use core::ffi::c_void;
 use std::{ffi::CString, str::FromStr};
 use wasmedge_types::{RefType, ValType};
@@ -104,15 +109,58 @@ pub struct WasmValue {
 }
 impl WasmValue {
     /// Returns the raw `WasmEdge_Value`.
+    ///
+    /// The raw value can be passed to the WasmEdge C API directly, and converted back by [from_raw](crate::WasmValue::from_raw).
     pub fn as_raw(&self) -> ffi::WasmEdge_Value {
         self.ctx
     }
 
+    /// Creates a [WasmValue] from a raw `WasmEdge_Value`.
+    ///
+    /// The conversion only reinterprets the raw value by its type tag, so it is always safe to perform. However, if the raw value is a `FuncRef` or an `ExternRef`, then the reference it carries is not checked. Reading the reference back by [func_ref](crate::WasmValue::func_ref) or [extern_ref](crate::WasmValue::extern_ref) is only valid if the referred function instance or external object is still alive, and, for an `ExternRef`, is of the requested type.
+    ///
+    /// # Argument
+    ///
+    /// * `raw` - The raw `WasmEdge_Value`.
+    ///
+    /// # Panics
+    ///
+    /// If the type tag of the raw value is unknown, then this function panics.
+    pub fn from_raw(raw: ffi::WasmEdge_Value) -> Self {
+        raw.into()
+    }
+
     /// Returns the type of a [WasmValue].
     pub fn ty(&self) -> ValType {
         self.ty
     }
 
+    /// Creates a [WasmValue] of the given type from raw bits.
+    ///
+    /// For `I32` and `F32`, the bits must fit in 32 bits. For `V128`, the bits are zero-extended to 128 bits.
+    ///
+    /// # Arguments
+    ///
+    /// * `ty` - The type of the value to create.
+    ///
+    /// * `bits` - The raw bits of the value.
+    ///
+    /// # Error
+    ///
+    /// If the type is a reference type or `None`, or the bits don't fit in the type, then an error is returned.
+    pub fn of(ty: ValType, bits: u64) -> WasmEdgeResult<Self> {
+        match ty {
+            ValType::I32 if bits <= u32::MAX as u64 => Ok(Self::from_i32(bits as u32 as i32)),
+            ValType::I64 => Ok(Self::from_i64(bits as i64)),
+            ValType::F32 if bits <= u32::MAX as u64 => {
+                Ok(Self::from_f32(f32::from_bits(bits as u32)))
+            }
+            ValType::F64 => Ok(Self::from_f64(f64::from_bits(bits))),
+            ValType::V128 => Ok(Self::from_v128(bits as i128)),
+            _ => Err(WasmEdgeError::ValueFromBits(ty)),
+        }
+    }
+
     /// Creates a [WasmValue] from a `i32` value.
     ///
     /// # Argument
@@ -126,10 +174,22 @@ impl WasmValue {
     }
 
     /// Generates a `i32` value from a [WasmValue].
+    ///
+    /// The value is reinterpreted regardless of the type of the [WasmValue]. Prefer [try_to_i32](crate::WasmValue::try_to_i32) unless the type is known to be `I32`.
     pub fn to_i32(&self) -> i32 {
         unsafe { ffi::WasmEdge_ValueGetI32(self.ctx) }
     }
 
+    /// Generates a `i32` value from a [WasmValue] of the type `I32`.
+    ///
+    /// # Error
+    ///
+    /// If the type of the [WasmValue] is not `I32`, then a `TypeError::Mismatch` error is returned.
+    pub fn try_to_i32(&self) -> WasmEdgeResult<i32> {
+        self.check_ty(ValType::I32)?;
+        Ok(self.to_i32())
+    }
+
     /// Creates a [WasmValue] from a `i64` value.
     ///
     /// # Argument
@@ -143,10 +203,22 @@ impl WasmValue {
     }
 
     /// Generates a `i64` value from a [WasmValue].
+    ///
+    /// The value is reinterpreted regardless of the type of the [WasmValue]. Prefer [try_to_i64](crate::WasmValue::try_to_i64) unless the type is known to be `I64`.
     pub fn to_i64(&self) -> i64 {
         unsafe { ffi::WasmEdge_ValueGetI64(self.ctx) }
     }
 
+    /// Generates a `i64` value from a [WasmValue] of the type `I64`.
+    ///
+    /// # Error
+    ///
+    /// If the type of the [WasmValue] is not `I64`, then a `TypeError::Mismatch` error is returned.
+    pub fn try_to_i64(&self) -> WasmEdgeResult<i64> {
+        self.check_ty(ValType::I64)?;
+        Ok(self.to_i64())
+    }
+
     /// Creates a [WasmValue] from a `f32` value.
     ///
     /// # Argument
@@ -160,10 +232,22 @@ impl WasmValue {
     }
 
     /// Generates a `f32` value from a [WasmValue].
+    ///
+    /// The value is reinterpreted regardless of the type of the [WasmValue]. Prefer [try_to_f32](crate::WasmValue::try_to_f32) unless the type is known to be `F32`.
     pub fn to_f32(&self) -> f32 {
         unsafe { ffi::WasmEdge_ValueGetF32(self.ctx) }
     }
 
+    /// Generates a `f32` value from a [WasmValue] of the type `F32`.
+    ///
+    /// # Error
+    ///
+    /// If the type of the [WasmValue] is not `F32`, then a `TypeError::Mismatch` error is returned.
+    pub fn try_to_f32(&self) -> WasmEdgeResult<f32> {
+        self.check_ty(ValType::F32)?;
+        Ok(self.to_f32())
+    }
+
     /// Creates a [WasmValue] from a `f64` value.
     ///
     /// # Argument
@@ -177,10 +261,22 @@ impl WasmValue {
     }
 
     /// Generates a `f64` value from a [WasmValue].
+    ///
+    /// The value is reinterpreted regardless of the type of the [WasmValue]. Prefer [try_to_f64](crate::WasmValue::try_to_f64) unless the type is known to be `F64`.
     pub fn to_f64(&self) -> f64 {
         unsafe { ffi::WasmEdge_ValueGetF64(self.ctx) }
     }
 
+    /// Generates a `f64` value from a [WasmValue] of the type `F64`.
+    ///
+    /// # Error
+    ///
+    /// If the type of the [WasmValue] is not `F64`, then a `TypeError::Mismatch` error is returned.
+    pub fn try_to_f64(&self) -> WasmEdgeResult<f64> {
+        self.check_ty(ValType::F64)?;
+        Ok(self.to_f64())
+    }
+
     /// Creates a [WasmValue] from a `i128` value.
     ///
     /// # Argument
@@ -194,10 +290,38 @@ impl WasmValue {
     }
 
     /// Generates a `v128` value from a [WasmValue].
+    ///
+    /// The value is reinterpreted regardless of the type of the [WasmValue]. Prefer [try_to_v128](crate::WasmValue::try_to_v128) unless the type is known to be `V128`.
     pub fn to_v128(&self) -> i128 {
         unsafe { ffi::WasmEdge_ValueGetV128(self.ctx) }
     }
 
+    /// Generates a `v128` value from a [WasmValue] of the type `V128`.
+    ///
+    /// # Error
+    ///
+    /// If the type of the [WasmValue] is not `V128`, then a `TypeError::Mismatch` error is returned.
+    pub fn try_to_v128(&self) -> WasmEdgeResult<i128> {
+        self.check_ty(ValType::V128)?;
+        Ok(self.to_v128())
+    }
+
+    /// Creates a [WasmValue] from the 16 bytes of a `v128` value.
+    ///
+    /// The bytes are in the little-endian order, i.e., `bytes[0]` is the lowest byte of the first lane, which is consistent with the memory layout of `v128` in WebAssembly.
+    ///
+    /// # Argument
+    ///
+    /// * `bytes` - The source bytes.
+    pub fn from_v128_bytes(bytes: [u8; 16]) -> Self {
+        Self::from_v128(i128::from_le_bytes(bytes))
+    }
+
+    /// Generates the 16 bytes of a `v128` value from a [WasmValue] in the little-endian order.
+    pub fn to_v128_bytes(&self) -> [u8; 16] {
+        self.to_v128().to_le_bytes()
+    }
+
     /// Creates a [WasmValue] from a [RefType](wasmedge_types::RefType) value.
     ///
     /// # Argument
@@ -218,6 +342,13 @@ impl WasmValue {
         unsafe { ffi::WasmEdge_ValueIsNullRef(self.ctx) }
     }
 
+    /// Checks if a [WasmValue] is of a reference type, that is, `FuncRef` or `ExternRef`.
+    ///
+    /// Null references are reference values too.
+    pub fn is_reference(&self) -> bool {
+        matches!(self.ty, ValType::FuncRef | ValType::ExternRef)
+    }
+
     /// Creates a [WasmValue] from a [FuncRef](crate::FuncRef).
     ///
     /// Notice that the [WasmValue]s generated from [FuncRef](crate::FuncRef)s are only meaningful when the `bulk_memory_operations` or `reference_types` option is enabled in the [Config](crate::Config).
@@ -232,9 +363,9 @@ impl WasmValue {
         }
     }
 
-    /// Returns the FuncRef(crate::FuncRef).
+    /// Returns the [FuncRef](crate::FuncRef) this [WasmValue] holds.
     ///
-    /// If the [WasmValue] is a `NullRef`, then `None` is returned.
+    /// If the [WasmValue] is a `NullRef`, then `None` is returned, so the null slots of a `funcref` table can be skipped safely while iterating over the table.
     pub fn func_ref(&self) -> Option<FuncRef> {
         unsafe {
             match ffi::WasmEdge_ValueIsNullRef(self.ctx) {
@@ -243,6 +374,7 @@ impl WasmValue {
                     let ctx = ffi::WasmEdge_ValueGetFuncRef(self.ctx);
                     Some(FuncRef {
                         inner: InnerFuncRef(ctx),
+                        cost: 0,
                     })
                 }
             }
@@ -254,6 +386,8 @@ impl WasmValue {
     /// The [WasmValue]s generated by this function are only meaningful when the `reference_types` option is enabled in
     /// the [Config](crate::Config).
     ///
+    /// Only the type-erased address of the external object is stored, so the [WasmValue] neither owns the object nor tracks its lifetime. The caller must keep the object alive and in place as long as the [WasmValue], or any copy of it passed into WebAssembly, may be read back by [extern_ref](crate::WasmValue::extern_ref) or [extern_ref_mut](crate::WasmValue::extern_ref_mut).
+    ///
     /// # Argument
     ///
     /// * `extern_obj` - The reference to an external object.
@@ -280,6 +414,85 @@ impl WasmValue {
             }
         }
     }
+
+    /// Returns the mutable reference to an external object.
+    ///
+    /// If the [WasmValue] is a `NullRef`, then `None` is returned.
+    ///
+    /// # Safety
+    ///
+    /// The [WasmValue] must be created by [from_extern_ref](crate::WasmValue::from_extern_ref) with an object of the type `T`, which is still alive. Since [WasmValue] is `Copy`, the caller must also make sure that no other reference to the object is in use while the returned one is alive.
+    pub unsafe fn extern_ref_mut<T>(&mut self) -> Option<&mut T> {
+        match ffi::WasmEdge_ValueIsNullRef(self.ctx) {
+            true => None,
+            false => {
+                let ptr = ffi::WasmEdge_ValueGetExternRef(self.ctx);
+                Some(&mut *(ptr as *mut T))
+            }
+        }
+    }
+}
+impl WasmValue {
+    /// Checks if the [WasmValue] is of the expected type.
+    fn check_ty(&self, expected: ValType) -> WasmEdgeResult<()> {
+        match self.ty == expected {
+            true => Ok(()),
+            false => Err(WasmEdgeError::Type(error::TypeError::Mismatch {
+                expected,
+                found: self.ty,
+            })),
+        }
+    }
+
+    /// Returns the address a reference value holds, or `0` for a null reference or a value of a non-reference type.
+    fn ref_addr(&self) -> usize {
+        if !self.is_reference() || self.is_null_ref() {
+            return 0;
+        }
+        unsafe {
+            match self.ty {
+                ValType::FuncRef => ffi::WasmEdge_ValueGetFuncRef(self.ctx) as usize,
+                _ => ffi::WasmEdge_ValueGetExternRef(self.ctx) as usize,
+            }
+        }
+    }
+}
+/// Compares the types and the payloads of two [WasmValue]s.
+///
+/// The floating-point values are compared bit-exactly, so a NaN equals a NaN of the same bit pattern, and `0.0` doesn't equal `-0.0`. The reference values are compared by the identity of the referred function instances or external objects.
+impl PartialEq for WasmValue {
+    fn eq(&self, other: &Self) -> bool {
+        self.ty == other.ty
+            && match self.ty {
+                ValType::I32 => self.to_i32() == other.to_i32(),
+                ValType::I64 => self.to_i64() == other.to_i64(),
+                ValType::F32 => self.to_f32().to_bits() == other.to_f32().to_bits(),
+                ValType::F64 => self.to_f64().to_bits() == other.to_f64().to_bits(),
+                ValType::V128 => self.to_v128() == other.to_v128(),
+                ValType::FuncRef | ValType::ExternRef => self.ref_addr() == other.ref_addr(),
+                ValType::None => true,
+            }
+    }
+}
+impl Eq for WasmValue {}
+/// Renders a [WasmValue] with its type, for example, `I32(3)`, `F64(2.5)`, or `V128(0x000000000000000000000000000000ff)`. The reference values are rendered as the addresses they refer to, for example, `funcref(null)` or `externref(0x7f2a4c001230)`.
+impl std::fmt::Display for WasmValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self.ty {
+            ValType::I32 => return write!(f, "I32({})", self.to_i32()),
+            ValType::I64 => return write!(f, "I64({})", self.to_i64()),
+            ValType::F32 => return write!(f, "F32({:?})", self.to_f32()),
+            ValType::F64 => return write!(f, "F64({:?})", self.to_f64()),
+            ValType::V128 => return write!(f, "V128({:#034x})", self.to_v128()),
+            ValType::None => return write!(f, "None"),
+            ValType::FuncRef => "funcref",
+            ValType::ExternRef => "externref",
+        };
+        match self.ref_addr() {
+            0 => write!(f, "{}(null)", name),
+            addr => write!(f, "{}({:#x})", name, addr),
+        }
+    }
 }
 impl From<ffi::WasmEdge_Value> for WasmValue {
     fn from(raw_val: ffi::WasmEdge_Value) -> Self {
@@ -321,15 +534,475 @@ impl From<ffi::WasmEdge_Value> for WasmValue {
     }
 }
 
+/// Converts a domain type into a flat list of [WasmValue]s and back.
+///
+/// The trait is used by [Function::create_marshaled](crate::Function::create_marshaled) to derive the signature of a host function from its request and response types, and to marshal the arguments and returns. The primitive types `i32`, `i64`, `f32`, `f64` and `()` implement this trait. For a struct, implement the trait by marshaling its fields in order.
+///
+/// # Example
+///
+/// ```rust
+/// use wasmedge_sys::{HostMarshal, WasmValue};
+/// use wasmedge_types::ValType;
+///
+/// struct Point {
+///     x: i32,
+///     y: f64,
+/// }
+///
+/// impl HostMarshal for Point {
+///     fn val_types() -> Vec<ValType> {
+///         vec![ValType::I32, ValType::F64]
+///     }
+///
+///     fn to_values(&self) -> Vec<WasmValue> {
+///         vec![WasmValue::from_i32(self.x), WasmValue::from_f64(self.y)]
+///     }
+///
+///     fn from_values(values: &[WasmValue]) -> Option<Self> {
+///         match values {
+///             [x, y] if x.ty() == ValType::I32 && y.ty() == ValType::F64 => Some(Point {
+///                 x: x.to_i32(),
+///                 y: y.to_f64(),
+///             }),
+///             _ => None,
+///         }
+///     }
+/// }
+/// ```
+pub trait HostMarshal: Sized {
+    /// Returns the types of the values this type is marshaled into.
+    fn val_types() -> Vec<ValType>;
+
+    /// Marshals this value into a flat list of [WasmValue]s.
+    fn to_values(&self) -> Vec<WasmValue>;
+
+    /// Unmarshals a value from a flat list of [WasmValue]s.
+    ///
+    /// If the number or the types of the given values don't match, then `None` is returned.
+    fn from_values(values: &[WasmValue]) -> Option<Self>;
+}
+impl HostMarshal for () {
+    fn val_types() -> Vec<ValType> {
+        vec![]
+    }
+
+    fn to_values(&self) -> Vec<WasmValue> {
+        vec![]
+    }
+
+    fn from_values(values: &[WasmValue]) -> Option<Self> {
+        match values.is_empty() {
+            true => Some(()),
+            false => None,
+        }
+    }
+}
+macro_rules! impl_host_marshal {
+    ($ty:ty, $val_ty:ident, $from:ident, $to:ident) => {
+        impl HostMarshal for $ty {
+            fn val_types() -> Vec<ValType> {
+                vec![ValType::$val_ty]
+            }
+
+            fn to_values(&self) -> Vec<WasmValue> {
+                vec![WasmValue::$from(*self)]
+            }
+
+            fn from_values(values: &[WasmValue]) -> Option<Self> {
+                match values {
+                    [v] if v.ty() == ValType::$val_ty => Some(v.$to()),
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+impl_host_marshal!(i32, I32, from_i32, to_i32);
+impl_host_marshal!(i64, I64, from_i64, to_i64);
+impl_host_marshal!(f32, F32, from_f32, to_f32);
+impl_host_marshal!(f64, F64, from_f64, to_f64);
+
+/// Converts a flat list of [WasmValue]s into a typed value, checking the number and the types of the values.
+///
+/// The trait is implemented for every [HostMarshal] type, and for the tuples of up to 8 [HostMarshal] types, of which each element takes as many values as its [val_types](crate::HostMarshal::val_types). Use [unpack](crate::UnpackWasmValues::unpack) to convert the arguments of a host function, or the returns of [call](crate::Function::call).
+pub trait FromWasmValues: Sized {
+    /// Converts the given values into a typed value.
+    ///
+    /// # Error
+    ///
+    /// If the number or the types of the values don't match, then a `FuncError::ValueMismatch` error naming the expected and actual types at the offending index is returned.
+    fn from_wasm_values(values: &[WasmValue]) -> WasmEdgeResult<Self>;
+}
+
+/// Converts a typed value into a flat list of [WasmValue]s.
+///
+/// The trait is implemented for the same types as [FromWasmValues], and can be used to produce the returns of a host function.
+pub trait IntoWasmValues {
+    /// Converts this value into a flat list of [WasmValue]s.
+    fn into_wasm_values(self) -> Vec<WasmValue>;
+}
+
+// Checks the number and the types of the values against the expected types, and reports the offending index on mismatch.
+fn check_values(expected: &[ValType], values: &[WasmValue]) -> WasmEdgeResult<()> {
+    if let Some((index, (expected, value))) = expected
+        .iter()
+        .zip(values)
+        .enumerate()
+        .find(|(_, (expected, value))| value.ty() != **expected)
+    {
+        return Err(WasmEdgeError::Func(error::FuncError::ValueMismatch(
+            format!(
+                "value {}: expected {:?}, found {:?}",
+                index,
+                expected,
+                value.ty()
+            ),
+        )));
+    }
+    match expected.len() == values.len() {
+        true => Ok(()),
+        false => Err(WasmEdgeError::Func(error::FuncError::ValueMismatch(
+            format!("expected {} values, found {}", expected.len(), values.len()),
+        ))),
+    }
+}
+
+// Unmarshals a value from the values which have been checked against its types.
+fn unmarshal<T: HostMarshal>(values: &[WasmValue]) -> WasmEdgeResult<T> {
+    T::from_values(values).ok_or_else(|| {
+        WasmEdgeError::Func(error::FuncError::ValueMismatch(format!(
+            "fail to unmarshal {}",
+            std::any::type_name::<T>()
+        )))
+    })
+}
+
+impl<T: HostMarshal> FromWasmValues for T {
+    fn from_wasm_values(values: &[WasmValue]) -> WasmEdgeResult<Self> {
+        check_values(&T::val_types(), values)?;
+        unmarshal(values)
+    }
+}
+impl<T: HostMarshal> IntoWasmValues for T {
+    fn into_wasm_values(self) -> Vec<WasmValue> {
+        self.to_values()
+    }
+}
+
+macro_rules! impl_wasm_values_for_tuple {
+    ($($name:ident),+) => {
+        impl<$($name: HostMarshal),+> FromWasmValues for ($($name,)+) {
+            // the values left after the last element are never read
+            #[allow(unused_assignments)]
+            fn from_wasm_values(values: &[WasmValue]) -> WasmEdgeResult<Self> {
+                check_values(&[$($name::val_types()),+].concat(), values)?;
+                let mut rest = values;
+                Ok(($({
+                    let (head, tail) = rest.split_at($name::val_types().len());
+                    rest = tail;
+                    unmarshal::<$name>(head)?
+                },)+))
+            }
+        }
+        impl<$($name: HostMarshal),+> IntoWasmValues for ($($name,)+) {
+            #[allow(non_snake_case)]
+            fn into_wasm_values(self) -> Vec<WasmValue> {
+                let ($($name,)+) = self;
+                let mut values = vec![];
+                $(values.extend($name.to_values());)+
+                values
+            }
+        }
+    };
+}
+impl_wasm_values_for_tuple!(A);
+impl_wasm_values_for_tuple!(A, B);
+impl_wasm_values_for_tuple!(A, B, C);
+impl_wasm_values_for_tuple!(A, B, C, D);
+impl_wasm_values_for_tuple!(A, B, C, D, E);
+impl_wasm_values_for_tuple!(A, B, C, D, E, F);
+impl_wasm_values_for_tuple!(A, B, C, D, E, F, G);
+impl_wasm_values_for_tuple!(A, B, C, D, E, F, G, H);
+
+/// Unpacks a list of [WasmValue]s into a typed value.
+///
+/// # Example
+///
+/// ```rust
+/// use wasmedge_sys::{UnpackWasmValues, WasmValue};
+///
+/// let args = vec![WasmValue::from_i32(1), WasmValue::from_i64(2)];
+/// let (a, b): (i32, i64) = args.unpack().expect("fail to unpack the arguments");
+/// assert_eq!((a, b), (1, 2));
+/// ```
+pub trait UnpackWasmValues {
+    /// Unpacks the values into a typed value.
+    ///
+    /// # Error
+    ///
+    /// If the number or the types of the values don't match, then an error is returned.
+    fn unpack<T: FromWasmValues>(&self) -> WasmEdgeResult<T>;
+}
+impl UnpackWasmValues for [WasmValue] {
+    fn unpack<T: FromWasmValues>(&self) -> WasmEdgeResult<T> {
+        T::from_wasm_values(self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{Table, TableType};
+    use crate::{
+        Config, Executor, FuncType, Function, ImportInstance, ImportModule, ImportObject, Table,
+        TableType, Vm,
+    };
     use std::{
         sync::{Arc, Mutex},
         thread,
     };
-    use wasmedge_types::RefType;
+    use wasmedge_types::{wat2wasm, RefType};
+
+    #[test]
+    fn test_types_value_v128_bytes() {
+        let pattern: [u8; 16] = [
+            0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd,
+            0xee, 0xff,
+        ];
+        let val = WasmValue::from_v128_bytes(pattern);
+        assert_eq!(val.ty(), ValType::V128);
+        assert_eq!(val.to_v128_bytes(), pattern);
+        assert_eq!(val.to_v128(), i128::from_le_bytes(pattern));
+
+        // the value survives the conversion to and from the raw value
+        let val = WasmValue::from_raw(val.as_raw());
+        assert_eq!(val.ty(), ValType::V128);
+        assert_eq!(val.to_v128_bytes(), pattern);
+
+        // the lanes returned by a SIMD function are laid out in the little-endian order
+        let result = wat2wasm(
+            br#"
+            (module
+                (func (export "splat") (param i32) (result v128)
+                    (i32x4.splat (local.get 0)))
+            )
+"#,
+        );
+        assert!(result.is_ok());
+        let wasm_bytes = result.unwrap();
+        let result = Vm::create(None, None);
+        assert!(result.is_ok());
+        let mut vm = result.unwrap();
+        let result =
+            vm.run_wasm_from_bytes(&wasm_bytes, "splat", [WasmValue::from_i32(0x01020304)]);
+        assert!(result.is_ok());
+        let returns = result.unwrap();
+        assert_eq!(returns[0].ty(), ValType::V128);
+        assert_eq!(
+            returns[0].to_v128_bytes(),
+            [4, 3, 2, 1].repeat(4).as_slice()
+        );
+    }
+
+    #[test]
+    fn test_types_value_extern_ref_host_func() {
+        // the host function appends a suffix to the String referred by its argument
+        fn append(inputs: Vec<WasmValue>) -> Result<Vec<WasmValue>, u8> {
+            let mut handle = inputs[0];
+            match unsafe { handle.extern_ref_mut::<String>() } {
+                Some(s) => {
+                    s.push_str(", wasm");
+                    Ok(vec![])
+                }
+                None => Err(1),
+            }
+        }
+
+        let result = Config::create();
+        assert!(result.is_ok());
+        let mut config = result.unwrap();
+        config.reference_types(true);
+        let result = Vm::create(Some(config), None);
+        assert!(result.is_ok());
+        let mut vm = result.unwrap();
+
+        let result = ImportModule::create("env");
+        assert!(result.is_ok());
+        let mut import = result.unwrap();
+        let result = FuncType::create([ValType::ExternRef], []);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+        let result = Function::create(&func_ty, Box::new(append), 0);
+        assert!(result.is_ok());
+        import.add_func("append", result.unwrap());
+        let result = vm.register_wasm_from_import(ImportObject::Import(import));
+        assert!(result.is_ok());
+
+        // the wasm function passes the handle back to the host function
+        let result = wat2wasm(
+            br#"
+            (module
+                (import "env" "append" (func $append (param externref)))
+                (func (export "run") (param externref)
+                    (call $append (local.get 0)))
+            )
+"#,
+        );
+        assert!(result.is_ok());
+        let wasm_bytes = result.unwrap();
+
+        let mut data = String::from("hello");
+        assert!(vm.load_wasm_from_bytes(&wasm_bytes).is_ok());
+        assert!(vm.validate().is_ok());
+        assert!(vm.instantiate().is_ok());
+        let result = vm.run_function("run", [WasmValue::from_extern_ref(&mut data)]);
+        assert!(result.is_ok());
+        assert_eq!(data, "hello, wasm");
+
+        // the null reference is rejected by the host function
+        let result = vm.run_function("run", [WasmValue::from_null_ref(RefType::ExternRef)]);
+        assert!(result.is_err());
+        assert_eq!(data, "hello, wasm");
+    }
+
+    #[test]
+    fn test_types_value_func_ref_table() {
+        let result = FuncType::create([ValType::I32], [ValType::I32]);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+        let result = Function::create(
+            &func_ty,
+            Box::new(|input: Vec<WasmValue>| Ok(vec![WasmValue::from_i32(input[0].to_i32() * 2)])),
+            0,
+        );
+        assert!(result.is_ok());
+        let double = result.unwrap();
+
+        // store the function into every other slot of a funcref table
+        let result = TableType::create(RefType::FuncRef, 4..=4);
+        assert!(result.is_ok());
+        let ty = result.unwrap();
+        let result = Table::create(&ty);
+        assert!(result.is_ok());
+        let mut table = result.unwrap();
+        for idx in [0, 2] {
+            let result = table.set_data(WasmValue::from_func_ref(double.as_ref()), idx);
+            assert!(result.is_ok());
+        }
+
+        // the null slots are skipped, and the others are called through the references
+        let result = Executor::create(None, None);
+        assert!(result.is_ok());
+        let mut executor = result.unwrap();
+        let mut returns = vec![];
+        for idx in 0..table.size() {
+            let result = table.get_data(idx);
+            assert!(result.is_ok());
+            let value = result.unwrap();
+            assert_eq!(value.ty(), ValType::FuncRef);
+            match value.func_ref() {
+                Some(func_ref) => {
+                    assert!(!value.is_null_ref());
+                    let result = func_ref.call(&mut executor, [WasmValue::from_i32(idx as i32)]);
+                    assert!(result.is_ok());
+                    returns.push(result.unwrap()[0].to_i32());
+                }
+                None => assert!(value.is_null_ref()),
+            }
+        }
+        assert_eq!(returns, [0, 4]);
+    }
+
+    #[test]
+    fn test_types_value_eq_display() {
+        // numeric values
+        assert_eq!(WasmValue::from_i32(3), WasmValue::from_i32(3));
+        assert_ne!(WasmValue::from_i32(3), WasmValue::from_i32(4));
+        assert_ne!(WasmValue::from_i32(3), WasmValue::from_i64(3));
+        assert_eq!(WasmValue::from_f64(2.5), WasmValue::from_f64(2.5));
+        assert_ne!(WasmValue::from_f32(0.0), WasmValue::from_f32(-0.0));
+        assert_eq!(WasmValue::from_v128(1314), WasmValue::from_v128(1314));
+        assert_eq!(WasmValue::from_i32(3).to_string(), "I32(3)");
+        assert_eq!(WasmValue::from_i64(-3).to_string(), "I64(-3)");
+        assert_eq!(WasmValue::from_f32(1.0).to_string(), "F32(1.0)");
+        assert_eq!(WasmValue::from_f64(2.5).to_string(), "F64(2.5)");
+        assert_eq!(
+            WasmValue::from_v128(0xff).to_string(),
+            "V128(0x000000000000000000000000000000ff)"
+        );
+
+        // NaN values are compared by their bits
+        let nan = f64::from_bits(0x7ff8_0000_0000_0001);
+        assert_eq!(WasmValue::from_f64(nan), WasmValue::from_f64(nan));
+        assert_ne!(WasmValue::from_f64(nan), WasmValue::from_f64(f64::NAN));
+        let nan = f32::from_bits(0x7fc0_0001);
+        assert_eq!(WasmValue::from_f32(nan), WasmValue::from_f32(nan));
+
+        // reference values are compared by identity
+        let mut a = 1_i32;
+        let mut b = 1_i32;
+        let ref_a = WasmValue::from_extern_ref(&mut a);
+        let ref_b = WasmValue::from_extern_ref(&mut b);
+        assert_eq!(ref_a, WasmValue::from_raw(ref_a.as_raw()));
+        assert_ne!(ref_a, ref_b);
+        assert_eq!(
+            ref_a.to_string(),
+            format!("externref({:#x})", &a as *const i32 as usize)
+        );
+        let null_func_ref = WasmValue::from_null_ref(RefType::FuncRef);
+        let null_extern_ref = WasmValue::from_null_ref(RefType::ExternRef);
+        assert_eq!(null_func_ref, WasmValue::from_null_ref(RefType::FuncRef));
+        assert_ne!(null_func_ref, null_extern_ref);
+        assert_ne!(ref_a, null_extern_ref);
+        assert_eq!(null_func_ref.to_string(), "funcref(null)");
+        assert_eq!(null_extern_ref.to_string(), "externref(null)");
+
+        let result = FuncType::create([], []);
+        assert!(result.is_ok());
+        let func_ty = result.unwrap();
+        let result = Function::create(&func_ty, Box::new(|_| Ok(vec![])), 0);
+        assert!(result.is_ok());
+        let func = result.unwrap();
+        let func_ref = WasmValue::from_func_ref(func.as_ref());
+        assert_eq!(func_ref, WasmValue::from_func_ref(func.as_ref()));
+        assert_ne!(func_ref, null_func_ref);
+        assert!(func_ref.to_string().starts_with("funcref(0x"));
+    }
+
+    #[test]
+    fn test_types_value_checked() {
+        assert_eq!(WasmValue::from_i32(3).try_to_i32(), Ok(3));
+        assert_eq!(WasmValue::from_i64(-3).try_to_i64(), Ok(-3));
+        assert_eq!(WasmValue::from_f32(1.5).try_to_f32(), Ok(1.5));
+        assert_eq!(WasmValue::from_f64(2.5).try_to_f64(), Ok(2.5));
+        assert_eq!(WasmValue::from_v128(1314).try_to_v128(), Ok(1314));
+
+        // the mismatched types are reported
+        let result = WasmValue::from_f64(2.5).try_to_i32();
+        assert!(result.is_err());
+        let err = result.unwrap_err();
+        assert_eq!(
+            err,
+            WasmEdgeError::Type(error::TypeError::Mismatch {
+                expected: ValType::I32,
+                found: ValType::F64,
+            })
+        );
+        let message = err.to_string();
+        assert!(message.contains("I32"));
+        assert!(message.contains("F64"));
+
+        let result = WasmValue::from_i64(1).try_to_i32();
+        assert_eq!(
+            result.unwrap_err(),
+            WasmEdgeError::Type(error::TypeError::Mismatch {
+                expected: ValType::I32,
+                found: ValType::I64,
+            })
+        );
+        assert!(WasmValue::from_null_ref(RefType::FuncRef)
+            .try_to_i64()
+            .is_err());
+    }
 
     #[test]
     fn test_types_value() {
@@ -381,6 +1054,163 @@ mod tests {
         assert_eq!(val1.to_i32(), val2.to_i32());
     }
 
+    #[test]
+    fn test_types_value_of() {
+        let result = WasmValue::of(ValType::I32, 0xffff_ffff);
+        assert!(result.is_ok());
+        let val = result.unwrap();
+        assert_eq!(val.ty(), ValType::I32);
+        assert_eq!(val.to_i32(), -1);
+
+        let result = WasmValue::of(ValType::I64, 1314);
+        assert!(result.is_ok());
+        let val = result.unwrap();
+        assert_eq!(val.ty(), ValType::I64);
+        assert_eq!(val.to_i64(), 1314);
+
+        let result = WasmValue::of(ValType::F32, 13.14f32.to_bits() as u64);
+        assert!(result.is_ok());
+        let val = result.unwrap();
+        assert_eq!(val.ty(), ValType::F32);
+        assert_eq!(val.to_f32(), 13.14);
+
+        let result = WasmValue::of(ValType::F64, 13.14f64.to_bits());
+        assert!(result.is_ok());
+        let val = result.unwrap();
+        assert_eq!(val.ty(), ValType::F64);
+        assert_eq!(val.to_f64(), 13.14);
+
+        let result = WasmValue::of(ValType::V128, 1314);
+        assert!(result.is_ok());
+        let val = result.unwrap();
+        assert_eq!(val.ty(), ValType::V128);
+        assert_eq!(val.to_v128(), 1314);
+
+        // the bits don't fit in 32 bits
+        let result = WasmValue::of(ValType::I32, 1 << 32);
+        assert!(result.is_err());
+        let result = WasmValue::of(ValType::F32, 1 << 32);
+        assert!(result.is_err());
+
+        // reference types are rejected
+        let result = WasmValue::of(ValType::FuncRef, 0);
+        assert_eq!(
+            result.unwrap_err(),
+            WasmEdgeError::ValueFromBits(ValType::FuncRef)
+        );
+        let result = WasmValue::of(ValType::ExternRef, 0);
+        assert_eq!(
+            result.unwrap_err(),
+            WasmEdgeError::ValueFromBits(ValType::ExternRef)
+        );
+    }
+
+    #[test]
+    fn test_types_value_raw() {
+        let val = WasmValue::from_raw(WasmValue::from_i32(1314).as_raw());
+        assert_eq!(val.ty(), ValType::I32);
+        assert_eq!(val.to_i32(), 1314);
+
+        let val = WasmValue::from_raw(WasmValue::from_i64(1314).as_raw());
+        assert_eq!(val.ty(), ValType::I64);
+        assert_eq!(val.to_i64(), 1314);
+
+        let val = WasmValue::from_raw(WasmValue::from_f32(13.14).as_raw());
+        assert_eq!(val.ty(), ValType::F32);
+        assert_eq!(val.to_f32(), 13.14);
+
+        let val = WasmValue::from_raw(WasmValue::from_f64(13.14).as_raw());
+        assert_eq!(val.ty(), ValType::F64);
+        assert_eq!(val.to_f64(), 13.14);
+
+        let val = WasmValue::from_raw(WasmValue::from_v128(1314).as_raw());
+        assert_eq!(val.ty(), ValType::V128);
+        assert_eq!(val.to_v128(), 1314);
+
+        let mut obj = 1314;
+        let val = WasmValue::from_raw(WasmValue::from_extern_ref(&mut obj).as_raw());
+        assert_eq!(val.ty(), ValType::ExternRef);
+        assert_eq!(val.extern_ref::<i32>(), Some(&1314));
+
+        let val = WasmValue::from_raw(WasmValue::from_null_ref(RefType::FuncRef).as_raw());
+        assert_eq!(val.ty(), ValType::FuncRef);
+        assert!(val.is_null_ref());
+    }
+
+    #[test]
+    fn test_types_value_is_reference() {
+        assert!(!WasmValue::from_i32(1314).is_reference());
+        assert!(!WasmValue::from_i64(1314).is_reference());
+        assert!(!WasmValue::from_f32(13.14).is_reference());
+        assert!(!WasmValue::from_f64(13.14).is_reference());
+        assert!(!WasmValue::from_v128(1314).is_reference());
+
+        assert!(WasmValue::from_null_ref(RefType::FuncRef).is_reference());
+        assert!(WasmValue::from_null_ref(RefType::ExternRef).is_reference());
+        let mut obj = 1314;
+        assert!(WasmValue::from_extern_ref(&mut obj).is_reference());
+    }
+
+    #[test]
+    fn test_types_unpack() {
+        let args = [WasmValue::from_i32(1), WasmValue::from_i64(2)];
+        let result = args.unpack::<(i32, i64)>();
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), (1, 2));
+        let result = args.unpack::<(i32, i64)>();
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().into_wasm_values().len(), 2);
+
+        let args = [WasmValue::from_f64(13.14)];
+        let result = args.unpack::<f64>();
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), 13.14);
+        let result = args.unpack::<(f64,)>();
+        assert!(result.is_ok());
+
+        // mismatched types
+        let args = [WasmValue::from_i32(1), WasmValue::from_i32(2)];
+        let result = args.unpack::<(i32, i64)>();
+        assert_eq!(
+            result.unwrap_err(),
+            WasmEdgeError::Func(error::FuncError::ValueMismatch(String::from(
+                "value 1: expected I64, found I32"
+            )))
+        );
+
+        // mismatched arity
+        assert_eq!(
+            args.unpack::<(i32,)>().unwrap_err().to_string(),
+            "Fail to convert the values: expected 1 values, found 2"
+        );
+        assert!(args.unpack::<(i32, i32, i32)>().is_err());
+        assert!(args.unpack::<()>().is_err());
+    }
+
+    #[test]
+    fn test_types_unpack_returns() {
+        let returns = [
+            WasmValue::from_i32(1),
+            WasmValue::from_i64(2),
+            WasmValue::from_f32(3.0),
+            WasmValue::from_f64(4.0),
+            WasmValue::from_i32(5),
+            WasmValue::from_i64(6),
+            WasmValue::from_f32(7.0),
+            WasmValue::from_f64(8.0),
+        ];
+        let result = returns.unpack::<(i32, i64, f32, f64, i32, i64, f32, f64)>();
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), (1, 2, 3.0, 4.0, 5, 6, 7.0, 8.0));
+
+        // the offending index is counted across the elements
+        let result = returns.unpack::<(i32, i64, f32, f64, i32, i64, f32, f32)>();
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Fail to convert the values: value 7: expected F32, found F64"
+        );
+    }
+
     #[test]
     fn test_types_value_send() {
         // I32
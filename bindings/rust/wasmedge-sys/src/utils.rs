@@ -266,51 +266,66 @@ pub(crate) fn check(result: WasmEdge_Result) -> WasmEdgeResult<()> {
         ))),
 
         // Execution phase
-        0x80 => Err(WasmEdgeError::Core(CoreError::Execution(
-            CoreExecutionError::WrongInstanceAddress,
-        ))),
-        0x81 => Err(WasmEdgeError::Core(CoreError::Execution(
-            CoreExecutionError::WrongInstanceIndex,
-        ))),
-        0x82 => Err(WasmEdgeError::Core(CoreError::Execution(
-            CoreExecutionError::InstrTypeMismatch,
-        ))),
-        0x83 => Err(WasmEdgeError::Core(CoreError::Execution(
-            CoreExecutionError::FuncTypeMismatch,
-        ))),
-        0x84 => Err(WasmEdgeError::Core(CoreError::Execution(
-            CoreExecutionError::DivideByZero,
-        ))),
-        0x85 => Err(WasmEdgeError::Core(CoreError::Execution(
-            CoreExecutionError::IntegerOverflow,
-        ))),
-        0x86 => Err(WasmEdgeError::Core(CoreError::Execution(
-            CoreExecutionError::InvalidConvToInt,
-        ))),
-        0x87 => Err(WasmEdgeError::Core(CoreError::Execution(
-            CoreExecutionError::TableOutOfBounds,
-        ))),
-        0x88 => Err(WasmEdgeError::Core(CoreError::Execution(
-            CoreExecutionError::MemoryOutOfBounds,
-        ))),
-        0x89 => Err(WasmEdgeError::Core(CoreError::Execution(
-            CoreExecutionError::Unreachable,
-        ))),
-        0x8A => Err(WasmEdgeError::Core(CoreError::Execution(
-            CoreExecutionError::UninitializedElement,
-        ))),
-        0x8B => Err(WasmEdgeError::Core(CoreError::Execution(
-            CoreExecutionError::UndefinedElement,
-        ))),
-        0x8C => Err(WasmEdgeError::Core(CoreError::Execution(
-            CoreExecutionError::IndirectCallTypeMismatch,
-        ))),
-        0x8D => Err(WasmEdgeError::Core(CoreError::Execution(
-            CoreExecutionError::ExecutionFailed,
-        ))),
-        0x8E => Err(WasmEdgeError::Core(CoreError::Execution(
-            CoreExecutionError::RefTypeMismatch,
-        ))),
+        0x80 => Err(WasmEdgeError::Core(CoreError::Execution {
+            kind: CoreExecutionError::WrongInstanceAddress,
+            trace: vec![],
+        })),
+        0x81 => Err(WasmEdgeError::Core(CoreError::Execution {
+            kind: CoreExecutionError::WrongInstanceIndex,
+            trace: vec![],
+        })),
+        0x82 => Err(WasmEdgeError::Core(CoreError::Execution {
+            kind: CoreExecutionError::InstrTypeMismatch,
+            trace: vec![],
+        })),
+        0x83 => Err(WasmEdgeError::Core(CoreError::Execution {
+            kind: CoreExecutionError::FuncTypeMismatch,
+            trace: vec![],
+        })),
+        0x84 => Err(WasmEdgeError::Core(CoreError::Execution {
+            kind: CoreExecutionError::DivideByZero,
+            trace: vec![],
+        })),
+        0x85 => Err(WasmEdgeError::Core(CoreError::Execution {
+            kind: CoreExecutionError::IntegerOverflow,
+            trace: vec![],
+        })),
+        0x86 => Err(WasmEdgeError::Core(CoreError::Execution {
+            kind: CoreExecutionError::InvalidConvToInt,
+            trace: vec![],
+        })),
+        0x87 => Err(WasmEdgeError::Core(CoreError::Execution {
+            kind: CoreExecutionError::TableOutOfBounds,
+            trace: vec![],
+        })),
+        0x88 => Err(WasmEdgeError::Core(CoreError::Execution {
+            kind: CoreExecutionError::MemoryOutOfBounds,
+            trace: vec![],
+        })),
+        0x89 => Err(WasmEdgeError::Core(CoreError::Execution {
+            kind: CoreExecutionError::Unreachable,
+            trace: vec![],
+        })),
+        0x8A => Err(WasmEdgeError::Core(CoreError::Execution {
+            kind: CoreExecutionError::UninitializedElement,
+            trace: vec![],
+        })),
+        0x8B => Err(WasmEdgeError::Core(CoreError::Execution {
+            kind: CoreExecutionError::UndefinedElement,
+            trace: vec![],
+        })),
+        0x8C => Err(WasmEdgeError::Core(CoreError::Execution {
+            kind: CoreExecutionError::IndirectCallTypeMismatch,
+            trace: vec![],
+        })),
+        0x8D => Err(WasmEdgeError::Core(CoreError::Execution {
+            kind: CoreExecutionError::ExecutionFailed,
+            trace: vec![],
+        })),
+        0x8E => Err(WasmEdgeError::Core(CoreError::Execution {
+            kind: CoreExecutionError::RefTypeMismatch,
+            trace: vec![],
+        })),
 
         _ => panic!("unknown error code: {}", code),
     }
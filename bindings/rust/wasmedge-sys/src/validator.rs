@@ -28,7 +28,7 @@ impl Validator {
             None => unsafe { ffi::WasmEdge_ValidatorCreate(std::ptr::null_mut()) },
         };
         match ctx.is_null() {
-            true => Err(WasmEdgeError::CompilerCreate),
+            true => Err(WasmEdgeError::ValidatorCreate),
             false => Ok(Self {
                 inner: InnerValidator(ctx),
                 registered: false,
@@ -44,9 +44,11 @@ impl Validator {
     ///
     /// * `module` - The [Module](crate::Module) to be validated.
     ///
+    /// Validation only checks the given module and runs none of its code, so it is safe to validate the untrusted wasm modules before instantiating them.
+    ///
     /// # Error
     ///
-    /// If the validation fails, then an error is returned.
+    /// If the validation fails, then a [CoreValidationError](crate::error::CoreValidationError) wrapped in [CoreError::Validation](crate::error::CoreError::Validation) is returned, e.g., `TypeCheckFailed` for the mismatched operand types or `InvalidFuncIdx` for the calls to the unknown functions. Notice that the unknown imports are not detected by validation, but reported during instantiation.
     pub fn validate(&self, module: &Module) -> WasmEdgeResult<()> {
         unsafe {
             check(ffi::WasmEdge_ValidatorValidate(
@@ -72,11 +74,15 @@ unsafe impl Sync for InnerValidator {}
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{Config, Loader};
+    use crate::{
+        error::{CoreError, CoreValidationError},
+        Config, Loader,
+    };
     use std::{
         sync::{Arc, Mutex},
         thread,
     };
+    use wasmedge_types::wat2wasm;
 
     #[test]
     fn test_validator() {
@@ -111,6 +117,110 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_validator_invalid_module() {
+        let result = Loader::create(None);
+        assert!(result.is_ok());
+        let loader = result.unwrap();
+        let result = Validator::create(None);
+        assert!(result.is_ok());
+        let validator = result.unwrap();
+
+        // the returned value is of i64 instead of i32
+        let result = wat2wasm(
+            br#"
+            (module
+                (func (export "f") (result i32)
+                    (i64.const 0))
+            )
+"#,
+        );
+        assert!(result.is_ok());
+        let result = loader.from_bytes(result.unwrap());
+        assert!(result.is_ok());
+        let module = result.unwrap();
+        let result = validator.validate(&module);
+        assert_eq!(
+            result.unwrap_err(),
+            WasmEdgeError::Core(CoreError::Validation(CoreValidationError::TypeCheckFailed))
+        );
+
+        // the called function doesn't exist
+        let result = wat2wasm(
+            br#"
+            (module
+                (func (export "f")
+                    (call 5))
+            )
+"#,
+        );
+        assert!(result.is_ok());
+        let result = loader.from_bytes(result.unwrap());
+        assert!(result.is_ok());
+        let module = result.unwrap();
+        let result = validator.validate(&module);
+        assert_eq!(
+            result.unwrap_err(),
+            WasmEdgeError::Core(CoreError::Validation(CoreValidationError::InvalidFuncIdx))
+        );
+    }
+
+    #[test]
+    fn test_validator_simd_proposal() {
+        let result = wat2wasm(
+            br#"
+            (module
+                (func (export "splat") (param i32) (result v128)
+                    (i32x4.splat (local.get 0)))
+            )
+"#,
+        );
+        assert!(result.is_ok());
+        let wasm_bytes = result.unwrap();
+
+        // the module using v128 is rejected if SIMD is disabled
+        let result = Config::create();
+        assert!(result.is_ok());
+        let mut config = result.unwrap();
+        config.simd(false);
+        assert!(!config.simd_enabled());
+        let result = Config::copy_from(&config);
+        assert!(result.is_ok());
+        let validator_config = result.unwrap();
+        let result = Loader::create(Some(config));
+        assert!(result.is_ok());
+        let loader = result.unwrap();
+        let result = Validator::create(Some(validator_config));
+        assert!(result.is_ok());
+        let validator = result.unwrap();
+        // depending on the stage checking the proposal, either loading or validation fails
+        let result = loader
+            .from_bytes(&wasm_bytes)
+            .and_then(|module| validator.validate(&module));
+        assert!(result.is_err());
+
+        // the same module passes if SIMD is enabled
+        let result = Config::create();
+        assert!(result.is_ok());
+        let mut config = result.unwrap();
+        config.simd(true);
+        assert!(config.simd_enabled());
+        let result = Config::copy_from(&config);
+        assert!(result.is_ok());
+        let validator_config = result.unwrap();
+        let result = Loader::create(Some(config));
+        assert!(result.is_ok());
+        let loader = result.unwrap();
+        let result = loader.from_bytes(&wasm_bytes);
+        assert!(result.is_ok());
+        let module = result.unwrap();
+        let result = Validator::create(Some(validator_config));
+        assert!(result.is_ok());
+        let validator = result.unwrap();
+        let result = validator.validate(&module);
+        assert!(result.is_ok());
+    }
+
     #[test]
     fn test_validator_send() {
         // create a Validator
@@ -1,26 +1,35 @@
 //! Defines WasmEdge Vm struct.
 
 use crate::{
-    error::{VmError, WasmEdgeError},
+    error::{CoreCommonError, CoreError, VmError, WasmEdgeError},
     ffi::{self, WasmEdge_HostRegistration_Wasi, WasmEdge_HostRegistration_WasmEdge_Process},
     instance::{
-        function::{FuncType, InnerFuncType},
+        function::{charge_host_costs_to, FuncType, InnerFuncType},
         module::InnerInstance,
     },
-    statistics::{InnerStat, Statistics},
+    statistics::{HostCost, InnerStat, Statistics},
     store::{InnerStore, Store},
     types::WasmEdgeString,
     utils::{self, check},
     Config, ImportObject, Instance, Module, WasiModule, WasmEdgeProcessModule, WasmEdgeResult,
     WasmValue,
 };
-use std::{collections::HashMap, path::Path};
+use std::{
+    collections::HashMap,
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+};
 
 /// A [Vm] defines a virtual environment for managing WebAssembly programs.
 #[derive(Debug)]
 pub struct Vm {
     pub(crate) inner: InnerVm,
     imports: HashMap<String, ImportObject>,
+    // the counter of the computed costs of host functions in the statistics of this vm
+    host_cost: Arc<HostCost>,
 }
 impl Vm {
     /// Creates a new [Vm] to be associated with the given [configuration](crate::Config) and [store](crate::Store).
@@ -59,6 +68,9 @@ impl Vm {
             false => Ok(Self {
                 inner: InnerVm(ctx),
                 imports: HashMap::new(),
+                host_cost: Arc::new(HostCost::new(unsafe {
+                    ffi::WasmEdge_VMGetStatisticsContext(ctx)
+                })),
             }),
         }
     }
@@ -286,7 +298,7 @@ impl Vm {
     ///
     /// # Error
     ///
-    /// If fail to run, then an error is returned.
+    /// If fail to run, then an error is returned. The phase that fails is told by the error: a `CoreError::Load`, `CoreError::Validation`, `CoreError::Instantiation`, or `CoreError::Execution` error is returned if fail to load, validate, instantiate the module, or run the function, respectively; an `InstanceError::NotFoundFunc` error is returned if the module doesn't export the function.
     pub fn run_wasm_from_bytes(
         &mut self,
         bytes: &[u8],
@@ -467,6 +479,7 @@ impl Vm {
         let mut returns = Vec::with_capacity(returns_len as usize);
 
         let func_name: WasmEdgeString = func_name.as_ref().into();
+        let _guard = charge_host_costs_to(Some(Arc::clone(&self.host_cost)));
         unsafe {
             check(ffi::WasmEdge_VMExecute(
                 self.inner.0,
@@ -482,6 +495,109 @@ impl Vm {
         Ok(returns.into_iter().map(Into::into).collect::<Vec<_>>())
     }
 
+    /// Runs an exported WASM function by name like [run_function](crate::Vm::run_function), which can be interrupted from another thread through the given [InterruptHandle].
+    ///
+    /// The function is run asynchronously by the WasmEdge engine, and this call blocks until the run completes or is interrupted.
+    ///
+    /// Notice that the engine runs the function on a worker thread of its own, which the per-thread state of the calling thread is not forwarded to. Therefore, unlike [run_function](crate::Vm::run_function), the costs computed by the host functions created by [create_with_cost_fn](crate::Function::create_with_cost_fn) are not charged to the [Statistics](crate::Statistics) of this [Vm], the errors of the host functions are reported by their bare codes instead of `FuncError::Host` or `FuncError::HostTrap` errors, and the host function calls are not counted in the [snapshots](crate::Statistics::snapshot) taken on the calling thread.
+    ///
+    /// # Arguments
+    ///
+    /// * `func_name` - The name of the exported WASM function to run.
+    ///
+    /// * `params` - The parameter values passed to the exported WASM function.
+    ///
+    /// * `handle` - The handle through which the run can be interrupted.
+    ///
+    /// # Error
+    ///
+    /// If the run is interrupted, or the `handle` has been interrupted before the run, then a `CoreCommonError::Interrupted` error is returned. If fail to run the WASM function, then an error is returned.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let handle = InterruptHandle::new();
+    ///
+    /// let handle_cloned = handle.clone();
+    /// std::thread::spawn(move || {
+    ///     std::thread::sleep(std::time::Duration::from_millis(100));
+    ///     handle_cloned.interrupt();
+    /// });
+    ///
+    /// // `spin` never returns by itself
+    /// let result = vm.run_function_interruptible("spin", [], &handle);
+    /// assert!(result.is_err());
+    /// ```
+    pub fn run_function_interruptible(
+        &self,
+        func_name: impl AsRef<str>,
+        params: impl IntoIterator<Item = WasmValue>,
+        handle: &InterruptHandle,
+    ) -> WasmEdgeResult<Vec<WasmValue>> {
+        // prepare parameters
+        let raw_params = params.into_iter().map(|x| x.as_raw()).collect::<Vec<_>>();
+
+        // prepare returns
+        let func_type = self.get_function_type(func_name.as_ref())?;
+        let returns_len = unsafe { ffi::WasmEdge_FunctionTypeGetReturnsLength(func_type.inner.0) };
+        let mut returns = Vec::with_capacity(returns_len as usize);
+
+        // start the run, unless the handle has been interrupted
+        let ctx = {
+            let mut running = handle
+                .inner
+                .running
+                .lock()
+                .unwrap_or_else(|e| e.into_inner());
+            if handle.is_interrupted() {
+                return Err(WasmEdgeError::Core(CoreError::Common(
+                    CoreCommonError::Interrupted,
+                )));
+            }
+
+            let raw_func_name: WasmEdgeString = func_name.as_ref().into();
+            let ctx = unsafe {
+                ffi::WasmEdge_VMAsyncExecute(
+                    self.inner.0,
+                    raw_func_name.as_raw(),
+                    raw_params.as_ptr(),
+                    raw_params.len() as u32,
+                )
+            };
+            if ctx.is_null() {
+                return Err(WasmEdgeError::Vm(VmError::AsyncRun(
+                    func_name.as_ref().to_string(),
+                )));
+            }
+            running.push(InnerAsync(ctx));
+            ctx
+        };
+
+        // wait for the run to complete or to be interrupted
+        let result = unsafe {
+            check(ffi::WasmEdge_AsyncGet(
+                ctx,
+                returns.as_mut_ptr(),
+                returns_len,
+            ))
+        };
+        if result.is_ok() {
+            unsafe { returns.set_len(returns_len as usize) };
+        }
+
+        // the run is done, so it can't be interrupted any longer
+        handle
+            .inner
+            .running
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .retain(|x| x.0 != ctx);
+        unsafe { ffi::WasmEdge_AsyncDelete(ctx) };
+
+        result?;
+        Ok(returns.into_iter().map(Into::into).collect::<Vec<_>>())
+    }
+
     /// Runs an exported WASM function by its name and the module's name in which the WASM function is hosted.
     ///
     /// After registering a WASM module in the [Vm], repeatedly call this function to run exported WASM functions by their function names and the module names until the [Vm] is reset.
@@ -515,6 +631,7 @@ impl Vm {
 
         let mod_name: WasmEdgeString = mod_name.as_ref().into();
         let func_name: WasmEdgeString = func_name.as_ref().into();
+        let _guard = charge_host_costs_to(Some(Arc::clone(&self.host_cost)));
         unsafe {
             check(ffi::WasmEdge_VMExecuteRegistered(
                 self.inner.0,
@@ -605,6 +722,7 @@ impl Vm {
                 false => Some(FuncType {
                     inner: InnerFuncType(ty as *mut _),
                     registered: true,
+                    wildcard: false,
                 }),
             };
             (Some(name), func_ty)
@@ -623,6 +741,7 @@ impl Vm {
             false => Ok(WasiModule {
                 inner: InnerInstance(io_ctx),
                 registered: true,
+                host_funcs: Vec::new(),
             }),
         }
     }
@@ -642,6 +761,7 @@ impl Vm {
             false => Ok(WasmEdgeProcessModule {
                 inner: InnerInstance(io_ctx),
                 registered: true,
+                host_funcs: Vec::new(),
             }),
         }
     }
@@ -666,6 +786,7 @@ impl Vm {
             false => Ok(Statistics {
                 inner: InnerStat(stat_ctx),
                 registered: true,
+                host_cost: Arc::clone(&self.host_cost),
             }),
         }
     }
@@ -710,9 +831,54 @@ pub(crate) struct InnerVm(pub(crate) *mut ffi::WasmEdge_VMContext);
 unsafe impl Send for InnerVm {}
 unsafe impl Sync for InnerVm {}
 
+/// Defines a handle to interrupt the WASM functions run by [run_function_interruptible](crate::Vm::run_function_interruptible), which is cheap to clone and can be sent to other threads.
+///
+/// The interruption cooperates with the trap mechanism of the WasmEdge engine: the running function stops with a `CoreCommonError::Interrupted` error rather than the thread being killed. Once interrupted, the handle keeps interrupted, and the following runs with it fail immediately until the handle is [reset](crate::InterruptHandle::reset).
+///
+/// Since the interruptible runs take place on a worker thread of the engine, the computed costs, the rich errors and the call counts of the host functions are lost for them; see [run_function_interruptible](crate::Vm::run_function_interruptible).
+#[derive(Debug, Clone, Default)]
+pub struct InterruptHandle {
+    inner: Arc<InnerInterruptHandle>,
+}
+impl InterruptHandle {
+    /// Creates a new [InterruptHandle].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Interrupts the runs in progress with this handle, and the following ones until the handle is [reset](crate::InterruptHandle::reset).
+    pub fn interrupt(&self) {
+        let running = self.inner.running.lock().unwrap_or_else(|e| e.into_inner());
+        self.inner.interrupted.store(true, Ordering::SeqCst);
+        for ctx in running.iter() {
+            unsafe { ffi::WasmEdge_AsyncCancel(ctx.0) };
+        }
+    }
+
+    /// Checks if this handle is interrupted.
+    pub fn is_interrupted(&self) -> bool {
+        self.inner.interrupted.load(Ordering::SeqCst)
+    }
+
+    /// Resets this handle, so that the following runs with it are not interrupted.
+    pub fn reset(&self) {
+        self.inner.interrupted.store(false, Ordering::SeqCst);
+    }
+}
+
+#[derive(Debug, Default)]
+struct InnerInterruptHandle {
+    interrupted: AtomicBool,
+    running: Mutex<Vec<InnerAsync>>,
+}
+
+#[derive(Debug)]
+struct InnerAsync(*mut ffi::WasmEdge_Async);
+unsafe impl Send for InnerAsync {}
+
 #[cfg(test)]
 mod tests {
-    use super::Vm;
+    use super::{InterruptHandle, Vm};
     use crate::{
         error::{
             CoreCommonError, CoreError, CoreExecutionError, CoreInstantiationError, CoreLoadError,
@@ -725,7 +891,7 @@ mod tests {
         sync::{Arc, Mutex},
         thread,
     };
-    use wasmedge_types::ValType;
+    use wasmedge_types::{wat2wasm, ValType};
 
     #[test]
     fn test_vm_create() {
@@ -1064,7 +1230,10 @@ mod tests {
         assert!(result.is_err());
         assert_eq!(
             result.unwrap_err(),
-            WasmEdgeError::Core(CoreError::Execution(CoreExecutionError::FuncTypeMismatch))
+            WasmEdgeError::Core(CoreError::Execution {
+                kind: CoreExecutionError::FuncTypeMismatch,
+                trace: vec![],
+            })
         );
 
         // run a function with the parameters of wrong type
@@ -1072,7 +1241,10 @@ mod tests {
         assert!(result.is_err());
         assert_eq!(
             result.unwrap_err(),
-            WasmEdgeError::Core(CoreError::Execution(CoreExecutionError::FuncTypeMismatch))
+            WasmEdgeError::Core(CoreError::Execution {
+                kind: CoreExecutionError::FuncTypeMismatch,
+                trace: vec![],
+            })
         );
 
         // run a function: the specified function name is non-existant
@@ -1219,7 +1391,10 @@ mod tests {
         assert!(result.is_err());
         assert_eq!(
             result.unwrap_err(),
-            WasmEdgeError::Core(CoreError::Execution(CoreExecutionError::FuncTypeMismatch))
+            WasmEdgeError::Core(CoreError::Execution {
+                kind: CoreExecutionError::FuncTypeMismatch,
+                trace: vec![],
+            })
         );
 
         // run a registered function with the parameters of wrong type
@@ -1227,7 +1402,10 @@ mod tests {
         assert!(result.is_err());
         assert_eq!(
             result.unwrap_err(),
-            WasmEdgeError::Core(CoreError::Execution(CoreExecutionError::FuncTypeMismatch))
+            WasmEdgeError::Core(CoreError::Execution {
+                kind: CoreExecutionError::FuncTypeMismatch,
+                trace: vec![],
+            })
         );
 
         // run a registered function but give a wrong function name.
@@ -1368,7 +1546,10 @@ mod tests {
         assert!(result.is_err());
         assert_eq!(
             result.unwrap_err(),
-            WasmEdgeError::Core(CoreError::Execution(CoreExecutionError::FuncTypeMismatch))
+            WasmEdgeError::Core(CoreError::Execution {
+                kind: CoreExecutionError::FuncTypeMismatch,
+                trace: vec![],
+            })
         );
 
         // run a function from a WASM file with the parameters of wrong type
@@ -1376,7 +1557,10 @@ mod tests {
         assert!(result.is_err());
         assert_eq!(
             result.unwrap_err(),
-            WasmEdgeError::Core(CoreError::Execution(CoreExecutionError::FuncTypeMismatch))
+            WasmEdgeError::Core(CoreError::Execution {
+                kind: CoreExecutionError::FuncTypeMismatch,
+                trace: vec![],
+            })
         );
 
         // fun a function: the specified function name is non-existant
@@ -1432,7 +1616,10 @@ mod tests {
         assert!(result.is_err());
         assert_eq!(
             result.unwrap_err(),
-            WasmEdgeError::Core(CoreError::Execution(CoreExecutionError::FuncTypeMismatch))
+            WasmEdgeError::Core(CoreError::Execution {
+                kind: CoreExecutionError::FuncTypeMismatch,
+                trace: vec![],
+            })
         );
 
         // run a function with the parameters of wrong type
@@ -1440,7 +1627,10 @@ mod tests {
         assert!(result.is_err());
         assert_eq!(
             result.unwrap_err(),
-            WasmEdgeError::Core(CoreError::Execution(CoreExecutionError::FuncTypeMismatch))
+            WasmEdgeError::Core(CoreError::Execution {
+                kind: CoreExecutionError::FuncTypeMismatch,
+                trace: vec![],
+            })
         );
 
         // fun a function: the specified function name is non-existant
@@ -1452,6 +1642,119 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_vm_run_wasm_from_bytes_phases() {
+        // (module (func (export "add") (param i32 i32) (result i32)
+        //     (i32.add (local.get 0) (local.get 1))))
+        const ADD_WASM: [u8; 41] = [
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // magic and version
+            0x01, 0x07, 0x01, 0x60, 0x02, 0x7f, 0x7f, 0x01, 0x7f, // type section
+            0x03, 0x02, 0x01, 0x00, // function section
+            0x07, 0x07, 0x01, 0x03, 0x61, 0x64, 0x64, 0x00, 0x00, // export section
+            0x0a, 0x09, 0x01, 0x07, 0x00, 0x20, 0x00, 0x20, 0x01, 0x6a, 0x0b, // code section
+        ];
+
+        let result = Vm::create(None, None);
+        assert!(result.is_ok());
+        let mut vm = result.unwrap();
+
+        // run the embedded module
+        let result = vm.run_wasm_from_bytes(
+            &ADD_WASM,
+            "add",
+            [WasmValue::from_i32(2), WasmValue::from_i32(40)],
+        );
+        assert!(result.is_ok());
+        let returns = result.unwrap();
+        assert_eq!(returns.len(), 1);
+        assert_eq!(returns[0].to_i32(), 42);
+
+        // fail to load: the truncated module
+        let result = vm.run_wasm_from_bytes(&ADD_WASM[..20], "add", []);
+        assert!(matches!(
+            result.unwrap_err(),
+            WasmEdgeError::Core(CoreError::Load(_))
+        ));
+
+        // fail to validate: `i32.add` with a single operand
+        let mut invalid = ADD_WASM.to_vec();
+        invalid.splice(31.., [0x07, 0x01, 0x05, 0x00, 0x20, 0x00, 0x6a, 0x0b]);
+        let result = vm.run_wasm_from_bytes(&invalid, "add", []);
+        assert!(matches!(
+            result.unwrap_err(),
+            WasmEdgeError::Core(CoreError::Validation(_))
+        ));
+
+        // fail to find the function
+        let result = vm.run_wasm_from_bytes(&ADD_WASM, "sub", []);
+        assert_eq!(
+            result.unwrap_err(),
+            WasmEdgeError::Instance(InstanceError::NotFoundFunc("sub".into()))
+        );
+
+        // fail to run: the mismatched arguments
+        let result = vm.run_wasm_from_bytes(&ADD_WASM, "add", [WasmValue::from_i32(2)]);
+        assert!(matches!(
+            result.unwrap_err(),
+            WasmEdgeError::Core(CoreError::Execution { .. })
+        ));
+    }
+
+    #[test]
+    fn test_vm_run_function_interruptible() {
+        let result = Vm::create(None, None);
+        assert!(result.is_ok());
+        let mut vm = result.unwrap();
+
+        let result = wat2wasm(
+            br#"
+            (module
+                (func (export "spin") (loop (br 0)))
+                (func (export "inc") (param i32) (result i32)
+                    (i32.add (local.get 0) (i32.const 1)))
+            )
+"#,
+        );
+        assert!(result.is_ok());
+        let wasm_bytes = result.unwrap();
+        assert!(vm.load_wasm_from_bytes(&wasm_bytes).is_ok());
+        assert!(vm.validate().is_ok());
+        assert!(vm.instantiate().is_ok());
+
+        // a run which is not interrupted
+        let handle = InterruptHandle::new();
+        let result = vm.run_function_interruptible("inc", [WasmValue::from_i32(41)], &handle);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap()[0].to_i32(), 42);
+
+        // interrupt the infinite loop from another thread
+        let handle_cloned = handle.clone();
+        let interrupter = thread::spawn(move || {
+            thread::sleep(std::time::Duration::from_millis(100));
+            handle_cloned.interrupt();
+        });
+        let result = vm.run_function_interruptible("spin", [], &handle);
+        assert_eq!(
+            result.unwrap_err(),
+            WasmEdgeError::Core(CoreError::Common(CoreCommonError::Interrupted))
+        );
+        assert!(interrupter.join().is_ok());
+        assert!(handle.is_interrupted());
+
+        // the interrupted handle fails the following runs immediately
+        let result = vm.run_function_interruptible("inc", [WasmValue::from_i32(1)], &handle);
+        assert_eq!(
+            result.unwrap_err(),
+            WasmEdgeError::Core(CoreError::Common(CoreCommonError::Interrupted))
+        );
+
+        // the vm is still usable after resetting the handle
+        handle.reset();
+        let result = vm.run_function_interruptible("inc", [WasmValue::from_i32(1)], &handle);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap()[0].to_i32(), 2);
+    }
+
     #[test]
     fn test_vm_run_wasm_from_module() {
         // create a Config context
@@ -1484,7 +1787,10 @@ mod tests {
         assert!(result.is_err());
         assert_eq!(
             result.unwrap_err(),
-            WasmEdgeError::Core(CoreError::Execution(CoreExecutionError::FuncTypeMismatch))
+            WasmEdgeError::Core(CoreError::Execution {
+                kind: CoreExecutionError::FuncTypeMismatch,
+                trace: vec![],
+            })
         );
 
         // run a function with the parameters of wrong type
@@ -1493,7 +1799,10 @@ mod tests {
         assert!(result.is_err());
         assert_eq!(
             result.unwrap_err(),
-            WasmEdgeError::Core(CoreError::Execution(CoreExecutionError::FuncTypeMismatch))
+            WasmEdgeError::Core(CoreError::Execution {
+                kind: CoreExecutionError::FuncTypeMismatch,
+                trace: vec![],
+            })
         );
 
         // fun a function: the specified function name is non-existant
@@ -1576,6 +1885,58 @@ mod tests {
         handle.join().unwrap();
     }
 
+    #[test]
+    fn test_vm_wasi_from_config() {
+        let result = wat2wasm(
+            br#"
+            (module
+                (import "wasi_snapshot_preview1" "fd_write"
+                    (func $fd_write (param i32 i32 i32 i32) (result i32)))
+                (memory (export "memory") 1)
+                (func (export "_start"))
+            )
+"#,
+        );
+        assert!(result.is_ok());
+        let wasm_bytes = result.unwrap();
+
+        // the wasi imports are resolved by the wasi module registered via the config
+        let result = Config::create();
+        assert!(result.is_ok());
+        let mut config = result.unwrap();
+        config.wasi(true);
+        assert!(config.wasi_enabled());
+        let result = Vm::create(Some(config), None);
+        assert!(result.is_ok());
+        let mut vm = result.unwrap();
+        assert!(vm.wasi_module_mut().is_ok());
+        assert!(vm.load_wasm_from_bytes(&wasm_bytes).is_ok());
+        assert!(vm.validate().is_ok());
+        assert!(vm.instantiate().is_ok());
+        let result = vm.run_function("_start", []);
+        assert!(result.is_ok());
+
+        // without the wasi host registration, the import is unknown
+        let result = Config::create();
+        assert!(result.is_ok());
+        let mut config = result.unwrap();
+        config.wasi(false);
+        assert!(!config.wasi_enabled());
+        let result = Vm::create(Some(config), None);
+        assert!(result.is_ok());
+        let mut vm = result.unwrap();
+        assert!(vm.load_wasm_from_bytes(&wasm_bytes).is_ok());
+        assert!(vm.validate().is_ok());
+        let result = vm.instantiate();
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err(),
+            WasmEdgeError::Core(CoreError::Instantiation(
+                CoreInstantiationError::UnknownImport
+            ))
+        );
+    }
+
     #[test]
     fn test_vm_get_wasi_module() {
         {
@@ -23,9 +23,9 @@ fn test_executor_with_statistics() {
     assert!(result.is_ok());
     let mut stat = result.unwrap();
     // set cost table
-    stat.set_cost_table(&mut []);
-    let mut cost_table = vec![20u64; 512];
-    stat.set_cost_table(&mut cost_table);
+    assert!(stat.set_cost_table(&mut []).is_err());
+    let mut cost_table = vec![20u64; Statistics::COST_TABLE_LEN];
+    assert!(stat.set_cost_table(&mut cost_table).is_ok());
     // set cost limit
     stat.set_cost_limit(100_000_000_000_000);
 
@@ -120,7 +120,10 @@ fn test_executor_with_statistics() {
     assert!(result.is_err());
     assert_eq!(
         result.unwrap_err(),
-        WasmEdgeError::Core(CoreError::Execution(CoreExecutionError::FuncTypeMismatch))
+        WasmEdgeError::Core(CoreError::Execution {
+            kind: CoreExecutionError::FuncTypeMismatch,
+            trace: vec![],
+        })
     );
 
     // function type mismatched
@@ -131,7 +134,10 @@ fn test_executor_with_statistics() {
     assert!(result.is_err());
     assert_eq!(
         result.unwrap_err(),
-        WasmEdgeError::Core(CoreError::Execution(CoreExecutionError::FuncTypeMismatch))
+        WasmEdgeError::Core(CoreError::Execution {
+            kind: CoreExecutionError::FuncTypeMismatch,
+            trace: vec![],
+        })
     );
 
     // try to get non-existent exported function
@@ -230,7 +236,10 @@ fn test_executor_with_statistics() {
     assert!(result.is_err());
     assert_eq!(
         result.unwrap_err(),
-        WasmEdgeError::Core(CoreError::Execution(CoreExecutionError::FuncTypeMismatch))
+        WasmEdgeError::Core(CoreError::Execution {
+            kind: CoreExecutionError::FuncTypeMismatch,
+            trace: vec![],
+        })
     );
     // Function type mismatch
     let result = executor.run_func(
@@ -243,7 +252,10 @@ fn test_executor_with_statistics() {
     assert!(result.is_err());
     assert_eq!(
         result.unwrap_err(),
-        WasmEdgeError::Core(CoreError::Execution(CoreExecutionError::FuncTypeMismatch))
+        WasmEdgeError::Core(CoreError::Execution {
+            kind: CoreExecutionError::FuncTypeMismatch,
+            trace: vec![],
+        })
     );
     // Module not found
     let result = store.module("error-name");
@@ -279,6 +291,9 @@ fn test_executor_with_statistics() {
     assert!(result.is_err());
     assert_eq!(
         result.unwrap_err(),
-        WasmEdgeError::Core(CoreError::Execution(CoreExecutionError::ExecutionFailed))
+        WasmEdgeError::Core(CoreError::Execution {
+            kind: CoreExecutionError::ExecutionFailed,
+            trace: vec![],
+        })
     );
 }
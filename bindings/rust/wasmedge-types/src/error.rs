@@ -1,6 +1,6 @@
 //! Defines WasmEdge error types.
 
-use crate::ExternalInstanceType;
+use crate::{ExternalInstanceType, ValType};
 use thiserror::Error;
 
 /// The error types used by both wasmedge-sys and wasmedge crates.
@@ -18,6 +18,8 @@ pub enum WasmEdgeError {
     Store(StoreError),
     #[error("Fail to create Statistics context")]
     StatisticsCreate,
+    #[error("{0}")]
+    Statistics(StatisticsError),
     #[error("Fail to create Module")]
     ModuleCreate,
     #[error("Fail to create Loader")]
@@ -54,6 +56,10 @@ pub enum WasmEdgeError {
     Export(ExportError),
     #[error("{0}")]
     Instance(InstanceError),
+    #[error("Fail to create a value of the type {0:?} from raw bits")]
+    ValueFromBits(ValType),
+    #[error("{0}")]
+    Type(TypeError),
 
     // std
     #[error("Found an interior nul byte")]
@@ -62,6 +68,77 @@ pub enum WasmEdgeError {
     NotFoundNulByte(#[from] std::ffi::FromBytesWithNulError),
     #[error("Fail to interpret a sequence of u8 as a string")]
     Utf8(#[from] std::str::Utf8Error),
+    #[error("I/O error: {0}")]
+    Io(String),
+}
+
+impl WasmEdgeError {
+    /// Classifies this error by whether retrying the failed operation may succeed.
+    ///
+    /// The resource and allocation errors, for example, the failures of creating contexts or instances, the interrupted executions, and the host functions timed out, are [Retryability::Transient]. The traps raised by the WebAssembly code, for example, dividing by zero or accessing the memory out of bounds, are [Retryability::GuestTrap], which are fatal to the same inputs. All the other errors are [Retryability::Fatal], including the failures of host functions and the misuses of the host API reported by the execution phase, for example, `ExecutionFailed` and `FuncTypeMismatch`.
+    pub fn retryability(&self) -> Retryability {
+        match self {
+            WasmEdgeError::Core(CoreError::Execution { kind, .. }) => match kind {
+                CoreExecutionError::DivideByZero
+                | CoreExecutionError::IntegerOverflow
+                | CoreExecutionError::InvalidConvToInt
+                | CoreExecutionError::TableOutOfBounds
+                | CoreExecutionError::MemoryOutOfBounds
+                | CoreExecutionError::Unreachable
+                | CoreExecutionError::UninitializedElement
+                | CoreExecutionError::UndefinedElement
+                | CoreExecutionError::IndirectCallTypeMismatch => Retryability::GuestTrap,
+                // raised by the host functions or the misuses of the host API
+                CoreExecutionError::WrongInstanceAddress
+                | CoreExecutionError::WrongInstanceIndex
+                | CoreExecutionError::InstrTypeMismatch
+                | CoreExecutionError::FuncTypeMismatch
+                | CoreExecutionError::ExecutionFailed
+                | CoreExecutionError::RefTypeMismatch => Retryability::Fatal,
+            },
+            WasmEdgeError::Core(CoreError::Common(CoreCommonError::Interrupted))
+            | WasmEdgeError::Func(FuncError::HostTrap(HostTrap::Timeout))
+            | WasmEdgeError::ImportObjCreate
+            | WasmEdgeError::ExecutorCreate
+            | WasmEdgeError::StatisticsCreate
+            | WasmEdgeError::ModuleCreate
+            | WasmEdgeError::LoaderCreate
+            | WasmEdgeError::ConfigCreate
+            | WasmEdgeError::CompilerCreate
+            | WasmEdgeError::ValidatorCreate
+            | WasmEdgeError::Store(StoreError::Create)
+            | WasmEdgeError::Vm(VmError::Create)
+            | WasmEdgeError::Func(FuncError::Create)
+            | WasmEdgeError::Func(FuncError::CreateBinding(_))
+            | WasmEdgeError::FuncTypeCreate
+            | WasmEdgeError::Mem(MemError::Create)
+            | WasmEdgeError::MemTypeCreate
+            | WasmEdgeError::Global(GlobalError::Create)
+            | WasmEdgeError::GlobalTypeCreate
+            | WasmEdgeError::Table(TableError::Create)
+            | WasmEdgeError::TableTypeCreate => Retryability::Transient,
+            WasmEdgeError::Func(FuncError::Trapped { error, .. })
+            | WasmEdgeError::Func(FuncError::BatchCallFailed { error, .. }) => error.retryability(),
+            _ => Retryability::Fatal,
+        }
+    }
+}
+
+/// Defines the classifications of [WasmEdgeError]s by whether retrying the failed operation may succeed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Retryability {
+    /// Retrying the failed operation may succeed, for example, after resources are released.
+    Transient,
+    /// Retrying the failed operation never succeeds.
+    Fatal,
+    /// The WebAssembly code trapped. Retrying with the same inputs traps again.
+    GuestTrap,
+}
+impl Retryability {
+    /// Checks if retrying the failed operation may succeed.
+    pub fn is_retryable(&self) -> bool {
+        *self == Retryability::Transient
+    }
 }
 
 /// The error types for WasmEdge Function.
@@ -73,6 +150,50 @@ pub enum FuncError {
     CreateBinding(String),
     #[error("Fail to get the function type")]
     Type,
+    #[error("The function instance is a null pointer")]
+    NullPointer,
+    #[error("The value type {0:?} is not supported in function types")]
+    UnsupportedType(ValType),
+    #[error("The function doesn't satisfy the import: {0}")]
+    ImportMismatch(String),
+    #[error("Unknown value type code: {0:#X}")]
+    UnknownTypeCode(u8),
+    #[error("Fail to convert between JSON and WasmValue: {0}")]
+    Json(String),
+    #[error("Fail to encode or decode the invocation: {0}")]
+    Invocation(String),
+    #[error("host function failed with code {code:#X}: {message}")]
+    Host { code: u8, message: String },
+    #[error("{0}")]
+    HostTrap(HostTrap),
+    #[error("The capacity {0} is less than the number of the registered host functions {1}")]
+    CapacityBelowLength(usize, usize),
+    #[error("Fail to convert the values: {0}")]
+    ValueMismatch(String),
+    #[error("host function \"{name}\" trapped: {error}")]
+    Trapped {
+        name: String,
+        error: Box<WasmEdgeError>,
+    },
+    #[error("the call at index {index} of the batch failed: {error}")]
+    BatchCallFailed {
+        index: usize,
+        error: Box<WasmEdgeError>,
+    },
+}
+
+/// The traps raised by the wrappers of host functions.
+#[derive(Error, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HostTrap {
+    /// The host function doesn't finish within its deadline.
+    #[error("host function timed out")]
+    Timeout,
+    /// The host function returns more values than the configured cap.
+    #[error("host function returned too many values")]
+    ResultTooLarge,
+    /// The host function panics on a worker thread, of which the panic message is lost. The panics on the calling thread are reported with their messages instead.
+    #[error("host function panicked")]
+    Panicked,
 }
 
 /// The error types for WasmEdge Memory.
@@ -88,6 +209,8 @@ pub enum MemError {
     MutPtr,
     #[error("Fail to convert a raw pointer to a reference")]
     Ptr2Ref,
+    #[error("The data range is out of the bounds of the memory")]
+    OutOfBounds,
 }
 
 /// The error types for WasmEdge Global.
@@ -99,7 +222,7 @@ pub enum GlobalError {
     Type,
     #[error("Trying to set value to a const global variable")]
     ModifyConst,
-    #[error("")]
+    #[error("The value type doesn't match the value type of the global")]
     UnmatchedValType,
 }
 
@@ -110,6 +233,10 @@ pub enum TableError {
     Create,
     #[error("Fail to get the table type")]
     Type,
+    #[error("The index {index} is out of the bounds of the table of size {size}")]
+    OutOfBounds { index: u32, size: u32 },
+    #[error("The reference type doesn't match the element type of the table")]
+    UnmatchedRefType,
 }
 
 /// The error types for WasmEdge ImportType.
@@ -167,6 +294,22 @@ pub enum InstanceError {
     NotFoundMem(String),
     #[error("Fail to find the target global ({0})")]
     NotFoundGlobal(String),
+    #[error("The export name ({0}) is used more than once")]
+    DuplicateExportName(String),
+}
+
+/// The error types for the mismatched value types.
+#[derive(Error, Clone, Debug, PartialEq, Eq)]
+pub enum TypeError {
+    #[error("Expect a value of the type {expected:?}, but found {found:?}")]
+    Mismatch { expected: ValType, found: ValType },
+}
+
+/// The error types for WasmEdge Statistics.
+#[derive(Error, Clone, Debug, PartialEq, Eq)]
+pub enum StatisticsError {
+    #[error("The cost table has {len} entries, but {required} entries are required to cover all the opcodes")]
+    CostTableTooShort { len: usize, required: usize },
 }
 
 /// The error types for WasmEdge Store.
@@ -235,6 +378,8 @@ pub enum VmError {
     InvalidImportModule,
     #[error("Not found active module instance")]
     NotFoundActiveModule,
+    #[error("Fail to start an asynchronous run of the target function ({0})")]
+    AsyncRun(String),
 }
 
 /// The error types raised by WasmEdge Core.
@@ -248,8 +393,21 @@ pub enum CoreError {
     Validation(CoreValidationError),
     #[error("{0}")]
     Instantiation(CoreInstantiationError),
-    #[error("{0}")]
-    Execution(CoreExecutionError),
+    #[error("{kind}{}", display_trace(.trace))]
+    Execution {
+        kind: CoreExecutionError,
+        /// The frames of the trapped call, from the innermost to the outermost. The WasmEdge C API provides no frames for now, so it is always empty.
+        trace: Vec<String>,
+    },
+}
+
+// Formats the frames of a trapped call, one per line after the trap kind.
+fn display_trace(trace: &[String]) -> String {
+    trace
+        .iter()
+        .enumerate()
+        .map(|(idx, frame)| format!("\n  {}: {}", idx, frame))
+        .collect()
 }
 
 /// The error type for the common errors from WasmEdge Core.
@@ -422,3 +580,131 @@ pub enum CoreExecutionError {
     #[error("reference type mismatch")]
     RefTypeMismatch,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_trap_trace() {
+        // the trace is empty if the engine provides no frames
+        let err = CoreError::Execution {
+            kind: CoreExecutionError::DivideByZero,
+            trace: vec![],
+        };
+        assert_eq!(err.to_string(), "integer divide by zero");
+
+        // the frames are listed after the trap kind
+        let err = CoreError::Execution {
+            kind: CoreExecutionError::DivideByZero,
+            trace: vec![String::from("div"), String::from("call_div")],
+        };
+        match &err {
+            CoreError::Execution { trace, .. } => assert_eq!(trace.len(), 2),
+            _ => unreachable!(),
+        }
+        assert_eq!(
+            err.to_string(),
+            "integer divide by zero\n  0: div\n  1: call_div"
+        );
+    }
+
+    #[test]
+    fn test_error_retryability() {
+        // resource and allocation errors
+        assert_eq!(
+            WasmEdgeError::ExecutorCreate.retryability(),
+            Retryability::Transient
+        );
+        assert_eq!(
+            WasmEdgeError::Func(FuncError::CreateBinding(String::from(
+                "The number of the host functions reaches the upper bound: 500"
+            )))
+            .retryability(),
+            Retryability::Transient
+        );
+        assert_eq!(
+            WasmEdgeError::Core(CoreError::Common(CoreCommonError::Interrupted)).retryability(),
+            Retryability::Transient
+        );
+        assert!(WasmEdgeError::Mem(MemError::Create)
+            .retryability()
+            .is_retryable());
+        assert_eq!(
+            WasmEdgeError::Func(FuncError::HostTrap(HostTrap::Timeout)).retryability(),
+            Retryability::Transient
+        );
+
+        // guest traps
+        assert_eq!(
+            WasmEdgeError::Func(FuncError::Trapped {
+                name: String::from("db_query"),
+                error: Box::new(WasmEdgeError::Core(CoreError::Execution {
+                    kind: CoreExecutionError::MemoryOutOfBounds,
+                    trace: vec![],
+                })),
+            })
+            .retryability(),
+            Retryability::GuestTrap
+        );
+        assert_eq!(
+            WasmEdgeError::Core(CoreError::Execution {
+                kind: CoreExecutionError::Unreachable,
+                trace: vec![],
+            })
+            .retryability(),
+            Retryability::GuestTrap
+        );
+        assert_eq!(
+            WasmEdgeError::Core(CoreError::Execution {
+                kind: CoreExecutionError::MemoryOutOfBounds,
+                trace: vec![],
+            })
+            .retryability(),
+            Retryability::GuestTrap
+        );
+        assert!(!WasmEdgeError::Core(CoreError::Execution {
+            kind: CoreExecutionError::DivideByZero,
+            trace: vec![],
+        })
+        .retryability()
+        .is_retryable());
+
+        // fatal errors
+        assert_eq!(
+            WasmEdgeError::Core(CoreError::Load(CoreLoadError::MalformedMagic)).retryability(),
+            Retryability::Fatal
+        );
+        assert_eq!(
+            WasmEdgeError::Core(CoreError::Common(CoreCommonError::CostLimitExceeded))
+                .retryability(),
+            Retryability::Fatal
+        );
+        assert_eq!(
+            WasmEdgeError::Vm(VmError::NotFoundFuncType(String::from("add"))).retryability(),
+            Retryability::Fatal
+        );
+
+        // host function failures and host API misuses are not guest traps
+        for kind in [
+            CoreExecutionError::ExecutionFailed,
+            CoreExecutionError::FuncTypeMismatch,
+            CoreExecutionError::WrongInstanceAddress,
+        ] {
+            assert_eq!(
+                WasmEdgeError::Core(CoreError::Execution {
+                    kind,
+                    trace: vec![],
+                })
+                .retryability(),
+                Retryability::Fatal
+            );
+        }
+        for trap in [HostTrap::Panicked, HostTrap::ResultTooLarge] {
+            assert_eq!(
+                WasmEdgeError::Func(FuncError::HostTrap(trap)).retryability(),
+                Retryability::Fatal
+            );
+        }
+    }
+}